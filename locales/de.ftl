@@ -0,0 +1,500 @@
+# Deutsche Meldungen. Die Schlüssel entsprechen denen in en.ftl.
+
+language-name = Deutsch
+
+## Hauptfenster
+
+button-import = Importieren...
+button-path-roots = Pfadwurzeln...
+button-sources = Quellen...
+button-switch = Wechseln...
+button-doctor = Diagnose...
+button-log = Protokoll...
+button-settings = Einstellungen...
+button-create-workspace =
+    Neuer
+    Arbeitsbereich
+button-create-from-tabs =
+    Aus offenen
+    Tabs erstellen
+button-create-from-scan =
+    Aus Suche
+    erstellen...
+button-create-dynamic =
+    Dynamischer
+    Arbeitsbereich
+button-pull = Pull
+button-commit = Commit
+button-remove = Entfernen
+button-export = Exportieren...
+button-merge = Zusammenführen
+button-preview-repos = Repos anzeigen
+button-remove-workspace = Arbeitsbereich entfernen
+button-copy-to-mine = Zu meinen kopieren
+button-fetch-all = Alle fetchen
+button-pull-all = Alle pullen
+button-status = Status
+button-checkout = Auschecken
+button-create-branch = Branch erstellen
+button-take-snapshot = Snapshot erstellen
+button-restore = Wiederherstellen
+button-delete = Löschen
+button-export-now = Jetzt exportieren
+button-open-workspace = Arbeitsbereich öffnen
+button-open-in = Öffnen in...
+button-cancel = Abbrechen
+button-continue = Fortfahren
+button-stash-and-continue = Stashen und fortfahren
+button-run-again = Erneut prüfen
+button-copy-report = Bericht kopieren
+button-close = Schließen
+button-copy = Kopieren
+button-copy-diagnostics = Diagnose kopieren
+button-export-confirm = Exportieren
+button-reload = Neu laden
+button-keep-mine = Meine behalten
+button-keep-all-mine = Alle meine behalten
+button-use-theirs = Ihre übernehmen
+button-add = Hinzufügen
+button-add-command = Befehl hinzufügen
+button-add-rewrite = Umschreibung hinzufügen
+button-load = Laden
+button-import-confirm = Importieren
+button-restore-and-open = Wiederherstellen und öffnen
+button-open-without-restoring = Ohne Wiederherstellen öffnen
+button-save = Speichern
+button-rule-folder = Ordner
+button-rule-bookmark-folder = Lesezeichenordner
+button-rule-remote = Remote
+button-stop = Anhalten
+button-scan = Suchen
+button-select-all = Alle
+button-select-none = Keine
+button-create-scanned = Arbeitsbereich erstellen
+button-reset-to-defaults = Standard wiederherstellen
+
+list-sort = Sortierung
+list-filter = Nach Name, Notizen oder Symbol filtern
+filter-tags = Tags
+filter-show-archived = Archivierte anzeigen
+filter-presets = Vorlagen
+filter-clear = Filter zurücksetzen
+filter-preset-name = Name der Vorlage
+button-save-preset = Vorlage speichern
+list-empty = Noch keine Arbeitsbereiche vorhanden...
+group-shared-read-only = Geteilt (schreibgeschützt)
+bulk-selected = { $count } ausgewählt
+sort-name = Name
+sort-name-descending = Name (Z-A)
+sort-repo-count = Anzahl Repos
+source-personal = Meine
+source-team = Geteilt
+source-project = Projekt { $name }
+workspace-new-name = Neuer Arbeitsbereich
+workspace-new-dynamic-name = Neuer dynamischer Arbeitsbereich
+workspace-last-name = Letzter Arbeitsbereich
+hook-new-name = Neuer Hook
+
+details-name = Name{" "}
+details-source = Quelle: { $source }
+details-rules = Regeln
+details-icon = Symbol
+details-color = Farbe
+details-tags = Tags
+details-new-tag = Neuer Tag
+details-no-color = Keine
+details-notes-hint = Notizen in Markdown: wofür er gedacht ist, Ticket-Links...
+color-red = Rot
+color-orange = Orange
+color-yellow = Gelb
+color-green = Grün
+color-blue = Blau
+color-purple = Lila
+color-grey = Grau
+details-resolves-to =
+    { $count ->
+        [one] Ergibt { $count } Repo
+       *[other] Ergibt { $count } Repos
+    }
+details-resolving = Wird ermittelt...
+git-heading = Git
+git-branch = Branch{" "}
+snapshots-heading = Snapshots ({ $count })
+snapshots-label = Bezeichnung{" "}
+hooks-heading = Hooks ({ $count })
+environment-heading = Umgebung ({ $count })
+open-with-heading = Öffnen mit
+exports-heading = IDE-Exporte ({ $count })
+settings-path = SourceTree-Einstellungspfad: { $path }
+settings-path-missing = ** SourceTree-Einstellungspfad NICHT gefunden. Bitte SourceTree installieren. **
+
+## Statusmeldungen
+
+status-error-writing = Fehler beim Schreiben auf die Festplatte.
+status-error-importing = Fehler beim Schreiben der importierten Arbeitsbereiche.
+status-imported =
+    { $count ->
+        [one] { $count } Arbeitsbereich importiert.
+       *[other] { $count } Arbeitsbereiche importiert.
+    }
+status-error-saving-roots = Fehler beim Schreiben der Pfadwurzeln.
+status-saved-roots = Pfadwurzeln gespeichert.
+status-error-saving-settings = Fehler beim Speichern der Einstellungen.
+status-saved-settings = Einstellungen gespeichert.
+status-exported =
+    { $count ->
+        [one] { $count } Arbeitsbereich nach { $path } exportiert.
+       *[other] { $count } Arbeitsbereiche nach { $path } exportiert.
+    }
+status-error-exporting = Fehler beim Exportieren der Arbeitsbereiche.
+status-error-creating-from-scan = Fehler beim Erstellen des Arbeitsbereichs aus den gefundenen Repositories
+status-created-from-scan = Arbeitsbereich aus den gefundenen Repositories erstellt
+status-tabs-saved = Tabs von SourceTree gespeichert.
+status-tabs-merged = Tabs von SourceTree mit den Änderungen hier zusammengeführt.
+status-tabs-kept = Die Tabs in SourceTree haben sich geändert, die Änderungen hier wurden aber behalten.
+status-error-saving-tabs = Fehler beim Speichern der Tabs von SourceTree.
+status-merged-external = Anderswo vorgenommene Änderungen an den Arbeitsbereichen wurden zusammengeführt.
+status-merged-external-conflicts =
+    { $count ->
+        [one] Anderswo vorgenommene Änderungen an den Arbeitsbereichen wurden zusammengeführt, mit { $count } Konflikt.
+       *[other] Anderswo vorgenommene Änderungen an den Arbeitsbereichen wurden zusammengeführt, mit { $count } Konflikten.
+    }
+status-error-recording-stash = Fehler beim Festhalten der gestashten Änderungen.
+status-stashed = Nicht committete Änderungen gestasht.
+status-stash-failed =
+    { $count ->
+        [one] { $count } Repo konnte nicht gestasht werden, daher wurde der Wechsel abgebrochen.
+       *[other] { $count } Repos konnten nicht gestasht werden, daher wurde der Wechsel abgebrochen.
+    }
+status-error-writing-snapshot = Fehler beim Schreiben des Snapshots.
+status-took-snapshot = Snapshot der ausgecheckten Branches erstellt.
+status-restored-snapshot = Ausgecheckte Branches wiederhergestellt.
+status-restore-failed =
+    { $count ->
+        [one] { $count } Repo konnte nicht wiederhergestellt werden.
+       *[other] { $count } Repos konnten nicht wiederhergestellt werden.
+    }
+status-error-saving-sources = Fehler beim Schreiben der Arbeitsbereichsquellen.
+status-reloaded-sources = Arbeitsbereichsquellen neu geladen.
+status-error-loading-sources = Arbeitsbereichsquellen konnten nicht geladen werden.
+status-error-loading-config = Die lokale Konfiguration konnte nicht gelesen werden, daher werden Pfadwurzeln nicht aufgelöst.
+status-error-loading-preferences = Die Einstellungen konnten nicht gelesen werden, daher gelten Standardwerte, die erst beim Speichern der Einstellungen gespeichert werden.
+status-resolving-repos = Die Repositorys von { $name } werden gesucht...
+status-pulled-catalogue = Geteilten Katalog gepullt.
+status-error-pulling-catalogue = Geteilter Katalog konnte nicht gepullt werden.
+status-committed-catalogue = Geteilten Katalog committet.
+status-nothing-to-commit = Keine geteilten Änderungen zum Committen.
+status-error-committing-catalogue = Geteilter Katalog konnte nicht committet werden.
+status-error-copying = Fehler beim Schreiben des kopierten Arbeitsbereichs.
+status-copied = Geteilten Arbeitsbereich zu meinen kopiert.
+status-error-creating = Fehler beim Erstellen des neuen Arbeitsbereichs
+status-created = Neuer Arbeitsbereich erstellt
+status-error-creating-dynamic = Fehler beim Erstellen des dynamischen Arbeitsbereichs
+status-created-dynamic = Dynamischer Arbeitsbereich erstellt
+status-error-loading-tabs = Die offenen Tabs konnten nicht geladen werden, um einen Arbeitsbereich zu erstellen.
+status-error-workspace-gone = Der zu öffnende Arbeitsbereich existiert nicht mehr.
+status-error-creating-from-tabs = Fehler beim Erstellen des Arbeitsbereichs aus den offenen Tabs
+status-created-from-tabs = Arbeitsbereich aus den offenen Tabs erstellt
+status-error-deleting = Fehler beim Löschen des Arbeitsbereichs
+status-deleted =
+    { $count ->
+        [one] Arbeitsbereich gelöscht
+       *[other] { $count } Arbeitsbereiche gelöscht
+    }
+status-error-merging = Fehler beim Zusammenführen der Arbeitsbereiche
+status-merged = { $count } Arbeitsbereiche zusammengeführt
+status-opened-in = { $name } in { $launcher } geöffnet.
+status-error-opening-in = Öffnen in { $launcher } fehlgeschlagen.
+status-opened-with-failures = Geöffnet, aber einiges ist fehlgeschlagen: { $failed }
+switch-failed-unstash = Wiederherstellen von Stashes ({ $count } Repositorys)
+
+instance-showed = Fenster angezeigt.
+instance-updated = Aktueller Arbeitsbereich aktualisiert.
+instance-update-failed = Der aktuelle Arbeitsbereich konnte nicht aktualisiert werden. '{ $error }'
+instance-no-answer = Die laufende Instanz hat nicht rechtzeitig geantwortet.
+instance-opening = Wechsle zu { $name }...
+notification-switched =
+    { $count ->
+        [one] Zu { $name } gewechselt ({ $count } Repo).
+       *[other] Zu { $name } gewechselt ({ $count } Repos).
+    }
+tray-switch = Arbeitsbereich wechseln...
+tray-quit = Beenden
+
+## Ergebnisse von Repos und Hooks
+
+repo-not-found = Repo nicht gefunden
+repo-fetched = Abgerufen
+repo-pulled = Gepullt
+repo-status = { $branch }, { $count ->
+    [0] sauber
+    [one] 1 Änderung
+   *[other] { $count } Änderungen
+}
+repo-already-on-branch = Bereits auf dem Branch
+repo-no-such-branch = Branch existiert nicht
+repo-checked-out = { $branch } ausgecheckt
+repo-branch-exists = Branch existiert bereits
+repo-created-branch = { $branch } erstellt
+repo-already-checked-out = Bereits ausgecheckt
+repo-uncommitted-changes = Nicht committete Änderungen, daher nicht zu { $branch } gewechselt
+repo-error-reading-status = Status konnte nicht gelesen werden
+repo-conflict-checking-out = Konflikt beim Auschecken von { $branch }
+repo-invalid-target = Kein Branch oder Commit, der ausgecheckt werden kann
+repo-nothing-to-stash = Nichts zu stashen
+repo-not-stashed = { $state }, nicht gestasht
+repo-error-stashing = Stash fehlgeschlagen
+repo-error-listing-stashes = Stashes konnten nicht aufgelistet werden
+repo-stash-gone = Stash existiert nicht mehr
+repo-restored-stash = Gestashte Änderungen wiederhergestellt
+repo-conflict-restoring-stash = Konflikt beim Wiederherstellen des Stashes, er wurde behalten
+hook-started = Gestartet
+hook-error-starting = Konnte nicht gestartet werden. { $error }
+hook-timed-out = Nach { $seconds } s abgebrochen
+hook-finished = Fertig
+hook-exited = Mit { $code } beendet
+hook-error-running = Konnte nicht ausgeführt werden. { $error }
+## Dialoge
+
+batch-title = { $operation } - { $name }
+batch-progress = { $title }: { $done } von { $count } Repos erledigt.
+batch-finished = { $title }: fertig, { $failed } fehlgeschlagen.
+batch-repo = Repo
+batch-result = Ergebnis
+batch-details = Details
+outcome-running = läuft
+outcome-succeeded = ok
+outcome-skipped = übersprungen
+outcome-failed = fehlgeschlagen
+
+delete-title = Arbeitsbereich entfernen?
+delete-message = { $name } entfernen? Das lässt sich nicht rückgängig machen.
+delete-several = { $count } Arbeitsbereiche
+
+dirty-guard-title = Unfertige Arbeit
+dirty-guard-message = Diese Repos in { $name } haben nicht committete Arbeit.
+dirty-guard-stash-note = Gestashte Änderungen werden wiederhergestellt, wenn der Arbeitsbereich erneut geöffnet wird.
+dirty-guard-open-tabs = den offenen Tabs
+stash-title = Stash - { $name }
+restore-title = Wiederherstellen - { $name }
+unstash-title = Stash wiederherstellen - { $name }
+
+doctor-title = Diagnose
+doctor-failed = Einige Prüfungen sind fehlgeschlagen, daher funktioniert das Wechseln eventuell nicht.
+doctor-passed = Nichts hindert die Arbeitsbereiche am Wechseln.
+doctor-fix = Lösung: { $fix }
+doctor-report-title = st-workspaces { $version } Diagnose
+
+error-report-details = Details
+
+export-title = Arbeitsbereiche exportieren
+export-message =
+    { $count ->
+        [one] { $count } Arbeitsbereich exportieren nach
+       *[other] { $count } Arbeitsbereiche exportieren nach
+    }
+exports-add = Export hinzufügen:
+
+external-change-title = Arbeitsbereiche geändert
+external-change-message = st-workspaces.json wurde außerhalb dieses Fensters geändert.
+external-change-reload-note = Beim Neuladen bleiben die Änderungen hier erhalten und die dort vorgenommenen werden übernommen. Arbeitsbereiche, die an beiden Stellen bearbeitet wurden, werden danach angezeigt.
+
+hooks-command = Befehl
+hooks-working-dir = In
+hooks-detached = Nicht warten
+hooks-timeout = Zeitlimit
+hooks-add = Hook hinzufügen:
+hook-after-open = Nach dem Öffnen
+hook-before-close = Vor dem Schließen
+hook-trust-title = Hooks ausführen?
+hook-trust-message = Diese Hooks stammen aus einem geteilten Katalog oder einer Projektdatei, wurden also von jemand anderem geschrieben. Sie laufen mit den Umgebungsvariablen dieses Arbeitsbereichs.
+button-trust-hooks = Vertrauen und ausführen
+button-skip-hooks = Ohne Hooks wechseln
+hook-run-title = Hooks laufen
+hook-run-message = Die Hooks „{ $event }“ von { $name } laufen...
+environment-variable = Variable{" "}
+
+import-title = Arbeitsbereiche importieren
+import-file = Datei{" "}
+import-error = Die zu importierenden Arbeitsbereiche konnten nicht geladen werden. '{ $error }'
+import-remaps = Pfadpräfixe umschreiben (${"{"}NAME{"}"} für eine Pfadwurzel)
+import-found = gefunden
+import-missing = fehlt
+
+launchers-new-command = Neuer Befehl
+launchers-placeholders = {"{"}repo{"}"}, {"{"}repos{"}"} und {"{"}name{"}"} werden ausgefüllt.
+launcher-terminal = Terminal
+launcher-file-manager = Dateimanager
+
+log-title = Protokoll
+log-no-directory = Das Protokollverzeichnis wurde nicht gefunden.
+
+merge-conflicts-title = Konflikte beim Zusammenführen
+merge-conflicts-message = Diese Arbeitsbereiche wurden sowohl hier als auch in st-workspaces.json geändert.
+merge-conflicts-mine = Meine: { $workspace }
+merge-conflicts-theirs = Ihre: { $workspace }
+merge-conflicts-workspace = { $name } mit { $count } Repos: { $repos }
+merge-conflicts-deleted = gelöscht
+
+switcher-title = Arbeitsbereich wechseln
+
+restore-prompt-title = Branches wiederherstellen?
+restore-prompt-message = Die Branches aus einem Snapshot von { $name } vor dem Öffnen auschecken?
+snapshot-description = { $taken_at } ({ $count } Repos)
+
+roots-title = Pfadwurzeln
+
+rules-repos-under = Repos unter
+rules-depth = Tiefe
+rules-bookmark-folder = Lesezeichen im Ordner
+rules-remote-matches = Lesezeichen mit Remote
+rules-add = Regel hinzufügen:
+rule-repos-under = Repos unter { $root } (Tiefe { $depth })
+rule-bookmark-folder = Lesezeichen im Ordner { $folder }
+rule-remote-matches = Lesezeichen mit einem Remote passend zu { $pattern }
+
+scan-workspace-name = Gefundener Arbeitsbereich
+scan-progress = Suche... { $visited } Ordner, { $count } Repos gefunden. { $path }
+scan-finished = { $visited } Ordner durchsucht, { $count } Repos gefunden.
+scan-title = Nach Repositories suchen
+scan-root = Wurzel{" "}
+scan-depth = Tiefe{" "}
+scan-ignore = Ignorieren{" "}
+
+sources-title = Arbeitsbereichsquellen
+sources-catalogue = Geteilter Katalog
+sources-catalogue-note = Ein Verzeichnis oder Git-Checkout mit der st-workspaces.json des Teams.
+sources-directory = Verzeichnis{" "}
+sources-read-only = Schreibgeschützt
+sources-project-repos = Projekt-Repos
+sources-project-repos-note = Repos mit einer committeten .st-workspace, die ihre benachbarten Repos beschreibt.
+
+git-fetch = Fetch
+git-pull = Pull
+git-status = Status
+git-checkout = { $branch } auschecken
+git-create-branch = Branch { $branch } erstellen
+repo-state-changes =
+    { $count ->
+        [one] { $count } nicht committete Änderung
+       *[other] { $count } nicht committete Änderungen
+    }
+repo-state-in-progress = { $operation } läuft
+repo-state-stashes = { $count } Stashes
+repo-state-clean = sauber
+
+## Einstellungen
+
+settings-title = Einstellungen
+settings-language = Sprache
+settings-language-system = Wie das System
+settings-theme = Design
+settings-font-scale = Schriftgröße
+settings-window-size = Fenstergröße
+settings-window-position = Fensterposition
+settings-centred = Zentriert
+settings-close-timeout = Zeitlimit beim Schließen
+settings-confirm-delete = Löschen bestätigen
+settings-confirm-delete-description = Vor dem Entfernen eines Arbeitsbereichs nachfragen
+settings-sourcetree = SourceTree
+settings-default-launcher = Standardprogramm
+theme-dark = Dunkel
+theme-light = Hell
+theme-high-contrast = Hoher Kontrast
+theme-system = Wie das System
+settings-error-font-scale = Die Schriftgröße muss zwischen { $min } und { $max } liegen.
+settings-error-window-size = Das Fenster muss mindestens { $width }x{ $height } groß sein.
+settings-error-language = Es gibt keine Übersetzung für '{ $language }'.
+settings-error-unknown = Es gibt keine Einstellung namens '{ $key }'.
+settings-error-value = '{ $value }' ist kein gültiger Wert für { $key }.
+
+## Kommandozeile
+
+cli-instance-running = Eine andere Instanz läuft noch.
+cli-settings-usage = Aufruf: st-workspaces settings [get <key> | set <key> <value> | reset [key]]
+cli-ui-failed = Fehler beim Ausführen der Oberfläche
+cli-list-usage = Aufruf: st-workspaces list [--tag <tag>]... [--all]
+cli-open-usage = Aufruf: st-workspaces open [name] [--tag <tag>]... [--all]
+cli-no-match = Kein Arbeitsbereich passt. Archivierte Arbeitsbereiche werden nur mit --all oder --tag archived gefunden.
+cli-several-match = { $count } Arbeitsbereiche passen: { $names }. Einen Namen oder ein weiteres --tag angeben, um einen auszuwählen.
+
+## Fehler
+
+error-path-not-found = { $what } wurde nicht gefunden. Ist SourceTree installiert?
+error-io =
+    { $operation ->
+        [read] { $path } konnte nicht gelesen werden. { $error }
+        [write] { $path } konnte nicht geschrieben werden. { $error }
+        [open] { $path } konnte nicht geöffnet werden. { $error }
+       *[other] { $operation } von { $path } fehlgeschlagen. { $error }
+    }
+error-parse = { $path } konnte nicht als { $format } gelesen werden{ $position }. { $error }
+error-process-exit = { $command } wurde mit { $code } beendet. '{ $output }'
+error-launch = { $program } konnte nicht gestartet werden. { $error }
+error-writing-open-tabs = Die offenen Tabs konnten nicht geschrieben werden, daher kann SourceTree nicht gestartet werden.
+error-launching-sourcetree = SourceTree konnte nicht gestartet werden. Ist es installiert?
+error-waiting-for-sourcetree = Der Wechsel zu SourceTree wurde nicht vollständig abgeschlossen. Prozessfehler?
+error-catalogue-read-only = Der geteilte Katalog ist schreibgeschützt.
+error-catalogue-not-git = Der geteilte Katalog ist kein Git-Repository.
+error-position-column = {" "}in Zeile { $line }, Spalte { $column }
+error-position-line = {" "}in Zeile { $line }
+error-exit-code = Code { $code }
+error-no-exit-code = keinem Exit-Code
+error-detail-missing = Fehlt
+error-detail-operation = Vorgang
+error-detail-path = Pfad
+error-detail-kind = Art
+error-detail-format = Format
+error-detail-line = Zeile
+error-detail-column = Spalte
+error-detail-message = Meldung
+error-detail-command = Befehl
+error-detail-exit-code = Exit-Code
+error-detail-output = Ausgabe
+error-detail-program = Programm
+diagnostics-error = Fehler: { $error }
+diagnostics-caused-by = Ursache: { $cause }
+
+## Diagnoseprüfungen
+
+check-path-missing = { $path } existiert nicht.
+check-path-missing-yet = { $path } existiert noch nicht.
+check-settings-path = Einstellungspfad
+check-settings-path-fix = SourceTree installieren und einmal öffnen, damit es seine Einstellungen anlegt.
+check-settings-path-no-app-data = Das lokale App-Data-Verzeichnis wurde nicht gefunden.
+check-settings-path-no-app-data-fix = st-workspaces als der Benutzer ausführen, für den SourceTree installiert ist.
+check-open-tabs = Offene Tabs
+check-open-tabs-missing-fix = Ein Repo in SourceTree öffnen und SourceTree schließen, damit es seine Tabs speichert.
+check-open-tabs-pass = { $count } Tabs in { $path }
+check-open-tabs-fail-fix = SourceTree schließen und die Datei löschen. SourceTree schreibt beim Schließen eine neue.
+check-workspaces-file = Arbeitsbereichsdatei
+check-workspaces-file-missing-fix = Einen Arbeitsbereich erstellen, um die Datei anzulegen.
+check-workspaces-file-fail-fix = Die Datei an der angegebenen Zeile korrigieren oder aus einer Sicherung wiederherstellen.
+check-workspaces-file-pass = { $count } Arbeitsbereiche in { $path }
+check-workspaces-file-problems-fix = Die Datei korrigieren oder die betroffenen Arbeitsbereiche im Fenster erneut speichern.
+check-problem-wrong-id = Arbeitsbereich { $uuid } ist unter der ID { $id } gespeichert.
+check-problem-no-name = Arbeitsbereich { $uuid } hat keinen Namen.
+check-problem-missing-current = Der aktuelle Arbeitsbereich { $uuid } existiert nicht.
+check-custom-actions = Benutzerdefinierte Aktionen
+check-custom-actions-install-fix = In SourceTree unter Tools > Options > Custom Actions eine Aktion mit dem Skript { $path } und den Parametern auto-update-and-close hinzufügen.
+check-custom-actions-missing = SourceTree hat keine benutzerdefinierten Aktionen.
+check-custom-actions-fail-fix = SourceTree schließen, die Datei korrigieren oder löschen und die Aktionen erneut hinzufügen.
+check-custom-actions-none = Keine davon führt st-workspaces aus.
+check-custom-actions-runs = { $caption } führt { $target } aus
+check-custom-actions-elsewhere = { $actions }, nicht { $path }.
+check-custom-actions-elsewhere-fix = Das Skript dieser Aktionen auf { $path } ändern.
+check-custom-actions-pass = { $count } Aktionen führen { $path } aus
+check-sourcetree = SourceTree
+check-sourcetree-install-fix = SourceTree { $version } für diesen Benutzer installieren oder mit `st-workspaces settings set exec_path <path>` angeben, wo es liegt.
+check-sourcetree-missing = { $path } wurde nicht gefunden.
+check-sourcetree-pass = { $version } unter { $path }
+check-sourcetree-untested = Version { $version } wurde nicht mit st-workspaces { $app_version } getestet.
+check-sourcetree-supported = Das Wechseln funktioniert bekanntermaßen mit SourceTree { $version }.
+check-sourcetree-unknown-version = Die Version von { $path } konnte nicht ermittelt werden.
+check-log-dir = Protokollverzeichnis
+check-log-dir-fail = In { $path } konnte nicht geschrieben werden. { $error }
+check-log-dir-fail-fix = Sicherstellen, dass dieser Benutzer in das Verzeichnis schreiben darf, oder Speicherplatz freigeben.
+check-repo-paths = Repo-Pfade
+check-repo-paths-pass = Alle { $count } Repo-Pfade existieren.
+check-repo-paths-missing = { $missing } von { $count } fehlen. { $paths }
+check-repo-paths-missing-fix = Sie aus ihren Arbeitsbereichen entfernen oder eine Pfadwurzel einrichten, falls die Repos verschoben wurden.
@@ -0,0 +1,459 @@
+# Messages shown in the window, the tray and on the command line. Every key here needs to be in
+# the other locales too, which the i18n tests check.
+
+language-name = English
+
+## Main window
+
+button-import = Import...
+button-path-roots = Path Roots...
+button-sources = Sources...
+button-switch = Switch...
+button-doctor = Doctor...
+button-log = Log...
+button-settings = Settings...
+button-create-workspace =
+    Create New
+    Workspace
+button-create-from-tabs =
+    Create from
+    Current Tabs
+button-create-from-scan =
+    Create from
+    Scan...
+button-create-dynamic =
+    Create Dynamic
+    Workspace
+button-pull = Pull
+button-commit = Commit
+button-remove = Remove
+button-export = Export...
+button-merge = Merge
+button-preview-repos = Preview Repos
+button-remove-workspace = Remove Workspace
+button-copy-to-mine = Copy to Mine
+button-fetch-all = Fetch All
+button-pull-all = Pull All
+button-status = Status
+button-checkout = Checkout
+button-create-branch = Create Branch
+button-take-snapshot = Take Snapshot
+button-restore = Restore
+button-delete = Delete
+button-export-now = Export Now
+button-open-workspace = Open Workspace
+button-open-in = Open in...
+button-cancel = Cancel
+button-continue = Continue
+button-stash-and-continue = Stash and Continue
+button-run-again = Run Again
+button-copy-report = Copy Report
+button-close = Close
+button-copy = Copy
+button-copy-diagnostics = Copy Diagnostics
+button-export-confirm = Export
+button-reload = Reload
+button-keep-mine = Keep Mine
+button-keep-all-mine = Keep All Mine
+button-use-theirs = Use Theirs
+button-add = Add
+button-add-command = Add Command
+button-add-rewrite = Add Rewrite
+button-load = Load
+button-import-confirm = Import
+button-restore-and-open = Restore and Open
+button-open-without-restoring = Open Without Restoring
+button-save = Save
+button-rule-folder = Folder
+button-rule-bookmark-folder = Bookmark Folder
+button-rule-remote = Remote
+button-stop = Stop
+button-scan = Scan
+button-select-all = All
+button-select-none = None
+button-create-scanned = Create Workspace
+button-reset-to-defaults = Reset to Defaults
+
+list-sort = Sort
+list-filter = Filter by name, notes or icon
+filter-tags = Tags
+filter-show-archived = Show archived
+filter-presets = Presets
+filter-clear = Clear filter
+filter-preset-name = Preset name
+button-save-preset = Save Preset
+list-empty = No workspaces exist... yet...
+group-shared-read-only = Shared (read-only)
+bulk-selected = { $count } selected
+sort-name = Name
+sort-name-descending = Name (Z-A)
+sort-repo-count = Repo Count
+source-personal = Mine
+source-team = Shared
+source-project = Project { $name }
+workspace-new-name = New Workspace
+workspace-new-dynamic-name = New Dynamic Workspace
+workspace-last-name = Last Workspace
+hook-new-name = New Hook
+
+details-name = Name{" "}
+details-source = Source: { $source }
+details-rules = Rules
+details-icon = Icon
+details-color = Color
+details-tags = Tags
+details-new-tag = New tag
+details-no-color = None
+details-notes-hint = Notes, in Markdown: what it's for, ticket links...
+color-red = Red
+color-orange = Orange
+color-yellow = Yellow
+color-green = Green
+color-blue = Blue
+color-purple = Purple
+color-grey = Grey
+details-resolves-to = Resolves to { $count } repos
+details-resolving = Resolving...
+git-heading = Git
+git-branch = Branch{" "}
+snapshots-heading = Snapshots ({ $count })
+snapshots-label = Label{" "}
+hooks-heading = Hooks ({ $count })
+environment-heading = Environment ({ $count })
+open-with-heading = Open With
+exports-heading = IDE Exports ({ $count })
+settings-path = SourceTree Settings Path: { $path }
+settings-path-missing = ** SourceTree Settings Path NOT found. Try installing SourceTree. **
+
+## Status messages
+
+status-error-writing = Error occurred writing to disk.
+status-error-importing = Error writing imported workspaces.
+status-imported = Imported { $count } workspaces.
+status-error-saving-roots = Error writing path roots.
+status-saved-roots = Saved path roots.
+status-error-saving-settings = Error saving settings.
+status-saved-settings = Saved settings.
+status-exported = Exported { $count } workspaces to { $path }.
+status-error-exporting = Error exporting workspaces.
+status-error-creating-from-scan = Error creating workspace from scanned repositories
+status-created-from-scan = Created workspace from scanned repositories
+status-tabs-saved = Saved SourceTree's tabs.
+status-tabs-merged = Merged SourceTree's tabs with the edits made here.
+status-tabs-kept = SourceTree's tabs changed, but the edits made here were kept.
+status-error-saving-tabs = Error saving SourceTree's tabs.
+status-merged-external = Merged changes made to the workspaces elsewhere.
+status-merged-external-conflicts = Merged changes made to the workspaces elsewhere, with { $count } conflicts.
+status-error-recording-stash = Error recording stashed changes.
+status-stashed = Stashed uncommitted changes.
+status-stash-failed = { $count } repos couldn't be stashed, so the switch was stopped.
+status-error-writing-snapshot = Error writing snapshot.
+status-took-snapshot = Took snapshot of checked-out branches.
+status-restored-snapshot = Restored checked-out branches.
+status-restore-failed = { $count } repos couldn't be restored.
+status-error-saving-sources = Error writing workspace sources.
+status-reloaded-sources = Reloaded workspace sources.
+status-error-loading-sources = Couldn't load workspace sources.
+status-error-loading-config = Couldn't read the local config, so path roots aren't expanded.
+status-error-loading-preferences = Couldn't read the preferences, so defaults are used and won't be saved until Settings is.
+status-resolving-repos = Finding the repos of { $name }...
+status-pulled-catalogue = Pulled shared catalogue.
+status-error-pulling-catalogue = Couldn't pull shared catalogue.
+status-committed-catalogue = Committed shared catalogue.
+status-nothing-to-commit = No shared changes to commit.
+status-error-committing-catalogue = Couldn't commit shared catalogue.
+status-error-copying = Error writing copied workspace.
+status-copied = Copied shared workspace to mine.
+status-error-creating = Error creating new workspace
+status-created = Created new workspace
+status-error-creating-dynamic = Error creating dynamic workspace
+status-created-dynamic = Created dynamic workspace
+status-error-loading-tabs = Couldn't load current tabs to create workspace.
+status-error-workspace-gone = The workspace being opened no longer exists.
+status-error-creating-from-tabs = Error creating workspace from current tabs
+status-created-from-tabs = Created workspace from current tabs
+status-error-deleting = Error deleting workspace
+status-deleted =
+    { $count ->
+        [one] Deleted workspace
+       *[other] Deleted { $count } workspaces
+    }
+status-error-merging = Error merging workspaces
+status-merged = Merged { $count } workspaces
+status-opened-in = Opened { $name } in { $launcher }.
+status-error-opening-in = Couldn't open in { $launcher }.
+status-opened-with-failures = Opened, but some things failed: { $failed }
+switch-failed-unstash = restoring stashed changes ({ $count } repos)
+
+instance-showed = Showed the window.
+instance-updated = Updated the current workspace.
+instance-update-failed = Couldn't update the current workspace. '{ $error }'
+instance-no-answer = The running instance didn't answer in time.
+instance-opening = Switching to { $name }...
+notification-switched = Switched to { $name } ({ $count } repos).
+tray-switch = Switch Workspace...
+tray-quit = Quit
+
+## Repo and hook results
+
+repo-not-found = Repo not found
+repo-fetched = Fetched
+repo-pulled = Pulled
+repo-status = { $branch }, { $count ->
+    [0] clean
+    [one] 1 change
+   *[other] { $count } changes
+}
+repo-already-on-branch = Already on branch
+repo-no-such-branch = No such branch
+repo-checked-out = Checked out { $branch }
+repo-branch-exists = Branch already exists
+repo-created-branch = Created { $branch }
+repo-already-checked-out = Already checked out
+repo-uncommitted-changes = Uncommitted changes, didn't switch to { $branch }
+repo-error-reading-status = Couldn't read status
+repo-conflict-checking-out = Conflict checking out { $branch }
+repo-invalid-target = Not a branch or commit that can be checked out
+repo-nothing-to-stash = Nothing to stash
+repo-not-stashed = { $state }, not stashed
+repo-error-stashing = Couldn't stash
+repo-error-listing-stashes = Couldn't list stashes
+repo-stash-gone = Stash no longer exists
+repo-restored-stash = Restored stashed changes
+repo-conflict-restoring-stash = Conflict restoring stash, it was kept
+hook-started = Started
+hook-error-starting = Couldn't start. { $error }
+hook-timed-out = Timed out after { $seconds }s
+hook-finished = Finished
+hook-exited = Exited with { $code }
+hook-error-running = Couldn't run. { $error }
+## Dialogs
+
+batch-title = { $operation } - { $name }
+batch-progress = { $title }: { $done } of { $count } repos done.
+batch-finished = { $title }: finished, { $failed } failed.
+batch-repo = Repo
+batch-result = Result
+batch-details = Details
+outcome-running = running
+outcome-succeeded = ok
+outcome-skipped = skipped
+outcome-failed = failed
+
+delete-title = Remove Workspace?
+delete-message = Remove { $name }? This can't be undone.
+delete-several = { $count } workspaces
+
+dirty-guard-title = Unfinished Work
+dirty-guard-message = These repos in { $name } have work that hasn't been committed.
+dirty-guard-stash-note = Stashed changes are restored when the workspace is opened again.
+dirty-guard-open-tabs = the open tabs
+stash-title = Stash - { $name }
+restore-title = Restore - { $name }
+unstash-title = Restore stash - { $name }
+
+doctor-title = Doctor
+doctor-failed = Some checks failed, so switching workspaces may not work.
+doctor-passed = Nothing is stopping workspaces from switching.
+doctor-fix = Fix: { $fix }
+doctor-report-title = st-workspaces { $version } doctor
+
+error-report-details = Details
+
+export-title = Export Workspaces
+export-message = Export { $count } workspaces to
+exports-add = Add export:
+
+external-change-title = Workspaces Changed
+external-change-message = st-workspaces.json was changed outside this window.
+external-change-reload-note = Reloading keeps the edits made here and takes the ones made there. Workspaces edited in both are shown afterwards.
+
+hooks-command = Command
+hooks-working-dir = In
+hooks-detached = Don't wait
+hooks-timeout = Timeout
+hooks-add = Add hook:
+hook-after-open = After open
+hook-before-close = Before close
+hook-trust-title = Run Hooks?
+hook-trust-message = These hooks come from a shared catalogue or a project file, so someone else wrote them. They run with this workspace's environment variables.
+button-trust-hooks = Trust and Run
+button-skip-hooks = Switch Without Hooks
+hook-run-title = Running Hooks
+hook-run-message = Running the { $event } hooks of { $name }...
+environment-variable = Variable{" "}
+
+import-title = Import Workspaces
+import-file = File{" "}
+import-error = Couldn't load workspaces to import. '{ $error }'
+import-remaps = Rewrite path prefixes (use ${"{"}NAME{"}"} for a path root)
+import-found = found
+import-missing = missing
+
+launchers-new-command = New Command
+launchers-placeholders = {"{"}repo{"}"}, {"{"}repos{"}"} and {"{"}name{"}"} are filled in.
+launcher-terminal = Terminal
+launcher-file-manager = File Manager
+
+log-title = Log
+log-no-directory = Couldn't find the log directory.
+
+merge-conflicts-title = Merge Conflicts
+merge-conflicts-message = These workspaces were changed both here and in st-workspaces.json.
+merge-conflicts-mine = Mine: { $workspace }
+merge-conflicts-theirs = Theirs: { $workspace }
+merge-conflicts-workspace = { $name } with { $count } repos: { $repos }
+merge-conflicts-deleted = deleted
+
+switcher-title = Switch Workspace
+
+restore-prompt-title = Restore Branches?
+restore-prompt-message = Check out the branches { $name } had in a snapshot before opening it?
+snapshot-description = { $taken_at } ({ $count } repos)
+
+roots-title = Path Roots
+
+rules-repos-under = Repos under
+rules-depth = depth
+rules-bookmark-folder = Bookmarks in folder
+rules-remote-matches = Bookmarks with remote
+rules-add = Add rule:
+rule-repos-under = Repos under { $root } (depth { $depth })
+rule-bookmark-folder = Bookmarks in folder { $folder }
+rule-remote-matches = Bookmarks with a remote matching { $pattern }
+
+scan-workspace-name = Scanned Workspace
+scan-progress = Scanning... { $visited } folders, { $count } repos found. { $path }
+scan-finished = Scanned { $visited } folders, { $count } repos found.
+scan-title = Scan for Repositories
+scan-root = Root{" "}
+scan-depth = Depth{" "}
+scan-ignore = Ignore{" "}
+
+sources-title = Workspace Sources
+sources-catalogue = Shared catalogue
+sources-catalogue-note = A directory, or git checkout, holding the team's st-workspaces.json.
+sources-directory = Directory{" "}
+sources-read-only = Read-only
+sources-project-repos = Project repos
+sources-project-repos-note = Repos with a committed .st-workspace describing their sibling repos.
+
+git-fetch = Fetch
+git-pull = Pull
+git-status = Status
+git-checkout = Checkout { $branch }
+git-create-branch = Create branch { $branch }
+repo-state-changes = { $count } uncommitted changes
+repo-state-in-progress = { $operation } in progress
+repo-state-stashes = { $count } stashes
+repo-state-clean = clean
+
+## Settings
+
+settings-title = Settings
+settings-language = Language
+settings-language-system = Follow System
+settings-theme = Theme
+settings-font-scale = Font scale
+settings-window-size = Window size
+settings-window-position = Window position
+settings-centred = Centred
+settings-close-timeout = Close timeout
+settings-confirm-delete = Confirm delete
+settings-confirm-delete-description = Ask before removing a workspace
+settings-sourcetree = SourceTree
+settings-default-launcher = Default launcher
+theme-dark = Dark
+theme-light = Light
+theme-high-contrast = High Contrast
+theme-system = Follow System
+settings-error-font-scale = The font scale must be between { $min } and { $max }.
+settings-error-window-size = The window must be at least { $width }x{ $height }.
+settings-error-language = There's no translation for '{ $language }'.
+settings-error-unknown = There's no setting called '{ $key }'.
+settings-error-value = '{ $value }' isn't a valid value for { $key }.
+
+## Command line
+
+cli-instance-running = Another instance is still running.
+cli-settings-usage = Usage: st-workspaces settings [get <key> | set <key> <value> | reset [key]]
+cli-ui-failed = Error running ui
+cli-list-usage = Usage: st-workspaces list [--tag <tag>]... [--all]
+cli-open-usage = Usage: st-workspaces open [name] [--tag <tag>]... [--all]
+cli-no-match = No workspace matches. Archived workspaces are only found with --all or --tag archived.
+cli-several-match = { $count } workspaces match: { $names }. Give a name or another --tag to pick one.
+
+## Errors
+
+error-path-not-found = Couldn't find the { $what }. Is SourceTree installed?
+error-io = Couldn't { $operation } { $path }. { $error }
+error-parse = Couldn't parse { $path } as { $format }{ $position }. { $error }
+error-process-exit = { $command } exited with { $code }. '{ $output }'
+error-launch = Couldn't launch { $program }. { $error }
+error-writing-open-tabs = Couldn't write open tabs, so can't launch SourceTree.
+error-launching-sourcetree = Couldn't launch SourceTree. Is it installed?
+error-waiting-for-sourcetree = Couldn't switch to SourceTree fully. Process error?
+error-catalogue-read-only = The shared catalogue is read-only.
+error-catalogue-not-git = The shared catalogue isn't a git repository.
+error-position-column = {" "}at line { $line }, column { $column }
+error-position-line = {" "}at line { $line }
+error-exit-code = code { $code }
+error-no-exit-code = no exit code
+error-detail-missing = Missing
+error-detail-operation = Operation
+error-detail-path = Path
+error-detail-kind = Kind
+error-detail-format = Format
+error-detail-line = Line
+error-detail-column = Column
+error-detail-message = Message
+error-detail-command = Command
+error-detail-exit-code = Exit code
+error-detail-output = Output
+error-detail-program = Program
+diagnostics-error = Error: { $error }
+diagnostics-caused-by = Caused by: { $cause }
+
+## Doctor checks
+
+check-path-missing = { $path } doesn't exist.
+check-path-missing-yet = { $path } doesn't exist yet.
+check-settings-path = Settings path
+check-settings-path-fix = Install SourceTree and open it once, so that it creates its settings.
+check-settings-path-no-app-data = Couldn't find the local app data directory.
+check-settings-path-no-app-data-fix = Run st-workspaces as the user that SourceTree is installed for.
+check-open-tabs = Open tabs
+check-open-tabs-missing-fix = Open a repo in SourceTree and close it, so that it saves its tabs.
+check-open-tabs-pass = { $count } tabs in { $path }
+check-open-tabs-fail-fix = Close SourceTree and delete the file. SourceTree writes a new one when it closes.
+check-workspaces-file = Workspaces file
+check-workspaces-file-missing-fix = Create a workspace to start the file.
+check-workspaces-file-fail-fix = Fix the file at the line given, or restore it from a backup.
+check-workspaces-file-pass = { $count } workspaces in { $path }
+check-workspaces-file-problems-fix = Edit the file to fix these, or re-save the affected workspaces from the window.
+check-problem-wrong-id = Workspace { $uuid } is stored under id { $id }.
+check-problem-no-name = Workspace { $uuid } has no name.
+check-problem-missing-current = The current workspace { $uuid } doesn't exist.
+check-custom-actions = Custom actions
+check-custom-actions-install-fix = In SourceTree, go to Tools > Options > Custom Actions and add one with the script { $path } and the parameters auto-update-and-close.
+check-custom-actions-missing = SourceTree has no custom actions.
+check-custom-actions-fail-fix = Close SourceTree and fix or delete the file, then add the custom actions again.
+check-custom-actions-none = None of them run st-workspaces.
+check-custom-actions-runs = { $caption } runs { $target }
+check-custom-actions-elsewhere = { $actions }, not { $path }.
+check-custom-actions-elsewhere-fix = Change the script of these custom actions to { $path }.
+check-custom-actions-pass = { $count } actions run { $path }
+check-sourcetree = SourceTree
+check-sourcetree-install-fix = Install SourceTree { $version } for this user, or point to where it is with `st-workspaces settings set exec_path <path>`.
+check-sourcetree-missing = Couldn't find { $path }.
+check-sourcetree-pass = { $version } at { $path }
+check-sourcetree-untested = Version { $version } hasn't been tested with st-workspaces { $app_version }.
+check-sourcetree-supported = Switching is known to work with SourceTree { $version }.
+check-sourcetree-unknown-version = Couldn't tell the version of { $path }.
+check-log-dir = Log directory
+check-log-dir-fail = Couldn't write to { $path }. { $error }
+check-log-dir-fail-fix = Make sure this user can write to the directory, or free up some disk space.
+check-repo-paths = Repo paths
+check-repo-paths-pass = All { $count } repo paths exist.
+check-repo-paths-missing = { $missing } of { $count } are missing. { $paths }
+check-repo-paths-missing-fix = Remove them from their workspaces, or set up a path root if the repos have moved.
@@ -0,0 +1,504 @@
+# Messages en français. Les clés sont les mêmes que dans en.ftl.
+
+language-name = Français
+
+## Fenêtre principale
+
+button-import = Importer...
+button-path-roots = Racines de chemins...
+button-sources = Sources...
+button-switch = Changer...
+button-doctor = Diagnostic...
+button-log = Journal...
+button-settings = Paramètres...
+button-create-workspace =
+    Nouvel espace
+    de travail
+button-create-from-tabs =
+    Créer depuis
+    les onglets
+button-create-from-scan =
+    Créer depuis
+    une recherche...
+button-create-dynamic =
+    Espace de travail
+    dynamique
+button-pull = Pull
+button-commit = Commit
+button-remove = Retirer
+button-export = Exporter...
+button-merge = Fusionner
+button-preview-repos = Aperçu des dépôts
+button-remove-workspace = Retirer l'espace de travail
+button-copy-to-mine = Copier dans les miens
+button-fetch-all = Tout récupérer
+button-pull-all = Tout tirer
+button-status = État
+button-checkout = Extraire
+button-create-branch = Créer la branche
+button-take-snapshot = Prendre un instantané
+button-restore = Restaurer
+button-delete = Supprimer
+button-export-now = Exporter maintenant
+button-open-workspace = Ouvrir l'espace de travail
+button-open-in = Ouvrir dans...
+button-cancel = Annuler
+button-continue = Continuer
+button-stash-and-continue = Remiser et continuer
+button-run-again = Relancer
+button-copy-report = Copier le rapport
+button-close = Fermer
+button-copy = Copier
+button-copy-diagnostics = Copier le diagnostic
+button-export-confirm = Exporter
+button-reload = Recharger
+button-keep-mine = Garder les miens
+button-keep-all-mine = Garder tous les miens
+button-use-theirs = Prendre les leurs
+button-add = Ajouter
+button-add-command = Ajouter une commande
+button-add-rewrite = Ajouter une réécriture
+button-load = Charger
+button-import-confirm = Importer
+button-restore-and-open = Restaurer et ouvrir
+button-open-without-restoring = Ouvrir sans restaurer
+button-save = Enregistrer
+button-rule-folder = Dossier
+button-rule-bookmark-folder = Dossier de signets
+button-rule-remote = Distant
+button-stop = Arrêter
+button-scan = Rechercher
+button-select-all = Tous
+button-select-none = Aucun
+button-create-scanned = Créer l'espace de travail
+button-reset-to-defaults = Rétablir les valeurs par défaut
+
+list-sort = Tri
+list-filter = Filtrer par nom, notes ou icône
+filter-tags = Étiquettes
+filter-show-archived = Afficher les archivés
+filter-presets = Préréglages
+filter-clear = Effacer le filtre
+filter-preset-name = Nom du préréglage
+button-save-preset = Enregistrer le préréglage
+list-empty = Aucun espace de travail... pour l'instant...
+group-shared-read-only = Partagés (lecture seule)
+bulk-selected =
+    { $count ->
+        [one] { $count } sélectionné
+       *[other] { $count } sélectionnés
+    }
+sort-name = Nom
+sort-name-descending = Nom (Z-A)
+sort-repo-count = Nombre de dépôts
+source-personal = Les miens
+source-team = Partagés
+source-project = Projet { $name }
+workspace-new-name = Nouvel espace de travail
+workspace-new-dynamic-name = Nouvel espace de travail dynamique
+workspace-last-name = Dernier espace de travail
+hook-new-name = Nouveau hook
+
+details-name = Nom{" "}
+details-source = Source : { $source }
+details-rules = Règles
+details-icon = Icône
+details-color = Couleur
+details-tags = Étiquettes
+details-new-tag = Nouvelle étiquette
+details-no-color = Aucune
+details-notes-hint = Notes en Markdown : à quoi il sert, liens vers les tickets...
+color-red = Rouge
+color-orange = Orange
+color-yellow = Jaune
+color-green = Vert
+color-blue = Bleu
+color-purple = Violet
+color-grey = Gris
+details-resolves-to =
+    { $count ->
+        [one] Correspond à { $count } dépôt
+       *[other] Correspond à { $count } dépôts
+    }
+details-resolving = Résolution...
+git-heading = Git
+git-branch = Branche{" "}
+snapshots-heading = Instantanés ({ $count })
+snapshots-label = Libellé{" "}
+hooks-heading = Hooks ({ $count })
+environment-heading = Environnement ({ $count })
+open-with-heading = Ouvrir avec
+exports-heading = Exports IDE ({ $count })
+settings-path = Chemin des paramètres SourceTree : { $path }
+settings-path-missing = ** Chemin des paramètres SourceTree INTROUVABLE. Essayez d'installer SourceTree. **
+
+## Messages d'état
+
+status-error-writing = Erreur lors de l'écriture sur le disque.
+status-error-importing = Erreur lors de l'écriture des espaces de travail importés.
+status-imported =
+    { $count ->
+        [one] { $count } espace de travail importé.
+       *[other] { $count } espaces de travail importés.
+    }
+status-error-saving-roots = Erreur lors de l'écriture des racines de chemins.
+status-saved-roots = Racines de chemins enregistrées.
+status-error-saving-settings = Erreur lors de l'enregistrement des paramètres.
+status-saved-settings = Paramètres enregistrés.
+status-exported =
+    { $count ->
+        [one] { $count } espace de travail exporté vers { $path }.
+       *[other] { $count } espaces de travail exportés vers { $path }.
+    }
+status-error-exporting = Erreur lors de l'export des espaces de travail.
+status-error-creating-from-scan = Erreur lors de la création de l'espace de travail à partir des dépôts trouvés
+status-created-from-scan = Espace de travail créé à partir des dépôts trouvés
+status-tabs-saved = Onglets de SourceTree enregistrés.
+status-tabs-merged = Onglets de SourceTree fusionnés avec les modifications faites ici.
+status-tabs-kept = Les onglets de SourceTree ont changé, mais les modifications faites ici ont été conservées.
+status-error-saving-tabs = Erreur lors de l'enregistrement des onglets de SourceTree.
+status-merged-external = Les modifications faites ailleurs aux espaces de travail ont été fusionnées.
+status-merged-external-conflicts =
+    { $count ->
+        [one] Les modifications faites ailleurs aux espaces de travail ont été fusionnées, avec { $count } conflit.
+       *[other] Les modifications faites ailleurs aux espaces de travail ont été fusionnées, avec { $count } conflits.
+    }
+status-error-recording-stash = Erreur lors de l'enregistrement des modifications remisées.
+status-stashed = Modifications non validées remisées.
+status-stash-failed =
+    { $count ->
+        [one] { $count } dépôt n'a pas pu être remisé, le changement a donc été interrompu.
+       *[other] { $count } dépôts n'ont pas pu être remisés, le changement a donc été interrompu.
+    }
+status-error-writing-snapshot = Erreur lors de l'écriture de l'instantané.
+status-took-snapshot = Instantané des branches extraites pris.
+status-restored-snapshot = Branches extraites restaurées.
+status-restore-failed =
+    { $count ->
+        [one] { $count } dépôt n'a pas pu être restauré.
+       *[other] { $count } dépôts n'ont pas pu être restaurés.
+    }
+status-error-saving-sources = Erreur lors de l'écriture des sources d'espaces de travail.
+status-reloaded-sources = Sources d'espaces de travail rechargées.
+status-error-loading-sources = Impossible de charger les sources d'espaces de travail.
+status-error-loading-config = Impossible de lire la configuration locale, les racines de chemin ne sont donc pas développées.
+status-error-loading-preferences = Impossible de lire les préférences, les valeurs par défaut sont donc utilisées et ne seront enregistrées qu'à l'enregistrement des paramètres.
+status-resolving-repos = Recherche des dépôts de { $name }...
+status-pulled-catalogue = Catalogue partagé tiré.
+status-error-pulling-catalogue = Impossible de tirer le catalogue partagé.
+status-committed-catalogue = Catalogue partagé validé.
+status-nothing-to-commit = Aucune modification partagée à valider.
+status-error-committing-catalogue = Impossible de valider le catalogue partagé.
+status-error-copying = Erreur lors de l'écriture de l'espace de travail copié.
+status-copied = Espace de travail partagé copié dans les miens.
+status-error-creating = Erreur lors de la création du nouvel espace de travail
+status-created = Nouvel espace de travail créé
+status-error-creating-dynamic = Erreur lors de la création de l'espace de travail dynamique
+status-created-dynamic = Espace de travail dynamique créé
+status-error-loading-tabs = Impossible de charger les onglets ouverts pour créer l'espace de travail.
+status-error-workspace-gone = L'espace de travail en cours d'ouverture n'existe plus.
+status-error-creating-from-tabs = Erreur lors de la création de l'espace de travail depuis les onglets ouverts
+status-created-from-tabs = Espace de travail créé depuis les onglets ouverts
+status-error-deleting = Erreur lors de la suppression de l'espace de travail
+status-deleted =
+    { $count ->
+        [one] Espace de travail supprimé
+       *[other] { $count } espaces de travail supprimés
+    }
+status-error-merging = Erreur lors de la fusion des espaces de travail
+status-merged = { $count } espaces de travail fusionnés
+status-opened-in = { $name } ouvert dans { $launcher }.
+status-error-opening-in = Impossible d'ouvrir dans { $launcher }.
+status-opened-with-failures = Ouvert, mais certaines choses ont échoué : { $failed }
+switch-failed-unstash = restauration des remises ({ $count } dépôts)
+
+instance-showed = Fenêtre affichée.
+instance-updated = Espace de travail actuel mis à jour.
+instance-update-failed = Impossible de mettre à jour l'espace de travail actuel. '{ $error }'
+instance-no-answer = L'instance en cours n'a pas répondu à temps.
+instance-opening = Passage à { $name }...
+notification-switched =
+    { $count ->
+        [one] Passage à { $name } ({ $count } dépôt).
+       *[other] Passage à { $name } ({ $count } dépôts).
+    }
+tray-switch = Changer d'espace de travail...
+tray-quit = Quitter
+
+## Résultats des dépôts et des hooks
+
+repo-not-found = Dépôt introuvable
+repo-fetched = Récupéré
+repo-pulled = Tiré
+repo-status = { $branch }, { $count ->
+    [0] propre
+    [one] 1 modification
+   *[other] { $count } modifications
+}
+repo-already-on-branch = Déjà sur la branche
+repo-no-such-branch = Branche inexistante
+repo-checked-out = { $branch } extraite
+repo-branch-exists = La branche existe déjà
+repo-created-branch = { $branch } créée
+repo-already-checked-out = Déjà extrait
+repo-uncommitted-changes = Modifications non validées, pas de passage à { $branch }
+repo-error-reading-status = Impossible de lire le statut
+repo-conflict-checking-out = Conflit lors de l'extraction de { $branch }
+repo-invalid-target = Ni une branche ni un commit pouvant être extrait
+repo-nothing-to-stash = Rien à remiser
+repo-not-stashed = { $state }, non remisé
+repo-error-stashing = Impossible de remiser
+repo-error-listing-stashes = Impossible de lister les remises
+repo-stash-gone = La remise n'existe plus
+repo-restored-stash = Modifications remisées restaurées
+repo-conflict-restoring-stash = Conflit lors de la restauration de la remise, elle a été conservée
+hook-started = Démarré
+hook-error-starting = Impossible de démarrer. { $error }
+hook-timed-out = Interrompu après { $seconds } s
+hook-finished = Terminé
+hook-exited = Terminé avec { $code }
+hook-error-running = Impossible d'exécuter. { $error }
+## Boîtes de dialogue
+
+batch-title = { $operation } - { $name }
+batch-progress = { $title } : { $done } dépôts sur { $count } terminés.
+batch-finished = { $title } : terminé, { $failed } en échec.
+batch-repo = Dépôt
+batch-result = Résultat
+batch-details = Détails
+outcome-running = en cours
+outcome-succeeded = ok
+outcome-skipped = ignoré
+outcome-failed = échec
+
+delete-title = Retirer l'espace de travail ?
+delete-message = Retirer { $name } ? Cette action est irréversible.
+delete-several = { $count } espaces de travail
+
+dirty-guard-title = Travail non terminé
+dirty-guard-message = Ces dépôts de { $name } contiennent du travail non validé.
+dirty-guard-stash-note = Les modifications remisées sont restaurées à la prochaine ouverture de l'espace de travail.
+dirty-guard-open-tabs = les onglets ouverts
+stash-title = Remise - { $name }
+restore-title = Restauration - { $name }
+unstash-title = Restauration de la remise - { $name }
+
+doctor-title = Diagnostic
+doctor-failed = Certaines vérifications ont échoué, le changement d'espace de travail risque de ne pas fonctionner.
+doctor-passed = Rien n'empêche de changer d'espace de travail.
+doctor-fix = Solution : { $fix }
+doctor-report-title = Diagnostic de st-workspaces { $version }
+
+error-report-details = Détails
+
+export-title = Exporter les espaces de travail
+export-message =
+    { $count ->
+        [one] Exporter { $count } espace de travail vers
+       *[other] Exporter { $count } espaces de travail vers
+    }
+exports-add = Ajouter un export :
+
+external-change-title = Espaces de travail modifiés
+external-change-message = st-workspaces.json a été modifié en dehors de cette fenêtre.
+external-change-reload-note = Recharger conserve les modifications faites ici et reprend celles faites ailleurs. Les espaces de travail modifiés des deux côtés sont affichés ensuite.
+
+hooks-command = Commande
+hooks-working-dir = Dans
+hooks-detached = Ne pas attendre
+hooks-timeout = Délai
+hooks-add = Ajouter un hook :
+hook-after-open = Après l'ouverture
+hook-before-close = Avant la fermeture
+hook-trust-title = Exécuter les hooks ?
+hook-trust-message = Ces hooks viennent d'un catalogue partagé ou d'un fichier de projet, ils ont donc été écrits par quelqu'un d'autre. Ils s'exécutent avec les variables d'environnement de cet espace de travail.
+button-trust-hooks = Faire confiance et exécuter
+button-skip-hooks = Changer sans les hooks
+hook-run-title = Exécution des hooks
+hook-run-message = Exécution des hooks « { $event } » de { $name }...
+environment-variable = Variable{" "}
+
+import-title = Importer des espaces de travail
+import-file = Fichier{" "}
+import-error = Impossible de charger les espaces de travail à importer. '{ $error }'
+import-remaps = Réécrire les préfixes de chemins (${"{"}NAME{"}"} pour une racine de chemin)
+import-found = trouvé
+import-missing = manquant
+
+launchers-new-command = Nouvelle commande
+launchers-placeholders = {"{"}repo{"}"}, {"{"}repos{"}"} et {"{"}name{"}"} sont remplis automatiquement.
+launcher-terminal = Terminal
+launcher-file-manager = Gestionnaire de fichiers
+
+log-title = Journal
+log-no-directory = Impossible de trouver le dossier du journal.
+
+merge-conflicts-title = Conflits de fusion
+merge-conflicts-message = Ces espaces de travail ont été modifiés ici et dans st-workspaces.json.
+merge-conflicts-mine = Les miens : { $workspace }
+merge-conflicts-theirs = Les leurs : { $workspace }
+merge-conflicts-workspace = { $name } avec { $count } dépôts : { $repos }
+merge-conflicts-deleted = supprimé
+
+switcher-title = Changer d'espace de travail
+
+restore-prompt-title = Restaurer les branches ?
+restore-prompt-message = Extraire les branches qu'avait { $name } dans un instantané avant de l'ouvrir ?
+snapshot-description = { $taken_at } ({ $count } dépôts)
+
+roots-title = Racines de chemins
+
+rules-repos-under = Dépôts sous
+rules-depth = profondeur
+rules-bookmark-folder = Signets du dossier
+rules-remote-matches = Signets avec le dépôt distant
+rules-add = Ajouter une règle :
+rule-repos-under = Dépôts sous { $root } (profondeur { $depth })
+rule-bookmark-folder = Signets du dossier { $folder }
+rule-remote-matches = Signets dont un dépôt distant correspond à { $pattern }
+
+scan-workspace-name = Espace de travail trouvé
+scan-progress = Recherche... { $visited } dossiers, { $count } dépôts trouvés. { $path }
+scan-finished = { $visited } dossiers parcourus, { $count } dépôts trouvés.
+scan-title = Rechercher des dépôts
+scan-root = Racine{" "}
+scan-depth = Profondeur{" "}
+scan-ignore = Ignorer{" "}
+
+sources-title = Sources d'espaces de travail
+sources-catalogue = Catalogue partagé
+sources-catalogue-note = Un dossier, ou une copie git, contenant le st-workspaces.json de l'équipe.
+sources-directory = Dossier{" "}
+sources-read-only = Lecture seule
+sources-project-repos = Dépôts de projet
+sources-project-repos-note = Dépôts avec un .st-workspace validé qui décrit leurs dépôts voisins.
+
+git-fetch = Récupérer
+git-pull = Tirer
+git-status = État
+git-checkout = Extraire { $branch }
+git-create-branch = Créer la branche { $branch }
+repo-state-changes =
+    { $count ->
+        [one] { $count } modification non validée
+       *[other] { $count } modifications non validées
+    }
+repo-state-in-progress = { $operation } en cours
+repo-state-stashes = { $count } remises
+repo-state-clean = propre
+
+## Paramètres
+
+settings-title = Paramètres
+settings-language = Langue
+settings-language-system = Celle du système
+settings-theme = Thème
+settings-font-scale = Taille du texte
+settings-window-size = Taille de la fenêtre
+settings-window-position = Position de la fenêtre
+settings-centred = Centrée
+settings-close-timeout = Délai de fermeture
+settings-confirm-delete = Confirmer la suppression
+settings-confirm-delete-description = Demander avant de retirer un espace de travail
+settings-sourcetree = SourceTree
+settings-default-launcher = Lanceur par défaut
+theme-dark = Sombre
+theme-light = Clair
+theme-high-contrast = Contraste élevé
+theme-system = Celui du système
+settings-error-font-scale = La taille du texte doit être comprise entre { $min } et { $max }.
+settings-error-window-size = La fenêtre doit mesurer au moins { $width }x{ $height }.
+settings-error-language = Il n'y a pas de traduction pour '{ $language }'.
+settings-error-unknown = Il n'y a pas de paramètre appelé '{ $key }'.
+settings-error-value = '{ $value }' n'est pas une valeur valide pour { $key }.
+
+## Ligne de commande
+
+cli-instance-running = Une autre instance est toujours en cours d'exécution.
+cli-settings-usage = Utilisation : st-workspaces settings [get <key> | set <key> <value> | reset [key]]
+cli-ui-failed = Erreur lors de l'exécution de l'interface
+cli-list-usage = Utilisation : st-workspaces list [--tag <tag>]... [--all]
+cli-open-usage = Utilisation : st-workspaces open [name] [--tag <tag>]... [--all]
+cli-no-match = Aucun espace de travail ne correspond. Les espaces de travail archivés ne sont trouvés qu'avec --all ou --tag archived.
+cli-several-match = { $count } espaces de travail correspondent : { $names }. Indiquez un nom ou un autre --tag pour en choisir un.
+
+## Erreurs
+
+error-path-not-found = Impossible de trouver { $what }. SourceTree est-il installé ?
+error-io =
+    { $operation ->
+        [read] Impossible de lire { $path }. { $error }
+        [write] Impossible d'écrire { $path }. { $error }
+        [open] Impossible d'ouvrir { $path }. { $error }
+       *[other] Échec de { $operation } sur { $path }. { $error }
+    }
+error-parse = Impossible d'analyser { $path } en { $format }{ $position }. { $error }
+error-process-exit = { $command } s'est terminé avec { $code }. '{ $output }'
+error-launch = Impossible de lancer { $program }. { $error }
+error-writing-open-tabs = Impossible d'écrire les onglets ouverts, SourceTree ne peut donc pas être lancé.
+error-launching-sourcetree = Impossible de lancer SourceTree. Est-il installé ?
+error-waiting-for-sourcetree = Le passage à SourceTree ne s'est pas terminé. Erreur de processus ?
+error-catalogue-read-only = Le catalogue partagé est en lecture seule.
+error-catalogue-not-git = Le catalogue partagé n'est pas un dépôt git.
+error-position-column = {" "}à la ligne { $line }, colonne { $column }
+error-position-line = {" "}à la ligne { $line }
+error-exit-code = le code { $code }
+error-no-exit-code = aucun code de sortie
+error-detail-missing = Manquant
+error-detail-operation = Opération
+error-detail-path = Chemin
+error-detail-kind = Type
+error-detail-format = Format
+error-detail-line = Ligne
+error-detail-column = Colonne
+error-detail-message = Message
+error-detail-command = Commande
+error-detail-exit-code = Code de sortie
+error-detail-output = Sortie
+error-detail-program = Programme
+diagnostics-error = Erreur : { $error }
+diagnostics-caused-by = Cause : { $cause }
+
+## Vérifications du diagnostic
+
+check-path-missing = { $path } n'existe pas.
+check-path-missing-yet = { $path } n'existe pas encore.
+check-settings-path = Chemin des paramètres
+check-settings-path-fix = Installez SourceTree et ouvrez-le une fois pour qu'il crée ses paramètres.
+check-settings-path-no-app-data = Impossible de trouver le dossier local des données d'application.
+check-settings-path-no-app-data-fix = Lancez st-workspaces avec l'utilisateur pour lequel SourceTree est installé.
+check-open-tabs = Onglets ouverts
+check-open-tabs-missing-fix = Ouvrez un dépôt dans SourceTree puis fermez-le pour qu'il enregistre ses onglets.
+check-open-tabs-pass = { $count } onglets dans { $path }
+check-open-tabs-fail-fix = Fermez SourceTree et supprimez le fichier. SourceTree en écrit un nouveau à la fermeture.
+check-workspaces-file = Fichier des espaces de travail
+check-workspaces-file-missing-fix = Créez un espace de travail pour créer le fichier.
+check-workspaces-file-fail-fix = Corrigez le fichier à la ligne indiquée ou restaurez-le depuis une sauvegarde.
+check-workspaces-file-pass = { $count } espaces de travail dans { $path }
+check-workspaces-file-problems-fix = Modifiez le fichier pour corriger ces points, ou réenregistrez les espaces de travail concernés depuis la fenêtre.
+check-problem-wrong-id = L'espace de travail { $uuid } est enregistré sous l'identifiant { $id }.
+check-problem-no-name = L'espace de travail { $uuid } n'a pas de nom.
+check-problem-missing-current = L'espace de travail actuel { $uuid } n'existe pas.
+check-custom-actions = Actions personnalisées
+check-custom-actions-install-fix = Dans SourceTree, allez dans Tools > Options > Custom Actions et ajoutez-en une avec le script { $path } et les paramètres auto-update-and-close.
+check-custom-actions-missing = SourceTree n'a aucune action personnalisée.
+check-custom-actions-fail-fix = Fermez SourceTree, corrigez ou supprimez le fichier, puis ajoutez à nouveau les actions personnalisées.
+check-custom-actions-none = Aucune n'exécute st-workspaces.
+check-custom-actions-runs = { $caption } exécute { $target }
+check-custom-actions-elsewhere = { $actions }, et non { $path }.
+check-custom-actions-elsewhere-fix = Remplacez le script de ces actions personnalisées par { $path }.
+check-custom-actions-pass = { $count } actions exécutent { $path }
+check-sourcetree = SourceTree
+check-sourcetree-install-fix = Installez SourceTree { $version } pour cet utilisateur, ou indiquez son emplacement avec `st-workspaces settings set exec_path <path>`.
+check-sourcetree-missing = Impossible de trouver { $path }.
+check-sourcetree-pass = { $version } dans { $path }
+check-sourcetree-untested = La version { $version } n'a pas été testée avec st-workspaces { $app_version }.
+check-sourcetree-supported = Le changement d'espace de travail fonctionne avec SourceTree { $version }.
+check-sourcetree-unknown-version = Impossible de déterminer la version de { $path }.
+check-log-dir = Dossier du journal
+check-log-dir-fail = Impossible d'écrire dans { $path }. { $error }
+check-log-dir-fail-fix = Vérifiez que cet utilisateur peut écrire dans le dossier, ou libérez de l'espace disque.
+check-repo-paths = Chemins des dépôts
+check-repo-paths-pass = Les { $count } chemins de dépôts existent.
+check-repo-paths-missing = { $missing } sur { $count } sont manquants. { $paths }
+check-repo-paths-missing-fix = Retirez-les de leurs espaces de travail, ou créez une racine de chemin si les dépôts ont été déplacés.
@@ -1,166 +1,942 @@
-use std::path::PathBuf;
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::PathBuf,
+    time::Duration,
+};
 
 use egui::{
-    Align, Button, Color32, FontFamily, FontId, Label, Layout, RichText, Sense, TextStyle, Ui,
-    Visuals,
+    Align, Button, CollapsingHeader, ComboBox, Key, Layout, RichText, ScrollArea, TextEdit, Ui,
 };
-use log::info;
+use log::{info, warn};
 use uuid::Uuid;
 
 use crate::{
+    dirty_guard::{pop_stash, repos_needing_attention, stash_changes, PendingStash},
+    filters::WorkspaceFilter,
+    git_batch::{GitOperation, RepoOutcome},
+    hooks::{hooks_fingerprint, HookEvent, HookResult, WorkspaceHook},
+    i18n::set_language,
+    instance::{InstanceRequest, InstanceResponse, InstanceServer},
+    launchers::Launcher,
+    local_config::LocalConfig,
     open_tabs::OpenTabs,
-    paths::{sourcetree_exec_path, sourcetree_settings_path},
-    workspaces::{Workspace, Workspaces},
+    paths::sourcetree_settings_path,
+    preferences::{Preferences, Theme, WindowGeometry},
+    project_file::project_file_path,
+    resident::switch_notification,
+    roots::PathRoots,
+    rules::WorkspaceRule,
+    selection::{display_order, neighbour, Selection},
+    session,
+    snapshots::{push_snapshot, BranchSnapshot},
+    tabs_watcher::{spawn_file_watcher, FileWatcher, SyncOutcome, TabsSync, TabsWatcherConfig},
+    tr,
+    ui_state::{group_key, SortMode, UiState},
+    workspace_merge::merge_workspaces,
+    workspaces::{Workspace, WorkspaceSource, Workspaces},
+};
+
+use self::{
+    batch_results::BatchRun,
+    delete_prompt::{show_delete_prompt, DeletePromptResult},
+    dirty_guard_prompt::{DirtyGuardPrompt, DirtyGuardResult},
+    doctor_panel::{DoctorPanel, DoctorPanelResult},
+    error_report::ErrorReport,
+    export_prompt::{ExportPrompt, ExportPromptResult},
+    exports_editor::update_exports_editor,
+    external_change_prompt::{show_external_change_prompt, ExternalChangeResult},
+    filter_bar::update_filter_bar,
+    hook_run::HookRun,
+    hook_trust_prompt::{HookTrustPrompt, HookTrustPromptResult},
+    hooks_editor::{update_environment_editor, update_hooks_editor},
+    import_wizard::{ImportWizard, ImportWizardResult},
+    launchers_editor::update_launchers_editor,
+    log_panel::{LogPanel, LogPanelResult},
+    merge_conflicts::{MergeConflicts, MergeConflictsResult},
+    notes_editor::update_notes_editor,
+    quick_switcher::{QuickSwitcher, QuickSwitcherResult},
+    resident_agent::{ResidentAgent, ResidentEvent},
+    restore_prompt::{RestorePrompt, RestorePromptResult},
+    roots_editor::{RootsEditor, RootsEditorResult},
+    rules_editor::{update_rules_editor, RulePreview},
+    scan_wizard::{ScanWizard, ScanWizardResult},
+    settings_editor::{SettingsEditor, SettingsEditorResult},
+    sources_editor::{SourcesEditor, SourcesEditorResult},
+    tags_editor::update_tags_editor,
+    theme::{apply_preferences, font_scale_shortcut, workspace_color, SystemThemeWatcher},
 };
 
+mod batch_results;
+mod delete_prompt;
+mod dirty_guard_prompt;
+mod doctor_panel;
+mod error_report;
+mod export_prompt;
+mod exports_editor;
+mod external_change_prompt;
+mod filter_bar;
+mod hook_run;
+mod hook_trust_prompt;
+mod hooks_editor;
+mod import_wizard;
+mod launchers_editor;
+mod log_panel;
+mod merge_conflicts;
+mod notes_editor;
+mod quick_switcher;
+mod resident_agent;
+mod restore_prompt;
+mod roots_editor;
+mod rules_editor;
+mod scan_wizard;
+mod settings_editor;
+mod sources_editor;
+mod tags_editor;
+mod theme;
+
+/// Long enough for an editor to finish saving the workspaces file.
+/// What to do with the current workspace's repos once they're resolved.
+enum RepoTask {
+    Switch,
+    Launch(Launcher),
+    GitOperation(GitOperation),
+    Snapshot,
+}
+
+const WORKSPACES_WATCHER_DEBOUNCE: Duration = Duration::from_millis(500);
+
 /// Main UI application struct.
 pub struct SourceTreeWorkspacesApp {
     version: String,
     settings_path: Option<PathBuf>,
     workspaces: Workspaces,
+    roots: PathRoots,
     status: String,
+    import_wizard: Option<ImportWizard>,
+    roots_editor: Option<RootsEditor>,
+    sources_editor: Option<SourcesEditor>,
+    scan_wizard: Option<ScanWizard>,
+    rule_preview: Option<RulePreview>,
+    /// Waiting on the current workspace's rules before doing something with its repos.
+    repo_task: Option<(RulePreview, RepoTask)>,
+    /// The repos the switch in progress opens, resolved before it started.
+    switch_repo_paths: Vec<String>,
+    batch_run: Option<BatchRun>,
+    branch_name: String,
+    snapshot_label: String,
+    restore_prompt: Option<RestorePrompt>,
+    dirty_guard: Option<DirtyGuardPrompt>,
+    new_variable_name: String,
+    /// What the workspace being opened will be opened in.
+    pending_launchers: Vec<Launcher>,
+    hook_trust_prompt: Option<HookTrustPrompt>,
+    /// The team and project hooks allowed to run on this machine, by [`hooks_fingerprint`].
+    trusted_hooks: BTreeSet<Uuid>,
+    hook_run: Option<HookRun>,
+    /// The hooks and launchers that failed so far in the switch under way.
+    switch_failures: Vec<String>,
+    quick_switcher: Option<QuickSwitcher>,
+    /// Present in resident mode, where the window hides after a switch instead of closing.
+    resident: Option<ResidentAgent>,
+    hide_requested: bool,
+    quitting: bool,
+    /// Keeps the opened workspace in step with SourceTree's tabs in resident mode, when enabled.
+    tabs_watcher: Option<(FileWatcher, TabsSync)>,
+    /// From the local config read at startup. When that couldn't be read the error was reported
+    /// then, and the watcher stays off.
+    tabs_watcher_config: TabsWatcherConfig,
+    /// Invocations made while this one is running, such as SourceTree's custom actions.
+    instance_server: Option<InstanceServer>,
+    /// Picked with `open` on the command line, and switched to on the first frame.
+    startup_switch: Option<Uuid>,
+    /// The personal workspaces as last read from or written to the file, to merge against.
+    saved_workspaces: HashMap<Uuid, Workspace>,
+    workspaces_watcher: Option<FileWatcher>,
+    external_change: bool,
+    merge_conflicts: Option<MergeConflicts>,
+    /// The failure behind the status line, while it's still showing.
+    error_report: Option<ErrorReport>,
+    doctor_panel: Option<DoctorPanel>,
+    log_panel: Option<LogPanel>,
+    preferences: Preferences,
+    /// Set while the preferences file couldn't be read, so it isn't saved over until Settings is.
+    preferences_unreadable: bool,
+    system_theme: SystemThemeWatcher,
+    settings_editor: Option<SettingsEditor>,
+    /// The workspaces waiting on the user to confirm they should be removed.
+    delete_prompt: Option<Vec<Uuid>>,
+    export_prompt: Option<ExportPrompt>,
+    selection: Selection,
+    filter: WorkspaceFilter,
+    /// The name the current filter will be saved under as a preset.
+    preset_name: String,
+    new_tag: String,
+    ui_state: UiState,
+    ui_state_unreadable: bool,
+    /// Where the window was last seen while it was neither minimised nor maximised. Left unsaved
+    /// once the window has been changed in Settings.
+    window: Option<WindowGeometry>,
+    window_set_in_settings: bool,
 }
 
 impl SourceTreeWorkspacesApp {
-    pub fn new(cc: &eframe::CreationContext<'_>, workspaces: Workspaces) -> Self {
-        let mut style = (*cc.egui_ctx.style()).clone();
-        //style.visuals = Visuals::light();
-        style.visuals = Visuals::dark();
-        cc.egui_ctx.set_style(style);
+    pub fn new(
+        cc: &eframe::CreationContext<'_>,
+        mut workspaces: Workspaces,
+        resident: bool,
+        instance_server: Option<InstanceServer>,
+        open_workspace: Option<Uuid>,
+    ) -> Self {
+        let (preferences, preferences_error) = match Preferences::read_or_default() {
+            Ok(preferences) => (preferences, None),
+            Err(why) => (Preferences::default(), Some(why)),
+        };
+        apply_preferences(&cc.egui_ctx, &preferences);
 
-        configure_text_styles(&cc.egui_ctx);
+        let ui_state = UiState::read_or_default().map_err(|why| {
+            warn!(
+                "Couldn't read the UI state, so it won't be saved. '{}'",
+                why
+            )
+        });
+        let ui_state_unreadable = ui_state.is_err();
+        let ui_state = ui_state.unwrap_or_default();
+        if let Some(uuid) = ui_state
+            .selected_workspace
+            .filter(|uuid| workspaces.workspaces.contains_key(uuid))
+        {
+            workspaces.current_workspace = uuid;
+        }
+
+        let (local_config, config_error) = match LocalConfig::read_or_default() {
+            Ok(local_config) => (local_config, None),
+            Err(why) => (LocalConfig::default(), Some(why)),
+        };
+        let resident = resident.then(|| ResidentAgent::start(&local_config.resident, &workspaces));
+        let workspaces_watcher =
+            Workspaces::path().map(|path| spawn_file_watcher(path, WORKSPACES_WATCHER_DEBOUNCE));
 
-        Self {
+        let mut app = Self {
             version: env!("CARGO_PKG_VERSION").to_owned(),
+            saved_workspaces: workspaces.personal(),
             workspaces,
+            roots: local_config.roots,
             settings_path: sourcetree_settings_path(),
             status: "".to_owned(),
+            import_wizard: None,
+            roots_editor: None,
+            sources_editor: None,
+            scan_wizard: None,
+            rule_preview: None,
+            repo_task: None,
+            switch_repo_paths: Vec::new(),
+            batch_run: None,
+            branch_name: String::new(),
+            snapshot_label: String::new(),
+            restore_prompt: None,
+            dirty_guard: None,
+            new_variable_name: String::new(),
+            pending_launchers: Vec::new(),
+            hook_trust_prompt: None,
+            trusted_hooks: local_config.trusted_hooks.clone(),
+            hook_run: None,
+            switch_failures: Vec::new(),
+            quick_switcher: None,
+            resident,
+            hide_requested: false,
+            quitting: false,
+            tabs_watcher: None,
+            tabs_watcher_config: local_config.tabs_watcher.clone(),
+            instance_server,
+            startup_switch: open_workspace,
+            workspaces_watcher,
+            external_change: false,
+            merge_conflicts: None,
+            error_report: None,
+            doctor_panel: None,
+            log_panel: None,
+            preferences_unreadable: preferences_error.is_some(),
+            preferences,
+            system_theme: SystemThemeWatcher::default(),
+            settings_editor: None,
+            delete_prompt: None,
+            export_prompt: None,
+            selection: Selection::default(),
+            filter: WorkspaceFilter::default(),
+            preset_name: String::new(),
+            new_tag: String::new(),
+            ui_state,
+            ui_state_unreadable,
+            window: None,
+            window_set_in_settings: false,
+        };
+
+        if let Some(why) = config_error {
+            app.report_error(tr!("status-error-loading-config"), why);
         }
+        if let Some(why) = preferences_error {
+            app.report_error(tr!("status-error-loading-preferences"), why);
+        }
+        app
     }
 }
 
 impl eframe::App for SourceTreeWorkspacesApp {
     fn update(&mut self, context: &egui::Context, frame: &mut eframe::Frame) {
+        if let Some(font_scale) = font_scale_shortcut(context, self.preferences.font_scale) {
+            self.preferences.font_scale = font_scale;
+            self.save_preferences(context);
+        }
+        self.system_theme.update(context, &self.preferences);
+        if let Some(uuid) = self.startup_switch.take() {
+            self.switch_to(uuid);
+        }
+
         self.update_top_panel(context);
+        self.update_bottom_panel(context);
         self.update_central_panel(context);
-        self.update_bottom_panel(context, frame);
+        self.update_import_wizard(context);
+        self.update_roots_editor(context);
+        self.update_sources_editor(context);
+        self.update_scan_wizard(context);
+        self.update_batch_run(context);
+        self.update_repo_task(context, frame);
+        self.update_dirty_guard(context, frame);
+        self.update_restore_prompt(context, frame);
+        self.update_hook_trust_prompt(context, frame);
+        self.update_hook_run(context, frame);
+        self.update_quick_switcher(context);
+        self.update_resident(context, frame);
+        self.update_instance_requests(context, frame);
+        self.update_workspaces_watcher(context);
+        self.update_external_change(context);
+        self.update_merge_conflicts(context);
+        self.update_doctor_panel(context);
+        self.update_log_panel(context);
+        self.update_settings_editor(context);
+        self.update_delete_prompt(context);
+        self.update_export_prompt(context);
+        self.track_window(frame);
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.save_ui_state();
+    }
+
+    fn on_close_event(&mut self) -> bool {
+        // Resident mode keeps running in the tray until Quit is picked.
+        if self.resident.is_some() && !self.quitting {
+            self.hide_requested = true;
+            return false;
+        }
+
+        true
     }
 }
 
 impl SourceTreeWorkspacesApp {
     fn update_top_panel(&mut self, context: &egui::Context) {
         egui::TopBottomPanel::top("top_panel").show(context, |ui| {
-            let dark_mode = ui.visuals().dark_mode;
             ui.horizontal(|ui| {
-                ui.heading(contrast_text("SourceTree Workspaces", false, dark_mode));
+                ui.heading("SourceTree Workspaces");
                 ui.label(format!("(v{})", self.version));
-                egui::widgets::global_dark_light_mode_buttons(ui);
+
+                let mut theme = self.preferences.theme;
+                ComboBox::from_id_source("theme")
+                    .selected_text(theme.to_string())
+                    .show_ui(ui, |ui| {
+                        for choice in Theme::all() {
+                            ui.selectable_value(&mut theme, choice, choice.to_string());
+                        }
+                    });
+                if theme != self.preferences.theme {
+                    self.preferences.theme = theme;
+                    self.save_preferences(context);
+                }
+
+                if ui.button(tr!("button-import")).clicked() {
+                    self.import_wizard = Some(ImportWizard::new(self.roots.clone()));
+                }
+
+                if ui.button(tr!("button-path-roots")).clicked() {
+                    self.roots_editor = Some(RootsEditor::new());
+                }
+
+                if ui.button(tr!("button-sources")).clicked() {
+                    self.sources_editor = Some(SourcesEditor::new());
+                }
+
+                let switch_shortcut =
+                    ui.input(|input| input.modifiers.command && input.key_pressed(Key::P));
+                if ui.button(tr!("button-switch")).clicked() || switch_shortcut {
+                    self.quick_switcher = Some(QuickSwitcher::new());
+                }
+
+                if ui.button(tr!("button-doctor")).clicked() {
+                    self.doctor_panel = Some(DoctorPanel::new());
+                }
+
+                if ui.button(tr!("button-log")).clicked() {
+                    self.log_panel = Some(LogPanel::new());
+                }
+
+                if ui.button(tr!("button-settings")).clicked() {
+                    self.settings_editor = Some(SettingsEditor::new(&self.preferences));
+                }
             });
         });
     }
 
     fn update_central_panel(&mut self, context: &egui::Context) {
-        egui::CentralPanel::default().show(context, |ui| {
-            ui.horizontal(|ui| {
-                self.update_workspace_list_panel(ui);
-                ui.separator();
-                self.update_workspace_details_panel(ui);
+        self.selection.sync(&self.workspaces);
+
+        let list_panel = egui::SidePanel::left("workspace_list")
+            .resizable(true)
+            .default_width(self.ui_state.list_width)
+            .width_range(160.0..=640.0)
+            .show(context, |ui| {
+                ScrollArea::vertical().show(ui, |ui| self.update_workspace_list_panel(ui));
             });
+        self.ui_state.list_width = list_panel.response.rect.width();
+
+        egui::CentralPanel::default().show(context, |ui| {
+            self.update_workspace_details_panel(ui);
         });
     }
 
     fn update_workspace_list_panel(&mut self, ui: &mut Ui) {
-        let dark_mode = ui.visuals().dark_mode;
         ui.vertical(|ui| {
+            ui.add(TextEdit::singleline(&mut self.filter.text).hint_text(tr!("list-filter")));
+            update_filter_bar(
+                ui,
+                &mut self.filter,
+                &self.workspaces.tags(),
+                &mut self.ui_state.filter_presets,
+                &mut self.preset_name,
+            );
+
+            ComboBox::from_label(tr!("list-sort"))
+                .selected_text(self.ui_state.sort_mode.to_string())
+                .show_ui(ui, |ui| {
+                    for sort_mode in SortMode::all() {
+                        ui.selectable_value(
+                            &mut self.ui_state.sort_mode,
+                            sort_mode,
+                            sort_mode.to_string(),
+                        );
+                    }
+                });
+
+            if self.selection.len() > 1 {
+                self.update_bulk_actions(ui);
+            }
+
             if self.workspaces.workspaces.is_empty() {
-                ui.label(contrast_text(
-                    "No workspaces exist... yet...",
-                    false,
-                    dark_mode,
-                ));
+                ui.label(tr!("list-empty"));
             } else {
-                let mut sorted_workspaces: Vec<_> =
-                    self.workspaces.workspaces.iter().map(|w| w.1).collect();
-                sorted_workspaces.sort();
-
-                for workspace in sorted_workspaces.iter() {
-                    if ui
-                        .add(
-                            Label::new(contrast_text(
-                                workspace.name.as_str(),
-                                workspace.uuid == self.workspaces.current_workspace,
-                                dark_mode,
-                            ))
-                            .sense(Sense::click()),
-                        )
-                        .clicked()
-                    {
-                        self.workspaces.current_workspace = workspace.uuid;
-                    };
-                }
+                self.update_workspace_group(
+                    ui,
+                    &WorkspaceSource::Personal.to_string(),
+                    &WorkspaceSource::Personal,
+                );
             }
 
             ui.horizontal(|ui| {
-                if ui.add(Button::new("Create New\nWorkspace")).clicked() {
+                if ui
+                    .add(Button::new(tr!("button-create-workspace")))
+                    .clicked()
+                {
                     self.create_new_workspace();
                 }
 
-                if ui.add(Button::new("Create from\nCurrent Tabs")).clicked() {
+                if ui
+                    .add(Button::new(tr!("button-create-from-tabs")))
+                    .clicked()
+                {
                     self.create_workspace_from_current_tabs();
                 }
+
+                if ui
+                    .add(Button::new(tr!("button-create-from-scan")))
+                    .clicked()
+                {
+                    self.scan_wizard = Some(ScanWizard::new());
+                }
+
+                if ui.add(Button::new(tr!("button-create-dynamic"))).clicked() {
+                    self.create_dynamic_workspace();
+                }
             });
+
+            if let Some(catalogue) = self.workspaces.catalogue.clone() {
+                ui.separator();
+                let heading = if catalogue.read_only {
+                    tr!("group-shared-read-only")
+                } else {
+                    WorkspaceSource::Team.to_string()
+                };
+                self.update_workspace_group(ui, &heading, &WorkspaceSource::Team);
+
+                if catalogue.is_git_repo() {
+                    ui.horizontal(|ui| {
+                        if ui.button(tr!("button-pull")).clicked() {
+                            self.pull_catalogue();
+                        }
+
+                        if !catalogue.read_only && ui.button(tr!("button-commit")).clicked() {
+                            self.commit_catalogue();
+                        }
+                    });
+                }
+            }
+
+            for source in self.workspaces.sources() {
+                if let WorkspaceSource::Project(_) = source {
+                    ui.separator();
+                    self.update_workspace_group(ui, &source.to_string(), &source);
+                }
+            }
+        });
+    }
+
+    /// A foldable heading over a source's workspaces, which stays folded between runs.
+    fn update_workspace_group(&mut self, ui: &mut Ui, heading: &str, source: &WorkspaceSource) {
+        let collapsed = self.ui_state.is_collapsed(source);
+        let group = CollapsingHeader::new(RichText::new(heading).strong())
+            .id_source(group_key(source))
+            .default_open(!collapsed)
+            .show(ui, |ui| self.update_workspace_list(ui, source));
+
+        if group.header_response.clicked() {
+            self.ui_state.set_collapsed(source, !collapsed);
+        }
+    }
+
+    fn update_workspace_list(&mut self, ui: &mut Ui, source: &WorkspaceSource) {
+        let mut sorted_workspaces = self.workspaces.by_source(source);
+        sorted_workspaces.retain(|workspace| self.filter.matches(workspace));
+        self.ui_state.sort_mode.sort(&mut sorted_workspaces);
+        let mut clicked_workspace = None;
+
+        for workspace in sorted_workspaces.iter() {
+            let mut label = RichText::new(workspace.label());
+            if let Some(color) = workspace.color {
+                label = label.color(workspace_color(color));
+            }
+
+            let mut item = ui.selectable_label(self.selection.contains(&workspace.uuid), label);
+            let mut hover_lines: Vec<String> = Vec::new();
+            if !workspace.tags.is_empty() {
+                hover_lines.push(format!("#{}", workspace.tags.join(" #")));
+            }
+            if let Some(first_line) = workspace.notes.lines().find(|line| !line.trim().is_empty()) {
+                hover_lines.push(first_line.to_owned());
+            }
+            if !hover_lines.is_empty() {
+                item = item.on_hover_text(hover_lines.join("\n"));
+            }
+
+            if item.clicked() {
+                clicked_workspace = Some(workspace.uuid);
+            };
+        }
+
+        if let Some(uuid) = clicked_workspace {
+            let modifiers = ui.input(|input| input.modifiers);
+            let order = display_order(&self.workspaces, self.ui_state.sort_mode, &self.filter);
+            self.workspaces.current_workspace =
+                self.selection
+                    .click(uuid, &order, modifiers.command, modifiers.shift);
+        }
+    }
+
+    /// What can be done to several selected workspaces at once.
+    fn update_bulk_actions(&mut self, ui: &mut Ui) {
+        let order = display_order(&self.workspaces, self.ui_state.sort_mode, &self.filter);
+        let selected = self.selection.in_order(&order);
+
+        ui.horizontal(|ui| {
+            ui.label(tr!("bulk-selected", count = selected.len()));
+
+            if ui.button(tr!("button-remove")).clicked() {
+                let editable: Vec<Uuid> = selected
+                    .iter()
+                    .filter(|uuid| {
+                        self.workspaces
+                            .workspaces
+                            .get(uuid)
+                            .is_some_and(|workspace| self.workspaces.is_editable(workspace))
+                    })
+                    .copied()
+                    .collect();
+                self.request_remove_workspaces(editable);
+            }
+
+            if ui.button(tr!("button-export")).clicked() {
+                self.export_prompt = Some(ExportPrompt::new(selected.clone()));
+            }
+
+            if ui.button(tr!("button-merge")).clicked() {
+                self.merge_workspaces(&selected);
+            }
         });
+        ui.separator();
     }
 
     fn update_workspace_details_panel(&mut self, ui: &mut Ui) {
         if self.workspaces.workspaces.is_empty() || self.workspaces.current_workspace.is_nil() {
             return;
         }
+        let is_editable = self
+            .workspaces
+            .current_workspace()
+            .is_some_and(|workspace| self.workspaces.is_editable(workspace));
+        let is_personal = self
+            .workspaces
+            .current_workspace()
+            .is_some_and(|workspace| workspace.source == WorkspaceSource::Personal);
+
         ui.vertical(|ui| {
-            let dark_mode = ui.visuals().dark_mode;
             let mut should_save = false;
             if let Some(current_workspace) = self.workspaces.current_workspace_mut() {
                 ui.horizontal(|ui| {
-                    ui.label(contrast_text("Name ", false, dark_mode));
-                    if ui
+                    let name_label = ui.strong(tr!("details-name"));
+                    if !is_editable {
+                        ui.label(&current_workspace.name);
+                    } else if ui
                         .text_edit_singleline(&mut current_workspace.name)
+                        .labelled_by(name_label.id)
                         .lost_focus()
                     {
                         should_save = true;
                     }
                 });
 
+                let source = match &current_workspace.source {
+                    WorkspaceSource::Project(repo_path) => {
+                        project_file_path(repo_path).display().to_string()
+                    }
+                    source => source.to_string(),
+                };
+                ui.small(tr!("details-source", source = source));
+
+                should_save |= update_notes_editor(ui, current_workspace, is_editable);
+                should_save |=
+                    update_tags_editor(ui, current_workspace, &mut self.new_tag, is_editable);
+                ui.separator();
+
                 for repo_path in current_workspace.repo_paths.iter() {
-                    ui.label(contrast_text(repo_path.as_str(), false, dark_mode));
+                    ui.label(repo_path.as_str());
+
+                    let expanded_path = self.roots.expand(repo_path);
+                    if expanded_path != *repo_path {
+                        ui.small(format!("    {expanded_path}"));
+                    }
+                }
+
+                if current_workspace.is_dynamic() || is_editable {
+                    ui.separator();
+                    ui.strong(tr!("details-rules"));
+                }
+
+                if is_editable {
+                    should_save |= update_rules_editor(ui, &mut current_workspace.rules);
+                } else {
+                    for rule in current_workspace.rules.iter() {
+                        ui.label(rule.description());
+                    }
+                }
+
+                if current_workspace.is_dynamic() {
+                    if ui.button(tr!("button-preview-repos")).clicked() {
+                        self.rule_preview =
+                            Some(RulePreview::spawn(current_workspace, &self.roots));
+                    }
+
+                    let preview = self
+                        .rule_preview
+                        .as_mut()
+                        .filter(|preview| preview.workspace_id == current_workspace.uuid);
+                    if let Some(preview) = preview {
+                        match preview.repo_paths() {
+                            Some(repo_paths) => {
+                                ui.small(tr!("details-resolves-to", count = repo_paths.len()));
+                                for repo_path in repo_paths.iter() {
+                                    ui.small(format!("    {repo_path}"));
+                                }
+                            }
+                            None => {
+                                ui.small(tr!("details-resolving"));
+                                ui.ctx().request_repaint();
+                            }
+                        }
+                    }
                 }
             }
 
             if should_save {
-                let write_result = self.workspaces.write();
-                if write_result.is_err() {
-                    self.status = "Error occurred writing to disk.".to_owned();
+                if let Err(why) = self.save_workspaces() {
+                    self.report_error(tr!("status-error-writing"), why);
                 }
             }
+
+            ui.separator();
+            self.update_git_operations(ui);
+
+            ui.separator();
+            self.update_snapshots(ui);
+            self.update_hooks(ui, is_editable);
+            self.update_launchers(ui, is_editable);
         });
 
         ui.with_layout(Layout::bottom_up(Align::RIGHT), |ui| {
-            if ui.add(Button::new("Remove Workspace")).clicked() {
-                self.remove_current_workspace();
+            if is_editable
+                && ui
+                    .add(Button::new(tr!("button-remove-workspace")))
+                    .clicked()
+            {
+                self.request_remove_workspaces(vec![self.workspaces.current_workspace]);
+            }
+
+            if !is_personal && ui.add(Button::new(tr!("button-copy-to-mine"))).clicked() {
+                self.copy_current_workspace_to_mine();
+            }
+        });
+    }
+
+    fn update_git_operations(&mut self, ui: &mut Ui) {
+        let is_running = self
+            .batch_run
+            .as_ref()
+            .is_some_and(|batch_run| batch_run.is_running());
+
+        ui.strong(tr!("git-heading"));
+        ui.add_enabled_ui(!is_running, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button(tr!("button-fetch-all")).clicked() {
+                    self.start_repo_task(RepoTask::GitOperation(GitOperation::Fetch));
+                }
+
+                if ui.button(tr!("button-pull-all")).clicked() {
+                    self.start_repo_task(RepoTask::GitOperation(GitOperation::Pull));
+                }
+
+                if ui.button(tr!("button-status")).clicked() {
+                    self.start_repo_task(RepoTask::GitOperation(GitOperation::Status));
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(tr!("git-branch"));
+                ui.text_edit_singleline(&mut self.branch_name);
+
+                let branch_name = self.branch_name.trim().to_owned();
+                if ui
+                    .add_enabled(!branch_name.is_empty(), Button::new(tr!("button-checkout")))
+                    .clicked()
+                {
+                    self.start_repo_task(RepoTask::GitOperation(GitOperation::Checkout(
+                        branch_name.clone(),
+                    )));
+                }
+
+                if ui
+                    .add_enabled(
+                        !branch_name.is_empty(),
+                        Button::new(tr!("button-create-branch")),
+                    )
+                    .clicked()
+                {
+                    self.start_repo_task(RepoTask::GitOperation(GitOperation::CreateBranch(
+                        branch_name,
+                    )));
+                }
+            });
+        });
+    }
+
+    fn update_snapshots(&mut self, ui: &mut Ui) {
+        let Some(current_workspace) = self.workspaces.current_workspace() else {
+            return;
+        };
+
+        let mut restored_index = None;
+        let mut removed_index = None;
+        ui.collapsing(
+            tr!(
+                "snapshots-heading",
+                count = current_workspace.snapshots.len()
+            ),
+            |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("snapshots-label"));
+                    ui.text_edit_singleline(&mut self.snapshot_label);
+                    if ui.button(tr!("button-take-snapshot")).clicked() {
+                        self.start_repo_task(RepoTask::Snapshot);
+                    }
+                });
+
+                let Some(current_workspace) = self.workspaces.current_workspace() else {
+                    return;
+                };
+
+                for (index, snapshot) in current_workspace.snapshots.iter().enumerate().rev() {
+                    ui.horizontal(|ui| {
+                        ui.label(snapshot.description());
+                        if ui.small_button(tr!("button-restore")).clicked() {
+                            restored_index = Some(index);
+                        }
+
+                        if ui.small_button(tr!("button-delete")).clicked() {
+                            removed_index = Some(index);
+                        }
+                    });
+                }
+            },
+        );
+
+        if let Some(index) = restored_index {
+            self.restore_snapshot(index);
+        }
+
+        if let Some(index) = removed_index {
+            if let Some(current_workspace) = self.workspaces.current_workspace_mut() {
+                current_workspace.snapshots.remove(index);
+            }
+
+            if let Err(why) = self.save_workspaces() {
+                self.report_error(tr!("status-error-writing"), why);
+            }
+        }
+    }
+
+    fn update_hooks(&mut self, ui: &mut Ui, is_editable: bool) {
+        let Some(current_workspace) = self.workspaces.current_workspace_mut() else {
+            return;
+        };
+
+        let mut should_save = false;
+        ui.collapsing(
+            tr!("hooks-heading", count = current_workspace.hooks.len()),
+            |ui| {
+                if is_editable {
+                    should_save |= update_hooks_editor(ui, &mut current_workspace.hooks);
+                } else {
+                    for hook in current_workspace.hooks.iter() {
+                        ui.label(format!("{}: {} - {}", hook.event, hook.name, hook.command));
+                    }
+                }
+            },
+        );
+
+        ui.collapsing(
+            tr!(
+                "environment-heading",
+                count = current_workspace.environment.len()
+            ),
+            |ui| {
+                if is_editable {
+                    should_save |= update_environment_editor(
+                        ui,
+                        &mut current_workspace.environment,
+                        &mut self.new_variable_name,
+                    );
+                } else {
+                    for (name, value) in current_workspace.environment.iter() {
+                        ui.label(format!("{name}={value}"));
+                    }
+                }
+            },
+        );
+
+        if should_save {
+            if let Err(why) = self.save_workspaces() {
+                self.report_error(tr!("status-error-writing"), why);
+            }
+        }
+    }
+
+    fn update_launchers(&mut self, ui: &mut Ui, is_editable: bool) {
+        let Some(current_workspace) = self.workspaces.current_workspace_mut() else {
+            return;
+        };
+
+        let mut should_save = false;
+        ui.collapsing(tr!("open-with-heading"), |ui| {
+            if is_editable {
+                should_save |= update_launchers_editor(ui, &mut current_workspace.launchers);
+            } else {
+                for launcher in
+                    current_workspace.default_launchers(&self.preferences.default_launcher)
+                {
+                    ui.label(launcher.to_string());
+                }
             }
         });
+
+        ui.collapsing(
+            tr!("exports-heading", count = current_workspace.exports.len()),
+            |ui| {
+                if is_editable {
+                    should_save |= update_exports_editor(ui, &mut current_workspace.exports);
+                } else {
+                    for export in current_workspace.exports.iter() {
+                        ui.label(format!("{}: {}", export.format, export.path));
+                    }
+                }
+
+                if !current_workspace.exports.is_empty()
+                    && ui.button(tr!("button-export-now")).clicked()
+                {
+                    should_save = true;
+                }
+            },
+        );
+
+        if should_save {
+            if let Err(why) = self.save_workspaces() {
+                self.report_error(tr!("status-error-writing"), why);
+            }
+        }
     }
 
-    fn update_bottom_panel(&mut self, context: &egui::Context, frame: &mut eframe::Frame) {
+    fn update_bottom_panel(&mut self, context: &egui::Context) {
         egui::TopBottomPanel::bottom("bottom_panel")
             .resizable(false)
             .min_height(0.0)
             .show(context, |ui| {
-                let dark_mode = ui.visuals().dark_mode;
                 ui.horizontal(|ui| {
-                    if self.workspaces.current_workspace().is_some()
-                        && ui.button("Open Workspace").clicked()
-                    {
-                        self.open_current_workspace(frame);
+                    if let Some(current_workspace) = self.workspaces.current_workspace() {
+                        let default_launchers =
+                            current_workspace.default_launchers(&self.preferences.default_launcher);
+                        let mut launchers: Vec<_> = Launcher::built_in().into();
+                        launchers.extend(
+                            current_workspace
+                                .launchers
+                                .iter()
+                                .filter(|launcher| matches!(launcher, Launcher::Command { .. }))
+                                .cloned(),
+                        );
+
+                        if ui.button(tr!("button-open-workspace")).clicked() {
+                            self.request_open_current_workspace(default_launchers);
+                        }
+
+                        ui.menu_button(tr!("button-open-in"), |ui| {
+                            for launcher in launchers {
+                                if ui.button(launcher.to_string()).clicked() {
+                                    ui.close_menu();
+                                    match launcher {
+                                        Launcher::SourceTree => {
+                                            self.request_open_current_workspace(vec![launcher])
+                                        }
+                                        launcher => {
+                                            self.start_repo_task(RepoTask::Launch(launcher))
+                                        }
+                                    }
+                                }
+                            }
+                        });
                     }
 
                     // if ui.button("Close SourceTree").clicked() && close_sourcetree().is_err() {
@@ -168,169 +944,1288 @@ impl SourceTreeWorkspacesApp {
                     // }
                 });
                 ui.separator();
-                ui.label(contrast_text(&self.status, false, dark_mode));
+                ui.label(&self.status);
+                if let Some(error_report) = self
+                    .error_report
+                    .as_ref()
+                    .filter(|error_report| error_report.status == self.status)
+                {
+                    error_report.show(ui);
+                }
                 ui.separator();
                 ui.vertical(|ui| {
-                    if self.settings_path.is_some() {
-                        ui.small(format!(
-                            "SourceTree Settings Path: {}",
-                            self.settings_path
-                                .as_ref()
-                                .unwrap()
-                                .as_os_str()
-                                .to_str()
-                                .unwrap()
+                    if let Some(settings_path) = self.settings_path.as_ref() {
+                        ui.small(tr!(
+                            "settings-path",
+                            path = settings_path.as_os_str().to_str().unwrap()
                         ));
                     } else {
-                        ui.small(
-                            "** SourceTree Settings Path NOT found. Try installing SourceTree. **",
-                        );
+                        ui.small(tr!("settings-path-missing"));
                     }
                 });
             });
     }
 
-    fn create_new_workspace(&mut self) {
-        info!("Creating new workspace...");
-        let new_workspace = Workspace::new("New Workspace", Uuid::new_v4());
-        self.workspaces
-            .workspaces
-            .insert(new_workspace.uuid, new_workspace);
-        let write_result = self.workspaces.write();
+    fn update_doctor_panel(&mut self, context: &egui::Context) {
+        let Some(doctor_panel) = self.doctor_panel.as_mut() else {
+            return;
+        };
 
-        if write_result.is_err() {
-            self.status = "Error creating workspace from current tabs".to_owned();
-        } else {
-            self.status = "Created workspace from current tabs".to_owned();
+        match doctor_panel.show(context) {
+            DoctorPanelResult::Open => {}
+            DoctorPanelResult::Closed => self.doctor_panel = None,
         }
-
-        self.workspaces.force_valid_workspace();
     }
 
-    fn create_workspace_from_current_tabs(&mut self) {
-        info!("Creating workspace from currently open tabs...");
-        let open_tabs = match OpenTabs::read() {
-            Ok(tabs) => tabs,
-            Err(_) => {
-                self.status = "Couldn't load current tabs to create workspace.".to_owned();
-                return;
-            }
+    fn update_log_panel(&mut self, context: &egui::Context) {
+        let Some(log_panel) = self.log_panel.as_mut() else {
+            return;
         };
-        let mut new_workspace: Workspace = (&open_tabs).into();
-        new_workspace.uuid = Uuid::new_v4();
-        self.workspaces
-            .workspaces
-            .insert(new_workspace.uuid, new_workspace);
-        let write_result = self.workspaces.write();
 
-        if write_result.is_err() {
-            self.status = "Error creating workspace from current tabs".to_owned();
-        } else {
-            self.status = "Created workspace from current tabs".to_owned();
+        match log_panel.show(context) {
+            LogPanelResult::Open => {}
+            LogPanelResult::Closed => self.log_panel = None,
         }
-
-        self.workspaces.force_valid_workspace();
     }
 
-    fn remove_current_workspace(&mut self) {
-        info!("Deleting current workspace...");
-        self.workspaces
-            .workspaces
-            .remove(&self.workspaces.current_workspace);
+    fn update_import_wizard(&mut self, context: &egui::Context) {
+        let Some(import_wizard) = self.import_wizard.as_mut() else {
+            return;
+        };
 
-        let write_result = self.workspaces.write();
+        match import_wizard.show(context) {
+            ImportWizardResult::Open => {}
+            ImportWizardResult::Cancelled => self.import_wizard = None,
+            ImportWizardResult::Imported(imported) => {
+                info!("Importing {} workspaces...", imported.workspaces.len());
+                let count = imported.workspaces.len();
+                self.workspaces.merge(*imported);
 
-        if write_result.is_err() {
-            self.status = "Error creating workspace from current tabs".to_owned();
-        } else {
-            self.status = "Created workspace from current tabs".to_owned();
-        }
+                if let Err(why) = self.save_workspaces() {
+                    self.report_error(tr!("status-error-importing"), why);
+                } else {
+                    self.status = tr!("status-imported", count = count);
+                }
 
-        //        self.workspaces.force_valid_workspace();
+                self.import_wizard = None;
+            }
+        }
     }
 
-    fn open_current_workspace(&mut self, frame: &mut eframe::Frame) {
-        if self.workspaces.write().is_err() {
-            info!("Didn't save workspace when closing.");
+    fn update_roots_editor(&mut self, context: &egui::Context) {
+        let Some(roots_editor) = self.roots_editor.as_mut() else {
+            return;
+        };
+
+        match roots_editor.show(context) {
+            RootsEditorResult::Open => {}
+            RootsEditorResult::Closed => self.roots_editor = None,
+            RootsEditorResult::Saved => match roots_editor.save() {
+                Ok(roots) => {
+                    self.roots = roots;
+                    self.status = tr!("status-saved-roots");
+                }
+                Err(why) => self.report_error(tr!("status-error-saving-roots"), why),
+            },
         }
+    }
 
-        let current_workspace = self.workspaces.current_workspace().unwrap();
-        let open_tabs: OpenTabs = current_workspace.into();
-        if OpenTabs::write(&open_tabs).is_err() {
-            self.status = "Couldn't write open tabs, so can't launch SourceTree.".to_owned();
+    fn update_settings_editor(&mut self, context: &egui::Context) {
+        let Some(settings_editor) = self.settings_editor.as_mut() else {
             return;
-        }
+        };
 
-        let spawn_result = std::process::Command::new(
-            sourcetree_exec_path()
-                .unwrap()
-                .as_os_str()
-                .to_str()
-                .unwrap(),
-        )
-        .spawn();
+        match settings_editor.show(context) {
+            SettingsEditorResult::Open => {}
+            SettingsEditorResult::Closed => self.settings_editor = None,
+            SettingsEditorResult::Saved => {
+                if let Err(why) = settings_editor.save() {
+                    self.report_error(tr!("status-error-saving-settings"), why);
+                } else {
+                    self.window_set_in_settings |=
+                        settings_editor.preferences.window != self.preferences.window;
+                    self.preferences = settings_editor.preferences.clone();
+                    self.preferences_unreadable = false;
+                    apply_preferences(context, &self.preferences);
+                    set_language(self.preferences.language.as_deref());
+                    self.status = tr!("status-saved-settings");
+                }
+            }
+        }
+    }
 
-        if spawn_result.is_err() {
-            self.status = "Couldn't launch SourceTree. Is it installed?".to_owned();
+    fn update_delete_prompt(&mut self, context: &egui::Context) {
+        let Some(uuids) = self.delete_prompt.as_ref() else {
             return;
+        };
+        let removing = match uuids.as_slice() {
+            [uuid] => self
+                .workspaces
+                .workspaces
+                .get(uuid)
+                .map(|workspace| workspace.name.clone())
+                .unwrap_or_default(),
+            uuids => tr!("delete-several", count = uuids.len()),
+        };
+
+        match show_delete_prompt(context, &removing) {
+            DeletePromptResult::Open => {}
+            DeletePromptResult::Cancelled => self.delete_prompt = None,
+            DeletePromptResult::Delete => {
+                if let Some(uuids) = self.delete_prompt.take() {
+                    self.remove_workspaces(uuids);
+                }
+            }
         }
+    }
 
-        if spawn_result.unwrap().wait().is_err() {
-            self.status = "Couldn't switch to SourceTree fully. Process error?".to_owned();
+    fn update_export_prompt(&mut self, context: &egui::Context) {
+        let Some(export_prompt) = self.export_prompt.as_mut() else {
             return;
-        }
+        };
 
-        frame.close();
+        match export_prompt.show(context) {
+            ExportPromptResult::Open => {}
+            ExportPromptResult::Cancelled => self.export_prompt = None,
+            ExportPromptResult::Export => {
+                let path = export_prompt.path();
+                let exported = self.workspaces.subset(&export_prompt.uuids);
+                match exported.write_to(&path) {
+                    Ok(()) => {
+                        self.status = tr!(
+                            "status-exported",
+                            count = exported.workspaces.len(),
+                            path = path.display().to_string()
+                        );
+                        self.export_prompt = None;
+                    }
+                    Err(why) => self.report_error(tr!("status-error-exporting"), why),
+                }
+            }
+        }
     }
-}
 
-impl From<&OpenTabs> for Workspace {
-    fn from(open_tabs: &OpenTabs) -> Self {
-        let mut uuid = Uuid::new_v4();
-        if open_tabs.workspace_id.is_some() {
-            uuid = open_tabs.workspace_id.unwrap();
+    /// Applies and keeps preferences changed outside Settings, such as the theme picker and the
+    /// text size shortcuts.
+    fn save_preferences(&mut self, context: &egui::Context) {
+        apply_preferences(context, &self.preferences);
+        if self.preferences_unreadable {
+            return;
         }
-
-        Workspace {
-            uuid,
-            name: "New Workspace".to_owned(),
-            repo_paths: open_tabs.tabs.clone(),
+        if let Err(why) = self.preferences.write() {
+            warn!("Couldn't save preferences. '{}'", why);
         }
     }
-}
 
-impl From<&Workspace> for OpenTabs {
-    fn from(workspace: &Workspace) -> Self {
-        OpenTabs {
-            tabs: workspace.repo_paths.clone(),
-            workspace_id: Some(workspace.uuid),
+    fn update_scan_wizard(&mut self, context: &egui::Context) {
+        let Some(scan_wizard) = self.scan_wizard.as_mut() else {
+            return;
+        };
+
+        let was_scanning = scan_wizard.is_scanning();
+        let result = scan_wizard.show(context);
+        if was_scanning || scan_wizard.is_scanning() {
+            self.status = scan_wizard.progress_text();
+        }
+
+        match result {
+            ScanWizardResult::Open => {}
+            ScanWizardResult::Cancelled => self.scan_wizard = None,
+            ScanWizardResult::Created(new_workspace) => {
+                info!("Creating workspace from scanned repositories...");
+                self.workspaces.current_workspace = new_workspace.uuid;
+                self.workspaces
+                    .workspaces
+                    .insert(new_workspace.uuid, *new_workspace);
+
+                if let Err(why) = self.save_workspaces() {
+                    self.report_error(tr!("status-error-creating-from-scan"), why);
+                } else {
+                    self.status = tr!("status-created-from-scan");
+                }
+
+                self.scan_wizard = None;
+            }
         }
     }
-}
 
-fn configure_text_styles(ctx: &egui::Context) {
-    use FontFamily::{Monospace, Proportional};
-
-    let mut style = (*ctx.style()).clone();
-    style.text_styles = [
-        (TextStyle::Heading, FontId::new(28.0, Proportional)),
-        (TextStyle::Body, FontId::new(18.0, Proportional)),
-        (TextStyle::Monospace, FontId::new(14.0, Monospace)),
-        (TextStyle::Button, FontId::new(14.0, Proportional)),
-        (TextStyle::Small, FontId::new(10.0, Proportional)),
-    ]
-    .into();
-    ctx.set_style(style);
-}
+    fn update_batch_run(&mut self, context: &egui::Context) {
+        let Some(batch_run) = self.batch_run.as_mut() else {
+            return;
+        };
+
+        let was_running = batch_run.is_running();
+        let open = batch_run.show(context);
+        if was_running {
+            self.status = batch_run.progress_text();
+        }
+
+        if !open && !batch_run.is_running() {
+            self.batch_run = None;
+        }
+    }
+
+    fn run_git_operation(&mut self, operation: GitOperation, repo_paths: Vec<String>) {
+        let Some(current_workspace) = self.workspaces.current_workspace() else {
+            return;
+        };
+
+        info!(
+            "Running {} across workspace {}...",
+            operation, current_workspace.name
+        );
+        self.batch_run = Some(BatchRun::spawn(
+            &current_workspace.name,
+            repo_paths,
+            &operation,
+        ));
+    }
+
+    fn update_quick_switcher(&mut self, context: &egui::Context) {
+        let Some(quick_switcher) = self.quick_switcher.as_mut() else {
+            return;
+        };
+
+        match quick_switcher.show(context, &self.workspaces) {
+            QuickSwitcherResult::Open => {}
+            QuickSwitcherResult::Cancelled => self.quick_switcher = None,
+            QuickSwitcherResult::Switch(uuid) => {
+                self.quick_switcher = None;
+                self.switch_to(uuid);
+            }
+        }
+    }
+
+    fn switch_to(&mut self, uuid: Uuid) {
+        let Some(workspace) = self.workspaces.workspaces.get(&uuid) else {
+            return;
+        };
+
+        let launchers = workspace.default_launchers(&self.preferences.default_launcher);
+        self.workspaces.current_workspace = uuid;
+        self.request_open_current_workspace(launchers);
+    }
+
+    fn update_resident(&mut self, context: &egui::Context, frame: &mut eframe::Frame) {
+        if self.hide_requested {
+            self.hide_requested = false;
+            frame.set_visible(false);
+        }
+
+        let Some(resident) = self.resident.as_mut() else {
+            return;
+        };
+
+        for event in resident.poll(&self.workspaces) {
+            match event {
+                ResidentEvent::ShowSwitcher => {
+                    frame.set_visible(true);
+                    frame.set_minimized(false);
+                    self.quick_switcher = Some(QuickSwitcher::new());
+                }
+                ResidentEvent::Open(uuid) => {
+                    frame.set_visible(true);
+                    self.switch_to(uuid);
+                }
+                ResidentEvent::Quit => {
+                    self.quitting = true;
+                    frame.close();
+                }
+            }
+        }
+
+        self.update_tabs_watcher();
+
+        // Keep listening to the tray and hotkey while hidden.
+        context.request_repaint_after(Duration::from_millis(250));
+    }
+
+    fn start_tabs_watcher(&mut self) {
+        let config = &self.tabs_watcher_config;
+        let Some(path) = OpenTabs::path().filter(|_| config.enabled) else {
+            return;
+        };
+
+        let watcher = spawn_file_watcher(path, Duration::from_millis(config.debounce_ms));
+        self.tabs_watcher = Some((watcher, TabsSync::new(config.conflict_policy)));
+    }
+
+    fn update_tabs_watcher(&mut self) {
+        let Some((watcher, sync)) = self.tabs_watcher.as_mut() else {
+            return;
+        };
+
+        if watcher.changes.try_recv().is_err() {
+            return;
+        }
+
+        let open_tabs = match OpenTabs::read() {
+            Ok(open_tabs) => open_tabs,
+            Err(why) => {
+                warn!("Couldn't read changed open tabs. '{}'", why);
+                return;
+            }
+        };
+
+        self.status = match sync.apply(&mut self.workspaces, &open_tabs.tabs, &self.roots) {
+            SyncOutcome::Unchanged => return,
+            SyncOutcome::Updated => tr!("status-tabs-saved"),
+            SyncOutcome::Merged => tr!("status-tabs-merged"),
+            SyncOutcome::KeptEditor => tr!("status-tabs-kept"),
+        };
+
+        if let Err(why) = self.save_workspaces() {
+            self.report_error(tr!("status-error-saving-tabs"), why);
+        }
+    }
+
+    fn update_instance_requests(&mut self, context: &egui::Context, frame: &mut eframe::Frame) {
+        let Some(server) = self.instance_server.as_ref() else {
+            return;
+        };
+
+        let requests: Vec<_> = server.requests.try_iter().collect();
+        for (request, reply) in requests {
+            let response = match request {
+                InstanceRequest::Show => {
+                    frame.set_visible(true);
+                    frame.set_minimized(false);
+                    InstanceResponse {
+                        handled: true,
+                        message: tr!("instance-showed"),
+                    }
+                }
+                InstanceRequest::Open { uuid } => match self.workspaces.workspaces.get(&uuid) {
+                    Some(workspace) => {
+                        let message = tr!("instance-opening", name = workspace.name.as_str());
+                        frame.set_visible(true);
+                        frame.set_minimized(false);
+                        self.switch_to(uuid);
+                        InstanceResponse {
+                            handled: true,
+                            message,
+                        }
+                    }
+                    None => InstanceResponse {
+                        handled: false,
+                        message: tr!("cli-no-match"),
+                    },
+                },
+                InstanceRequest::AutoUpdate { show } => {
+                    let response = match self.sync_session() {
+                        Ok(()) => InstanceResponse {
+                            handled: true,
+                            message: tr!("instance-updated"),
+                        },
+                        Err(why) => InstanceResponse {
+                            handled: false,
+                            message: tr!("instance-update-failed", error = why.to_string()),
+                        },
+                    };
+                    self.status = response.message.clone();
+                    if show {
+                        frame.set_visible(true);
+                        frame.set_minimized(false);
+                    }
+                    response
+                }
+            };
+
+            // The other instance may have given up waiting.
+            let _ = reply.send(response);
+        }
+
+        // Keep listening while idle or hidden.
+        context.request_repaint_after(Duration::from_millis(250));
+    }
+
+    fn update_workspaces_watcher(&mut self, context: &egui::Context) {
+        let Some(watcher) = self.workspaces_watcher.as_ref() else {
+            return;
+        };
+
+        // Keep checking while idle or hidden.
+        context.request_repaint_after(Duration::from_millis(250));
+        if watcher.changes.try_recv().is_err() {
+            return;
+        }
+
+        // Our own saves show up as changes too.
+        match Workspaces::read_personal() {
+            Ok(theirs) if theirs == self.saved_workspaces => {}
+            Ok(_) => self.external_change = true,
+            Err(why) => warn!("Couldn't read changed workspaces. '{}'", why),
+        }
+    }
+
+    fn update_external_change(&mut self, context: &egui::Context) {
+        if !self.external_change {
+            return;
+        }
+
+        match show_external_change_prompt(context) {
+            ExternalChangeResult::Open => {}
+            ExternalChangeResult::Reload => {
+                self.external_change = false;
+                self.merge_external_changes();
+                if self.workspaces.personal() != self.saved_workspaces {
+                    if let Err(why) = self.save_workspaces() {
+                        self.report_error(tr!("status-error-writing"), why);
+                    }
+                }
+            }
+            ExternalChangeResult::KeepMine => {
+                self.external_change = false;
+                if let Err(why) = self.write_workspaces() {
+                    self.report_error(tr!("status-error-writing"), why);
+                }
+            }
+        }
+    }
+
+    fn update_merge_conflicts(&mut self, context: &egui::Context) {
+        let Some(merge_conflicts) = self.merge_conflicts.as_mut() else {
+            return;
+        };
+
+        match merge_conflicts.show(context) {
+            MergeConflictsResult::Open => {}
+            MergeConflictsResult::Closed => self.merge_conflicts = None,
+            MergeConflictsResult::UseTheirs(conflict) => {
+                if merge_conflicts.is_empty() {
+                    self.merge_conflicts = None;
+                }
+
+                match conflict.theirs {
+                    Some(theirs) => {
+                        self.workspaces.workspaces.insert(conflict.uuid, theirs);
+                    }
+                    None => {
+                        self.workspaces.workspaces.remove(&conflict.uuid);
+                        if self.workspaces.current_workspace == conflict.uuid {
+                            self.workspaces.current_workspace = Uuid::nil();
+                            self.workspaces.force_valid_workspace();
+                        }
+                    }
+                }
+
+                if let Err(why) = self.save_workspaces() {
+                    self.report_error(tr!("status-error-writing"), why);
+                }
+            }
+        }
+    }
+
+    fn track_window(&mut self, frame: &eframe::Frame) {
+        let window_info = &frame.info().window_info;
+        if window_info.minimized || window_info.maximized || window_info.fullscreen {
+            return;
+        }
+
+        self.window = Some(WindowGeometry {
+            width: window_info.size.x,
+            height: window_info.size.y,
+            position: window_info.position.map(Into::into),
+        });
+    }
+
+    /// Keeps the window and list as they are for the next run.
+    fn save_ui_state(&mut self) {
+        self.ui_state.selected_workspace =
+            Some(self.workspaces.current_workspace).filter(|uuid| !uuid.is_nil());
+        if self.ui_state_unreadable {
+            info!("Didn't save the UI state over the file that couldn't be read.");
+        } else if let Err(why) = self.ui_state.write() {
+            warn!("Couldn't save the UI state. '{}'", why);
+        }
+
+        let Some(window) = self.window.filter(|window| {
+            *window != self.preferences.window
+                && !self.window_set_in_settings
+                && !self.preferences_unreadable
+        }) else {
+            return;
+        };
+        self.preferences.window = window;
+        if let Err(why) = self.preferences.write() {
+            warn!("Couldn't save the window geometry. '{}'", why);
+        }
+    }
+
+    fn report_error(&mut self, status: impl Into<String>, error: anyhow::Error) {
+        self.status = status.into();
+        warn!("{} '{:#}'", self.status, error);
+        self.error_report = Some(ErrorReport::new(&self.status, &error));
+    }
+
+    /// Saves the workspaces, first merging in edits made to the file by something else.
+    fn save_workspaces(&mut self) -> anyhow::Result<()> {
+        self.merge_external_changes();
+        self.write_workspaces()
+    }
+
+    fn write_workspaces(&mut self) -> anyhow::Result<()> {
+        self.workspaces.write()?;
+        self.saved_workspaces = self.workspaces.personal();
+        Ok(())
+    }
+
+    fn merge_external_changes(&mut self) {
+        let theirs = match Workspaces::read_personal() {
+            Ok(theirs) => theirs,
+            Err(why) => {
+                warn!("Couldn't read workspaces to merge. '{}'", why);
+                return;
+            }
+        };
+
+        if theirs == self.saved_workspaces {
+            return;
+        }
+
+        info!("Workspaces were changed elsewhere. Merging...");
+        let outcome =
+            merge_workspaces(&self.saved_workspaces, &self.workspaces.personal(), &theirs);
+        self.workspaces.replace_personal(outcome.merged);
+        self.saved_workspaces = theirs;
+        self.external_change = false;
+
+        if outcome.conflicts.is_empty() {
+            self.status = tr!("status-merged-external");
+            return;
+        }
+
+        self.status = tr!(
+            "status-merged-external-conflicts",
+            count = outcome.conflicts.len()
+        );
+        match self.merge_conflicts.as_mut() {
+            Some(merge_conflicts) => merge_conflicts.extend(outcome.conflicts),
+            None => self.merge_conflicts = Some(MergeConflicts::new(outcome.conflicts)),
+        }
+    }
+
+    /// SourceTree may still be running, from a switch in resident mode or opened by hand, so fold
+    /// its tabs back into their workspace the same way a one-shot run does at startup.
+    fn sync_session(&mut self) -> anyhow::Result<()> {
+        // The session sync saves the tabs itself as SourceTree closes.
+        self.tabs_watcher = None;
+        let current_workspace = self.workspaces.current_workspace;
+        self.workspaces = session::sync_last_session(true)?;
+        self.saved_workspaces = self.workspaces.personal();
+        if self.workspaces.workspaces.contains_key(&current_workspace) {
+            self.workspaces.current_workspace = current_workspace;
+        }
+        Ok(())
+    }
+
+    /// Starts switching to the current workspace once its repos are resolved.
+    fn request_open_current_workspace(&mut self, launchers: Vec<Launcher>) {
+        // Already switching, waiting on rules, hooks or on whether hooks may run.
+        if self.repo_task.is_some() || self.hook_run.is_some() || self.hook_trust_prompt.is_some() {
+            return;
+        }
+
+        self.pending_launchers = launchers;
+        self.start_repo_task(RepoTask::Switch);
+    }
+
+    /// Resolves the current workspace's repos on a background thread, since rules may walk
+    /// directory trees, then carries on with the task.
+    fn start_repo_task(&mut self, task: RepoTask) {
+        if self.repo_task.is_some() {
+            return;
+        }
+        let Some(current_workspace) = self.workspaces.current_workspace() else {
+            return;
+        };
+
+        if !current_workspace.rules.is_empty() {
+            self.status = tr!(
+                "status-resolving-repos",
+                name = current_workspace.name.as_str()
+            );
+        }
+        self.repo_task = Some((RulePreview::spawn(current_workspace, &self.roots), task));
+    }
+
+    fn update_repo_task(&mut self, context: &egui::Context, frame: &mut eframe::Frame) {
+        let Some((rule_preview, _)) = self.repo_task.as_mut() else {
+            return;
+        };
+
+        let Some(repo_paths) = rule_preview.repo_paths().cloned() else {
+            context.request_repaint_after(Duration::from_millis(250));
+            return;
+        };
+        let workspace_id = rule_preview.workspace_id;
+        let Some((_, task)) = self.repo_task.take() else {
+            return;
+        };
+
+        // Another workspace was picked meanwhile.
+        if workspace_id != self.workspaces.current_workspace {
+            self.pending_launchers.clear();
+            return;
+        }
+
+        match task {
+            RepoTask::Switch => self.check_before_switch(frame, repo_paths),
+            RepoTask::Launch(launcher) => self.launch_current_workspace(&launcher, repo_paths),
+            RepoTask::GitOperation(operation) => self.run_git_operation(operation, repo_paths),
+            RepoTask::Snapshot => self.take_snapshot(repo_paths),
+        }
+    }
+
+    /// Checks the repos that will be closed before switching, when SourceTree is being opened.
+    fn check_before_switch(&mut self, frame: &mut eframe::Frame, repo_paths: Vec<String>) {
+        let dirty_guard = match self.pending_launchers.contains(&Launcher::SourceTree) {
+            true => self.check_outgoing_repos(&repo_paths),
+            false => None,
+        };
+        self.switch_repo_paths = repo_paths;
+
+        match dirty_guard {
+            Some(dirty_guard) => self.dirty_guard = Some(dirty_guard),
+            None => self.continue_open_current_workspace(frame),
+        }
+    }
+
+    fn continue_open_current_workspace(&mut self, frame: &mut eframe::Frame) {
+        let Some(current_workspace) = self.workspaces.current_workspace() else {
+            return;
+        };
+
+        if current_workspace.snapshots.is_empty() {
+            self.open_current_workspace(frame);
+        } else {
+            self.restore_prompt = Some(RestorePrompt::new(current_workspace));
+        }
+    }
+
+    /// Looks for unfinished work in the open repos that aren't part of the current workspace.
+    fn check_outgoing_repos(&self, incoming_repo_paths: &[String]) -> Option<DirtyGuardPrompt> {
+        let current_workspace = self.workspaces.current_workspace()?;
+        let open_tabs = OpenTabs::read().ok()?;
+        if open_tabs.workspace_id == Some(current_workspace.uuid) {
+            return None;
+        }
+
+        let outgoing_repo_paths: Vec<String> = open_tabs
+            .tabs
+            .iter()
+            .map(|tab| self.roots.expand(tab))
+            .filter(|repo_path| !incoming_repo_paths.contains(repo_path))
+            .collect();
+
+        let states = repos_needing_attention(&outgoing_repo_paths);
+        if states.is_empty() {
+            return None;
+        }
+
+        let outgoing_workspace = open_tabs
+            .workspace_id
+            .and_then(|uuid| self.workspaces.workspaces.get(&uuid));
+        let outgoing_name = outgoing_workspace
+            .map(|workspace| workspace.name.clone())
+            .unwrap_or_else(|| tr!("dirty-guard-open-tabs"));
+
+        Some(DirtyGuardPrompt::new(
+            outgoing_workspace.map(|workspace| workspace.uuid),
+            &outgoing_name,
+            states,
+        ))
+    }
+
+    fn update_dirty_guard(&mut self, context: &egui::Context, frame: &mut eframe::Frame) {
+        let Some(dirty_guard) = self.dirty_guard.as_mut() else {
+            return;
+        };
+
+        match dirty_guard.show(context) {
+            DirtyGuardResult::Open => {}
+            DirtyGuardResult::Cancelled => self.dirty_guard = None,
+            DirtyGuardResult::Continue => {
+                self.dirty_guard = None;
+                self.continue_open_current_workspace(frame);
+            }
+            DirtyGuardResult::StashAndContinue => {
+                let dirty_guard = self.dirty_guard.take().unwrap();
+                if self.stash_outgoing_repos(dirty_guard) {
+                    self.continue_open_current_workspace(frame);
+                }
+            }
+        }
+    }
+
+    /// Stashes the outgoing repos' changes and records them on their workspace. Returns false, and
+    /// shows what went wrong, if any repo couldn't be stashed.
+    fn stash_outgoing_repos(&mut self, dirty_guard: DirtyGuardPrompt) -> bool {
+        let Some(outgoing_workspace) = dirty_guard
+            .outgoing_workspace
+            .and_then(|uuid| self.workspaces.workspaces.get_mut(&uuid))
+        else {
+            return false;
+        };
+
+        info!("Stashing changes in {}...", outgoing_workspace.name);
+        let results: Vec<_> = dirty_guard
+            .states
+            .iter()
+            .map(|state| {
+                stash_changes(
+                    &state.repo_path,
+                    &outgoing_workspace.name,
+                    outgoing_workspace.uuid,
+                )
+            })
+            .collect();
+
+        for result in &results {
+            if result.outcome == RepoOutcome::Succeeded {
+                outgoing_workspace.pending_stashes.push(PendingStash {
+                    repo_path: result.repo_path.clone(),
+                    message: result.summary.clone(),
+                });
+            }
+        }
 
-fn contrast_text(text: &str, highlight: bool, dark_mode: bool) -> RichText {
-    if highlight == dark_mode {
-        RichText::new(text)
-            .color(Color32::from_rgb(10, 10, 10))
-            .background_color(Color32::from_rgb(255, 255, 255))
-    } else {
-        RichText::new(text)
-            .color(Color32::from_rgb(200, 200, 200))
-            .background_color(Color32::from_rgb(27, 27, 27))
+        let title = tr!("stash-title", name = outgoing_workspace.name.as_str());
+        if let Err(why) = self.save_workspaces() {
+            self.report_error(tr!("status-error-recording-stash"), why);
+        }
+
+        let failed = results
+            .iter()
+            .filter(|result| result.outcome == RepoOutcome::Failed)
+            .count();
+        if failed == 0 {
+            self.status = tr!("status-stashed");
+            return true;
+        }
+
+        self.status = tr!("status-stash-failed", count = failed);
+        self.batch_run = Some(BatchRun::from_results(&title, results));
+        false
+    }
+
+    /// Pops the changes stashed when the current workspace was last switched away from. Stashes
+    /// that can't be popped cleanly are kept, and shown.
+    fn restore_pending_stashes(&mut self) {
+        let Some(current_workspace) = self.workspaces.current_workspace_mut() else {
+            return;
+        };
+
+        if current_workspace.pending_stashes.is_empty() {
+            return;
+        }
+
+        info!("Restoring stashed changes in {}...", current_workspace.name);
+        let pending_stashes = std::mem::take(&mut current_workspace.pending_stashes);
+        let results: Vec<_> = pending_stashes.iter().map(pop_stash).collect();
+        for (stash, result) in pending_stashes.into_iter().zip(&results) {
+            if result.outcome == RepoOutcome::Failed {
+                current_workspace.pending_stashes.push(stash);
+            }
+        }
+
+        let failed = current_workspace.pending_stashes.len();
+        if failed == 0 {
+            return;
+        }
+
+        warn!(
+            "{} stashes couldn't be restored in {}.",
+            failed, current_workspace.name
+        );
+        let title = tr!("unstash-title", name = current_workspace.name.as_str());
+        self.switch_failures
+            .push(tr!("switch-failed-unstash", count = failed));
+        self.batch_run = Some(BatchRun::from_results(&title, results));
+    }
+
+    fn update_restore_prompt(&mut self, context: &egui::Context, frame: &mut eframe::Frame) {
+        let Some(restore_prompt) = self.restore_prompt.as_mut() else {
+            return;
+        };
+
+        let Some(current_workspace) = self.workspaces.current_workspace() else {
+            self.restore_prompt = None;
+            return;
+        };
+
+        match restore_prompt.show(context, current_workspace) {
+            RestorePromptResult::Open => {}
+            RestorePromptResult::Cancelled => self.restore_prompt = None,
+            RestorePromptResult::OpenWithoutRestoring => {
+                self.restore_prompt = None;
+                self.open_current_workspace(frame);
+            }
+            RestorePromptResult::RestoreAndOpen(index) => {
+                self.restore_prompt = None;
+                if self.restore_snapshot(index) {
+                    self.open_current_workspace(frame);
+                }
+            }
+        }
+    }
+
+    fn take_snapshot(&mut self, repo_paths: Vec<String>) {
+        let Some(current_workspace) = self.workspaces.current_workspace_mut() else {
+            return;
+        };
+
+        info!("Taking snapshot of {}...", current_workspace.name);
+        let snapshot =
+            BranchSnapshot::capture(&repo_paths, self.snapshot_label.trim(), &self.roots);
+        push_snapshot(&mut current_workspace.snapshots, snapshot);
+        self.snapshot_label.clear();
+
+        if let Err(why) = self.save_workspaces() {
+            self.report_error(tr!("status-error-writing-snapshot"), why);
+        } else {
+            self.status = tr!("status-took-snapshot");
+        }
+    }
+
+    /// Checks out a snapshot's branches in the current workspace. Returns false, and shows what
+    /// went wrong, if any repo couldn't be switched.
+    fn restore_snapshot(&mut self, index: usize) -> bool {
+        let Some(current_workspace) = self.workspaces.current_workspace() else {
+            return false;
+        };
+
+        let Some(snapshot) = current_workspace.snapshots.get(index) else {
+            return false;
+        };
+
+        info!(
+            "Restoring snapshot {} of {}...",
+            snapshot.description(),
+            current_workspace.name
+        );
+        let results = snapshot.restore(&self.roots);
+        let failed = results
+            .iter()
+            .filter(|result| result.outcome == RepoOutcome::Failed)
+            .count();
+
+        if failed == 0 {
+            self.status = tr!("status-restored-snapshot");
+            return true;
+        }
+
+        self.status = tr!("status-restore-failed", count = failed);
+        self.batch_run = Some(BatchRun::from_results(
+            &tr!("restore-title", name = current_workspace.name.as_str()),
+            results,
+        ));
+        false
+    }
+
+    fn update_sources_editor(&mut self, context: &egui::Context) {
+        let Some(sources_editor) = self.sources_editor.as_mut() else {
+            return;
+        };
+
+        match sources_editor.show(context) {
+            SourcesEditorResult::Open => {}
+            SourcesEditorResult::Closed => self.sources_editor = None,
+            SourcesEditorResult::Saved => {
+                let config = match sources_editor.save() {
+                    Ok(config) => config,
+                    Err(why) => {
+                        self.report_error(tr!("status-error-saving-sources"), why);
+                        return;
+                    }
+                };
+
+                self.sources_editor = None;
+                match self.workspaces.load_sources(&config) {
+                    Ok(_) => self.status = tr!("status-reloaded-sources"),
+                    Err(why) => self.report_error(tr!("status-error-loading-sources"), why),
+                }
+            }
+        }
+    }
+
+    fn pull_catalogue(&mut self) {
+        let Some(catalogue) = self.workspaces.catalogue.clone() else {
+            return;
+        };
+
+        info!("Pulling shared catalogue...");
+        let pull_result = catalogue.pull().and_then(|_| {
+            self.workspaces
+                .load_sources(&LocalConfig::read_or_default()?)
+        });
+
+        match pull_result {
+            Ok(_) => self.status = tr!("status-pulled-catalogue"),
+            Err(why) => self.report_error(tr!("status-error-pulling-catalogue"), why),
+        }
+    }
+
+    fn commit_catalogue(&mut self) {
+        let Some(catalogue) = self.workspaces.catalogue.clone() else {
+            return;
+        };
+
+        info!("Committing shared catalogue...");
+        let commit_result = self
+            .workspaces
+            .write()
+            .and_then(|_| catalogue.commit("Update shared workspaces"));
+
+        match commit_result {
+            Ok(true) => self.status = tr!("status-committed-catalogue"),
+            Ok(false) => self.status = tr!("status-nothing-to-commit"),
+            Err(why) => self.report_error(tr!("status-error-committing-catalogue"), why),
+        }
+    }
+
+    fn copy_current_workspace_to_mine(&mut self) {
+        info!("Copying shared workspace to personal workspaces...");
+        let Some(copy_uuid) = self
+            .workspaces
+            .copy_to_personal(self.workspaces.current_workspace)
+        else {
+            return;
+        };
+
+        self.workspaces.current_workspace = copy_uuid;
+        if let Err(why) = self.save_workspaces() {
+            self.report_error(tr!("status-error-copying"), why);
+        } else {
+            self.status = tr!("status-copied");
+        }
+    }
+
+    fn create_new_workspace(&mut self) {
+        info!("Creating new workspace...");
+        let new_workspace = Workspace::new(&tr!("workspace-new-name"), Uuid::new_v4());
+        self.workspaces
+            .workspaces
+            .insert(new_workspace.uuid, new_workspace);
+        if let Err(why) = self.save_workspaces() {
+            self.report_error(tr!("status-error-creating"), why);
+        } else {
+            self.status = tr!("status-created");
+        }
+
+        self.workspaces.force_valid_workspace();
+    }
+
+    fn create_dynamic_workspace(&mut self) {
+        info!("Creating dynamic workspace...");
+        let mut new_workspace = Workspace::new(&tr!("workspace-new-dynamic-name"), Uuid::new_v4());
+        new_workspace.rules.push(WorkspaceRule::ReposUnder {
+            root: String::new(),
+            max_depth: 2,
+        });
+        self.workspaces.current_workspace = new_workspace.uuid;
+        self.workspaces
+            .workspaces
+            .insert(new_workspace.uuid, new_workspace);
+
+        if let Err(why) = self.save_workspaces() {
+            self.report_error(tr!("status-error-creating-dynamic"), why);
+        } else {
+            self.status = tr!("status-created-dynamic");
+        }
+    }
+
+    fn create_workspace_from_current_tabs(&mut self) {
+        info!("Creating workspace from currently open tabs...");
+        let open_tabs = match OpenTabs::read() {
+            Ok(tabs) => tabs,
+            Err(why) => {
+                self.report_error(tr!("status-error-loading-tabs"), why);
+                return;
+            }
+        };
+        let mut new_workspace = open_tabs.to_workspace(&self.roots);
+        new_workspace.uuid = Uuid::new_v4();
+        self.workspaces
+            .workspaces
+            .insert(new_workspace.uuid, new_workspace);
+        if let Err(why) = self.save_workspaces() {
+            self.report_error(tr!("status-error-creating-from-tabs"), why);
+        } else {
+            self.status = tr!("status-created-from-tabs");
+        }
+
+        self.workspaces.force_valid_workspace();
+    }
+
+    fn request_remove_workspaces(&mut self, uuids: Vec<Uuid>) {
+        if uuids.is_empty() {
+            return;
+        }
+
+        match self.preferences.confirm_delete {
+            true => self.delete_prompt = Some(uuids),
+            false => self.remove_workspaces(uuids),
+        }
+    }
+
+    /// Removes the workspaces and selects the one listed after them.
+    fn remove_workspaces(&mut self, uuids: Vec<Uuid>) {
+        info!("Deleting workspaces {:?}...", uuids);
+        let order = display_order(&self.workspaces, self.ui_state.sort_mode, &self.filter);
+        if uuids.contains(&self.workspaces.current_workspace) {
+            self.workspaces.current_workspace = neighbour(&order, &uuids).unwrap_or_default();
+        }
+        for uuid in uuids.iter() {
+            self.workspaces.workspaces.remove(uuid);
+        }
+        self.workspaces.force_valid_workspace();
+        self.selection
+            .select_only(self.workspaces.current_workspace);
+
+        if let Err(why) = self.save_workspaces() {
+            self.report_error(tr!("status-error-deleting"), why);
+        } else {
+            self.status = tr!("status-deleted", count = uuids.len());
+        }
+    }
+
+    fn merge_workspaces(&mut self, uuids: &[Uuid]) {
+        let Some(uuid) = self.workspaces.merge_into_new(uuids) else {
+            return;
+        };
+        self.workspaces.current_workspace = uuid;
+        self.selection.select_only(uuid);
+
+        if let Err(why) = self.save_workspaces() {
+            self.report_error(tr!("status-error-merging"), why);
+        } else {
+            self.status = tr!("status-merged", count = uuids.len());
+        }
+    }
+
+    /// The workspace whose tabs are open, when it's about to be switched away from.
+    fn outgoing_workspace(&self) -> Option<&Workspace> {
+        OpenTabs::read()
+            .ok()
+            .and_then(|open_tabs| open_tabs.workspace_id)
+            .filter(|uuid| *uuid != self.workspaces.current_workspace)
+            .and_then(|uuid| self.workspaces.workspaces.get(&uuid))
+    }
+
+    /// Hooks from the team catalogue or a project's `.st-workspace` only run once trusted here.
+    fn trusts_hooks(&self, workspace: &Workspace) -> bool {
+        workspace.source == WorkspaceSource::Personal
+            || self
+                .trusted_hooks
+                .contains(&hooks_fingerprint(&workspace.hooks, &workspace.environment))
+    }
+
+    /// The workspace's hooks for the event, or none when they aren't trusted.
+    fn runnable_hooks(&self, workspace: &Workspace, event: HookEvent) -> Vec<WorkspaceHook> {
+        match self.trusts_hooks(workspace) {
+            true => workspace
+                .hooks
+                .iter()
+                .filter(|hook| hook.enabled && hook.event == event)
+                .cloned()
+                .collect(),
+            false => Vec::new(),
+        }
+    }
+
+    fn update_hook_trust_prompt(&mut self, context: &egui::Context, frame: &mut eframe::Frame) {
+        let Some(hook_trust_prompt) = self.hook_trust_prompt.as_ref() else {
+            return;
+        };
+
+        match hook_trust_prompt.show(context) {
+            HookTrustPromptResult::Open => {}
+            HookTrustPromptResult::Cancelled => {
+                self.hook_trust_prompt = None;
+                self.pending_launchers.clear();
+            }
+            HookTrustPromptResult::Skipped => {
+                self.hook_trust_prompt = None;
+                self.start_switch(frame);
+            }
+            HookTrustPromptResult::Trusted => {
+                let fingerprints = hook_trust_prompt.fingerprints().to_vec();
+                self.hook_trust_prompt = None;
+                self.trust_hooks(fingerprints);
+                self.start_switch(frame);
+            }
+        }
+    }
+
+    /// Remembers that the hooks may run, on this machine, until they change.
+    fn trust_hooks(&mut self, fingerprints: Vec<Uuid>) {
+        self.trusted_hooks.extend(fingerprints);
+        let trusted_hooks = self.trusted_hooks.clone();
+        let write_result = LocalConfig::read_or_default().and_then(|mut config| {
+            config.trusted_hooks = trusted_hooks;
+            config.write()
+        });
+        if let Err(why) = write_result {
+            warn!("Couldn't remember the trusted hooks. '{}'", why);
+        }
+    }
+
+    fn update_hook_run(&mut self, context: &egui::Context, frame: &mut eframe::Frame) {
+        let Some(hook_run) = self.hook_run.as_ref() else {
+            return;
+        };
+
+        let Some(results) = hook_run.show(context) else {
+            return;
+        };
+        let event = hook_run.event;
+        self.hook_run = None;
+        self.add_hook_failures(&results);
+
+        match event {
+            HookEvent::BeforeClose => self.launch_switch(frame),
+            HookEvent::AfterOpen => self.finish_switch(frame),
+        }
+    }
+
+    fn add_hook_failures(&mut self, results: &[HookResult]) {
+        self.switch_failures.extend(
+            results
+                .iter()
+                .filter(|result| !result.succeeded)
+                .map(|result| format!("{} ({})", result.name, result.summary)),
+        );
+    }
+
+    /// Opens the current workspace in something other than SourceTree, leaving this window open.
+    fn launch_current_workspace(&mut self, launcher: &Launcher, repo_paths: Vec<String>) {
+        let Some(current_workspace) = self.workspaces.current_workspace() else {
+            return;
+        };
+
+        info!("Opening {} in {}...", current_workspace.name, launcher);
+        match launcher.launch(current_workspace, repo_paths) {
+            Ok(()) => {
+                self.status = tr!(
+                    "status-opened-in",
+                    name = current_workspace.name.as_str(),
+                    launcher = launcher.to_string()
+                )
+            }
+            Err(why) => self.report_error(
+                tr!("status-error-opening-in", launcher = launcher.to_string()),
+                why,
+            ),
+        }
+    }
+
+    /// Switches to the current workspace, first asking whether hooks that someone else wrote may
+    /// run.
+    fn open_current_workspace(&mut self, frame: &mut eframe::Frame) {
+        let mut untrusted = Vec::new();
+        if let Some(outgoing) = self.outgoing_workspace() {
+            if !self.trusts_hooks(outgoing) {
+                untrusted.push((outgoing, HookEvent::BeforeClose));
+            }
+        }
+        if let Some(current_workspace) = self.workspaces.current_workspace() {
+            if !self.trusts_hooks(current_workspace) {
+                untrusted.push((current_workspace, HookEvent::AfterOpen));
+            }
+        }
+
+        match HookTrustPrompt::new(&untrusted) {
+            Some(hook_trust_prompt) => self.hook_trust_prompt = Some(hook_trust_prompt),
+            None => self.start_switch(frame),
+        }
+    }
+
+    /// Runs the outgoing workspace's hooks, then carries on with the switch once they're done.
+    fn start_switch(&mut self, frame: &mut eframe::Frame) {
+        self.switch_failures.clear();
+        let hook_run = self.outgoing_workspace().and_then(|outgoing| {
+            let hooks = self.runnable_hooks(outgoing, HookEvent::BeforeClose);
+            (!hooks.is_empty())
+                .then(|| HookRun::spawn(outgoing, hooks, HookEvent::BeforeClose, &self.roots))
+        });
+
+        match hook_run {
+            Some(hook_run) => self.hook_run = Some(hook_run),
+            None => self.launch_switch(frame),
+        }
+    }
+
+    fn launch_switch(&mut self, frame: &mut eframe::Frame) {
+        let mut launchers = std::mem::take(&mut self.pending_launchers);
+        if launchers.is_empty() {
+            launchers.push(Launcher::SourceTree);
+        }
+
+        // SourceTree goes first, since it's waited on and the switch stops if it can't start.
+        launchers.sort_by_key(|launcher| *launcher != Launcher::SourceTree);
+
+        if self.resident.is_some() {
+            if let Err(why) = self.sync_session() {
+                warn!("Couldn't save the last session's tabs. '{}'", why);
+            }
+        }
+        self.restore_pending_stashes();
+        if self.save_workspaces().is_err() {
+            info!("Didn't save workspace when closing.");
+        }
+
+        let Some(current_workspace) = self.workspaces.current_workspace() else {
+            let status = tr!("status-error-workspace-gone");
+            self.report_error(status.clone(), anyhow::anyhow!(status));
+            return;
+        };
+        for launcher in launchers {
+            match launcher.launch(current_workspace, self.switch_repo_paths.clone()) {
+                Ok(()) => {}
+                Err(why) if launcher == Launcher::SourceTree => {
+                    self.report_error(why.to_string(), why);
+                    return;
+                }
+                Err(why) => self.switch_failures.push(format!("{launcher} ({why})")),
+            }
+        }
+
+        let hooks = self.runnable_hooks(current_workspace, HookEvent::AfterOpen);
+        match hooks.is_empty() {
+            true => self.finish_switch(frame),
+            false => {
+                self.hook_run = Some(HookRun::spawn(
+                    current_workspace,
+                    hooks,
+                    HookEvent::AfterOpen,
+                    &self.roots,
+                ))
+            }
+        }
+    }
+
+    fn finish_switch(&mut self, frame: &mut eframe::Frame) {
+        // A failed hook or launcher doesn't stop the switch, but stay open so it can be seen.
+        let failed = std::mem::take(&mut self.switch_failures);
+        if !failed.is_empty() {
+            self.status = tr!("status-opened-with-failures", failed = failed.join(", "));
+            return;
+        }
+
+        match self.resident.as_ref() {
+            Some(resident) => {
+                let Some(current_workspace) = self.workspaces.current_workspace() else {
+                    return;
+                };
+                let message =
+                    switch_notification(current_workspace, current_workspace.repo_paths.len());
+                resident.notify(&message);
+                self.status = message;
+                frame.set_visible(false);
+                self.start_tabs_watcher();
+            }
+            None => frame.close(),
+        }
     }
 }
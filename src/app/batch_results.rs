@@ -0,0 +1,138 @@
+use std::{
+    path::Path,
+    sync::mpsc::{self, Receiver},
+};
+
+use egui::{Grid, ScrollArea};
+
+use crate::{
+    git_batch::{spawn_batch, GitOperation, RepoOutcome, RepoResult, DEFAULT_CONCURRENCY},
+    tr,
+};
+
+/// A git operation running across a workspace's repos, and the table of what happened in each.
+pub struct BatchRun {
+    title: String,
+    repo_paths: Vec<String>,
+    receiver: Receiver<(usize, RepoResult)>,
+    results: Vec<Option<RepoResult>>,
+}
+
+impl BatchRun {
+    pub fn spawn(workspace_name: &str, repo_paths: Vec<String>, operation: &GitOperation) -> Self {
+        let receiver = spawn_batch(&repo_paths, operation, DEFAULT_CONCURRENCY);
+        Self {
+            title: tr!(
+                "batch-title",
+                operation = operation.to_string(),
+                name = workspace_name
+            ),
+            results: vec![None; repo_paths.len()],
+            repo_paths,
+            receiver,
+        }
+    }
+
+    /// A results table for work that has already finished.
+    pub fn from_results(title: &str, results: Vec<RepoResult>) -> Self {
+        let (_, receiver) = mpsc::channel();
+        Self {
+            title: title.to_owned(),
+            repo_paths: results
+                .iter()
+                .map(|result| result.repo_path.clone())
+                .collect(),
+            results: results.into_iter().map(Some).collect(),
+            receiver,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.results.iter().any(|result| result.is_none())
+    }
+
+    /// Progress for the status bar.
+    pub fn progress_text(&self) -> String {
+        let done = self.results.iter().flatten().count();
+        let failed = self
+            .results
+            .iter()
+            .flatten()
+            .filter(|result| result.outcome == RepoOutcome::Failed)
+            .count();
+
+        if self.is_running() {
+            tr!(
+                "batch-progress",
+                title = self.title.as_str(),
+                done = done,
+                count = self.results.len()
+            )
+        } else {
+            tr!(
+                "batch-finished",
+                title = self.title.as_str(),
+                failed = failed
+            )
+        }
+    }
+
+    /// Shows the results table. Returns false once the user closes it.
+    pub fn show(&mut self, context: &egui::Context) -> bool {
+        for (index, result) in self.receiver.try_iter() {
+            self.results[index] = Some(result);
+        }
+
+        if self.is_running() {
+            context.request_repaint();
+        }
+
+        let mut open = true;
+        egui::Window::new(&self.title)
+            .open(&mut open)
+            .resizable(true)
+            .show(context, |ui| {
+                ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                    Grid::new("batch_results").striped(true).show(ui, |ui| {
+                        ui.strong(tr!("batch-repo"));
+                        ui.strong(tr!("batch-result"));
+                        ui.strong(tr!("batch-details"));
+                        ui.end_row();
+
+                        for (repo_path, result) in self.repo_paths.iter().zip(self.results.iter()) {
+                            let repo_name = Path::new(repo_path)
+                                .file_name()
+                                .map(|name| name.to_string_lossy().into_owned())
+                                .unwrap_or_else(|| repo_path.clone());
+                            ui.label(repo_name).on_hover_text(repo_path);
+
+                            match result {
+                                Some(result) => {
+                                    ui.label(outcome_text(result.outcome));
+                                    let details = ui.label(&result.summary);
+                                    if !result.output.trim().is_empty() {
+                                        details.on_hover_text(&result.output);
+                                    }
+                                }
+                                None => {
+                                    ui.label(tr!("outcome-running"));
+                                    ui.label("");
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+
+        open
+    }
+}
+
+fn outcome_text(outcome: RepoOutcome) -> String {
+    match outcome {
+        RepoOutcome::Succeeded => tr!("outcome-succeeded"),
+        RepoOutcome::Skipped => tr!("outcome-skipped"),
+        RepoOutcome::Failed => tr!("outcome-failed"),
+    }
+}
@@ -0,0 +1,31 @@
+use crate::tr;
+
+pub enum DeletePromptResult {
+    Open,
+    Cancelled,
+    Delete,
+}
+
+/// Asks before workspaces are removed, unless that's been turned off in Settings.
+pub fn show_delete_prompt(context: &egui::Context, removing: &str) -> DeletePromptResult {
+    let mut result = DeletePromptResult::Open;
+
+    egui::Window::new(tr!("delete-title"))
+        .collapsible(false)
+        .show(context, |ui| {
+            ui.label(tr!("delete-message", name = removing));
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button(tr!("button-remove")).clicked() {
+                    result = DeletePromptResult::Delete;
+                }
+
+                if ui.button(tr!("button-cancel")).clicked() {
+                    result = DeletePromptResult::Cancelled;
+                }
+            });
+        });
+
+    result
+}
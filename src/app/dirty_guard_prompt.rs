@@ -0,0 +1,86 @@
+use egui::{Grid, ScrollArea};
+use uuid::Uuid;
+
+use crate::{dirty_guard::RepoState, tr};
+
+pub enum DirtyGuardResult {
+    Open,
+    Cancelled,
+    Continue,
+    StashAndContinue,
+}
+
+/// Lists the repos about to be closed that still have work in them, before switching workspaces.
+pub struct DirtyGuardPrompt {
+    /// The workspace being switched away from, when the open tabs belong to one.
+    pub outgoing_workspace: Option<Uuid>,
+    outgoing_name: String,
+    pub states: Vec<RepoState>,
+}
+
+impl DirtyGuardPrompt {
+    pub fn new(
+        outgoing_workspace: Option<Uuid>,
+        outgoing_name: &str,
+        states: Vec<RepoState>,
+    ) -> Self {
+        Self {
+            outgoing_workspace,
+            outgoing_name: outgoing_name.to_owned(),
+            states,
+        }
+    }
+
+    pub fn show(&mut self, context: &egui::Context) -> DirtyGuardResult {
+        let mut result = DirtyGuardResult::Open;
+
+        egui::Window::new(tr!("dirty-guard-title"))
+            .collapsible(false)
+            .show(context, |ui| {
+                ui.label(tr!(
+                    "dirty-guard-message",
+                    name = self.outgoing_name.as_str()
+                ));
+
+                ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                    Grid::new("dirty_guard_grid")
+                        .num_columns(2)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for state in &self.states {
+                                ui.label(&state.repo_path);
+                                ui.label(state.summary());
+                                ui.end_row();
+                            }
+                        });
+                });
+
+                if self.outgoing_workspace.is_some() {
+                    ui.label(tr!("dirty-guard-stash-note"));
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("button-continue")).clicked() {
+                        result = DirtyGuardResult::Continue;
+                    }
+
+                    if ui
+                        .add_enabled(
+                            self.outgoing_workspace.is_some(),
+                            egui::Button::new(tr!("button-stash-and-continue")),
+                        )
+                        .clicked()
+                    {
+                        result = DirtyGuardResult::StashAndContinue;
+                    }
+
+                    if ui.button(tr!("button-cancel")).clicked() {
+                        result = DirtyGuardResult::Cancelled;
+                    }
+                });
+            });
+
+        result
+    }
+}
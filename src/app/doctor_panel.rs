@@ -0,0 +1,81 @@
+use egui::{Color32, RichText, ScrollArea};
+
+use crate::{
+    doctor::{has_failures, report, run_checks, CheckResult, CheckStatus},
+    tr,
+};
+
+pub enum DoctorPanelResult {
+    Open,
+    Closed,
+}
+
+/// The same checks as `st-workspaces doctor`, for when switching doesn't work.
+pub struct DoctorPanel {
+    results: Vec<CheckResult>,
+}
+
+impl DoctorPanel {
+    pub fn new() -> Self {
+        Self {
+            results: run_checks(),
+        }
+    }
+
+    pub fn show(&mut self, context: &egui::Context) -> DoctorPanelResult {
+        let mut result = DoctorPanelResult::Open;
+
+        egui::Window::new(tr!("doctor-title"))
+            .collapsible(false)
+            .resizable(true)
+            .show(context, |ui| {
+                if has_failures(&self.results) {
+                    ui.label(tr!("doctor-failed"));
+                } else {
+                    ui.label(tr!("doctor-passed"));
+                }
+
+                ui.separator();
+                ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                    for check in self.results.iter() {
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(status_text(check.status));
+                            ui.strong(&check.name);
+                            ui.label(&check.message);
+                        });
+
+                        if let Some(fix) = check.fix.as_ref() {
+                            ui.small(tr!("doctor-fix", fix = fix.as_str()));
+                        }
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("button-run-again")).clicked() {
+                        self.results = run_checks();
+                    }
+
+                    if ui.button(tr!("button-copy-report")).clicked() {
+                        ui.output_mut(|output| output.copied_text = report(&self.results));
+                    }
+
+                    if ui.button(tr!("button-close")).clicked() {
+                        result = DoctorPanelResult::Closed;
+                    }
+                });
+            });
+
+        result
+    }
+}
+
+fn status_text(status: CheckStatus) -> RichText {
+    let color = match status {
+        CheckStatus::Pass => Color32::from_rgb(0x3c, 0xb3, 0x71),
+        CheckStatus::Warn => Color32::from_rgb(0xe0, 0xa0, 0x20),
+        CheckStatus::Fail => Color32::from_rgb(0xe0, 0x40, 0x40),
+    };
+
+    RichText::new(status.to_string()).color(color).strong()
+}
@@ -0,0 +1,54 @@
+use egui::{CollapsingHeader, Ui};
+
+use crate::{
+    error::{diagnostics, Error},
+    tr,
+};
+
+/// What went wrong behind a status message, shown under it with a way to copy it all.
+pub struct ErrorReport {
+    pub status: String,
+    causes: Vec<String>,
+    details: Vec<(String, String)>,
+    diagnostics: String,
+}
+
+impl ErrorReport {
+    pub fn new(status: &str, error: &anyhow::Error) -> Self {
+        Self {
+            status: status.to_owned(),
+            causes: error.chain().map(|cause| cause.to_string()).collect(),
+            details: error
+                .chain()
+                .find_map(|cause| cause.downcast_ref::<Error>())
+                .map(|error| error.details())
+                .unwrap_or_default(),
+            diagnostics: diagnostics(error),
+        }
+    }
+
+    pub fn show(&self, ui: &mut Ui) {
+        CollapsingHeader::new(tr!("error-report-details"))
+            .id_source("error_report")
+            .show(ui, |ui| {
+                for cause in self.causes.iter() {
+                    ui.label(cause);
+                }
+
+                if !self.details.is_empty() {
+                    ui.separator();
+                    egui::Grid::new("error_details").show(ui, |ui| {
+                        for (label, value) in self.details.iter() {
+                            ui.strong(label);
+                            ui.label(value);
+                            ui.end_row();
+                        }
+                    });
+                }
+
+                if ui.button(tr!("button-copy-diagnostics")).clicked() {
+                    ui.output_mut(|output| output.copied_text = self.diagnostics.clone());
+                }
+            });
+    }
+}
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use uuid::Uuid;
+
+use crate::{paths::sourcetree_settings_path, tr};
+
+pub enum ExportPromptResult {
+    Open,
+    Cancelled,
+    Export,
+}
+
+/// Asks where to write the selected workspaces, which Import can read back on another machine.
+pub struct ExportPrompt {
+    pub uuids: Vec<Uuid>,
+    path: String,
+}
+
+impl ExportPrompt {
+    pub fn new(uuids: Vec<Uuid>) -> Self {
+        let path = sourcetree_settings_path()
+            .map(|path| path.join("st-workspaces-export.json"))
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+
+        Self { uuids, path }
+    }
+
+    pub fn path(&self) -> PathBuf {
+        PathBuf::from(self.path.trim())
+    }
+
+    pub fn show(&mut self, context: &egui::Context) -> ExportPromptResult {
+        let mut result = ExportPromptResult::Open;
+
+        egui::Window::new(tr!("export-title"))
+            .collapsible(false)
+            .show(context, |ui| {
+                ui.label(tr!("export-message", count = self.uuids.len()));
+                ui.add(egui::TextEdit::singleline(&mut self.path).desired_width(360.0));
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("button-export-confirm")).clicked()
+                        && !self.path.trim().is_empty()
+                    {
+                        result = ExportPromptResult::Export;
+                    }
+
+                    if ui.button(tr!("button-cancel")).clicked() {
+                        result = ExportPromptResult::Cancelled;
+                    }
+                });
+            });
+
+        result
+    }
+}
@@ -0,0 +1,58 @@
+use egui::{ComboBox, Ui};
+
+use crate::{
+    ide_export::{ExportFormat, IdeExport},
+    tr,
+};
+
+/// Edits where a workspace's IDE project files are kept. Returns true when something changed that
+/// should be saved, which also regenerates the files.
+pub fn update_exports_editor(ui: &mut Ui, exports: &mut Vec<IdeExport>) -> bool {
+    let mut changed = false;
+    let mut removed_index = None;
+
+    for (index, export) in exports.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ComboBox::from_id_source(("export_format", index))
+                .selected_text(export.format.to_string())
+                .show_ui(ui, |ui| {
+                    for format in [ExportFormat::VsCode, ExportFormat::JetBrains] {
+                        changed |= ui
+                            .selectable_value(&mut export.format, format, format.to_string())
+                            .changed();
+                    }
+                });
+
+            changed |= ui.text_edit_singleline(&mut export.path).lost_focus();
+            if ui.small_button(tr!("button-remove")).clicked() {
+                removed_index = Some(index);
+            }
+        });
+    }
+
+    if let Some(index) = removed_index {
+        exports.remove(index);
+        changed = true;
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(tr!("exports-add"));
+        if ui.small_button("VS Code").clicked() {
+            exports.push(IdeExport {
+                format: ExportFormat::VsCode,
+                path: "${CODE}/workspace.code-workspace".to_owned(),
+            });
+            changed = true;
+        }
+
+        if ui.small_button("JetBrains").clicked() {
+            exports.push(IdeExport {
+                format: ExportFormat::JetBrains,
+                path: "${CODE}/workspace".to_owned(),
+            });
+            changed = true;
+        }
+    });
+
+    changed
+}
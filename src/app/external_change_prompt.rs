@@ -0,0 +1,33 @@
+use crate::tr;
+
+pub enum ExternalChangeResult {
+    Open,
+    Reload,
+    KeepMine,
+}
+
+/// Asks what to do about the workspaces file being changed by something else, such as the command
+/// line, a SourceTree custom action or a text editor.
+pub fn show_external_change_prompt(context: &egui::Context) -> ExternalChangeResult {
+    let mut result = ExternalChangeResult::Open;
+
+    egui::Window::new(tr!("external-change-title"))
+        .collapsible(false)
+        .show(context, |ui| {
+            ui.label(tr!("external-change-message"));
+            ui.label(tr!("external-change-reload-note"));
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button(tr!("button-reload")).clicked() {
+                    result = ExternalChangeResult::Reload;
+                }
+
+                if ui.button(tr!("button-keep-mine")).clicked() {
+                    result = ExternalChangeResult::KeepMine;
+                }
+            });
+        });
+
+    result
+}
@@ -0,0 +1,77 @@
+use egui::{Button, ComboBox, TextEdit, Ui};
+
+use crate::{
+    filters::{save_preset, FilterPreset, WorkspaceFilter},
+    tr,
+};
+
+/// Narrows the list by tag, and keeps filters that are used often as presets.
+pub fn update_filter_bar(
+    ui: &mut Ui,
+    filter: &mut WorkspaceFilter,
+    tags: &[String],
+    presets: &mut Vec<FilterPreset>,
+    preset_name: &mut String,
+) {
+    if !tags.is_empty() {
+        ui.horizontal_wrapped(|ui| {
+            ui.label(tr!("filter-tags"));
+            for tag in tags {
+                if ui
+                    .selectable_label(filter.has_tag(tag), format!("#{tag}"))
+                    .clicked()
+                {
+                    filter.toggle_tag(tag);
+                }
+            }
+        });
+    }
+
+    ui.checkbox(&mut filter.show_archived, tr!("filter-show-archived"));
+
+    ui.horizontal(|ui| {
+        let mut removed_index = None;
+        ComboBox::from_id_source("filter_presets")
+            .selected_text(tr!("filter-presets"))
+            .show_ui(ui, |ui| {
+                if ui.selectable_label(false, tr!("filter-clear")).clicked() {
+                    *filter = WorkspaceFilter::default();
+                }
+
+                for (index, preset) in presets.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .selectable_label(*filter == preset.filter, &preset.name)
+                            .clicked()
+                        {
+                            *filter = preset.filter.clone();
+                        }
+
+                        if ui.small_button(tr!("button-remove")).clicked() {
+                            removed_index = Some(index);
+                        }
+                    });
+                }
+            });
+
+        if let Some(index) = removed_index {
+            presets.remove(index);
+        }
+
+        ui.add(
+            TextEdit::singleline(preset_name)
+                .hint_text(tr!("filter-preset-name"))
+                .desired_width(100.0),
+        );
+        if ui
+            .add_enabled(
+                !preset_name.trim().is_empty(),
+                Button::new(tr!("button-save-preset")),
+            )
+            .clicked()
+        {
+            save_preset(presets, preset_name, filter);
+            preset_name.clear();
+        }
+    });
+}
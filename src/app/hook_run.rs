@@ -0,0 +1,59 @@
+use std::{
+    sync::mpsc::{Receiver, TryRecvError},
+    time::Duration,
+};
+
+use crate::{
+    hooks::{spawn_hooks, HookEvent, HookResult, WorkspaceHook},
+    roots::PathRoots,
+    tr,
+    workspaces::Workspace,
+};
+
+/// Hooks running on a worker thread during a switch, shown with a spinner so the window stays
+/// responsive while they take their time.
+pub struct HookRun {
+    pub event: HookEvent,
+    workspace_name: String,
+    receiver: Receiver<Vec<HookResult>>,
+}
+
+impl HookRun {
+    pub fn spawn(
+        workspace: &Workspace,
+        hooks: Vec<WorkspaceHook>,
+        event: HookEvent,
+        roots: &PathRoots,
+    ) -> Self {
+        Self {
+            event,
+            workspace_name: workspace.name.clone(),
+            receiver: spawn_hooks(hooks, event, roots.clone(), workspace.environment.clone()),
+        }
+    }
+
+    /// Shows that the hooks are running. Returns their results once they've all finished.
+    pub fn show(&self, context: &egui::Context) -> Option<Vec<HookResult>> {
+        match self.receiver.try_recv() {
+            Ok(results) => return Some(results),
+            Err(TryRecvError::Disconnected) => return Some(Vec::new()),
+            Err(TryRecvError::Empty) => {}
+        }
+
+        context.request_repaint_after(Duration::from_millis(100));
+        egui::Window::new(tr!("hook-run-title"))
+            .collapsible(false)
+            .resizable(false)
+            .show(context, |ui| {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(tr!(
+                        "hook-run-message",
+                        event = self.event.to_string(),
+                        name = self.workspace_name.as_str()
+                    ));
+                });
+            });
+        None
+    }
+}
@@ -0,0 +1,102 @@
+use egui::ScrollArea;
+use uuid::Uuid;
+
+use crate::{
+    hooks::{has_hooks_for, hooks_fingerprint, HookEvent, WorkspaceHook},
+    tr,
+    workspaces::Workspace,
+};
+
+pub enum HookTrustPromptResult {
+    Open,
+    Cancelled,
+    /// Switch without running the hooks.
+    Skipped,
+    /// Run the hooks, now and from now on until they change.
+    Trusted,
+}
+
+/// Asks before running hooks that came from the team catalogue or a project's `.st-workspace`,
+/// since they run whatever command someone else wrote.
+pub struct HookTrustPrompt {
+    /// Each workspace's label and source, and the hooks that would run.
+    workspaces: Vec<(String, Vec<WorkspaceHook>)>,
+    fingerprints: Vec<Uuid>,
+}
+
+impl HookTrustPrompt {
+    /// A prompt for the workspaces' hooks for each event, or `None` when there are none.
+    pub fn new(workspaces: &[(&Workspace, HookEvent)]) -> Option<Self> {
+        let workspaces: Vec<_> = workspaces
+            .iter()
+            .filter(|(workspace, event)| has_hooks_for(&workspace.hooks, *event))
+            .collect();
+        if workspaces.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            workspaces: workspaces
+                .iter()
+                .map(|(workspace, event)| {
+                    let hooks = workspace
+                        .hooks
+                        .iter()
+                        .filter(|hook| hook.enabled && hook.event == *event)
+                        .cloned()
+                        .collect();
+                    (
+                        format!("{} ({})", workspace.label(), workspace.source),
+                        hooks,
+                    )
+                })
+                .collect(),
+            fingerprints: workspaces
+                .iter()
+                .map(|(workspace, _)| hooks_fingerprint(&workspace.hooks, &workspace.environment))
+                .collect(),
+        })
+    }
+
+    pub fn fingerprints(&self) -> &[Uuid] {
+        &self.fingerprints
+    }
+
+    pub fn show(&self, context: &egui::Context) -> HookTrustPromptResult {
+        let mut result = HookTrustPromptResult::Open;
+
+        egui::Window::new(tr!("hook-trust-title"))
+            .collapsible(false)
+            .show(context, |ui| {
+                ui.label(tr!("hook-trust-message"));
+
+                ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                    for (heading, hooks) in self.workspaces.iter() {
+                        ui.separator();
+                        ui.strong(heading);
+                        for hook in hooks.iter() {
+                            ui.label(format!("{}: {}", hook.event, hook.name));
+                            ui.code(&hook.command);
+                        }
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("button-trust-hooks")).clicked() {
+                        result = HookTrustPromptResult::Trusted;
+                    }
+
+                    if ui.button(tr!("button-skip-hooks")).clicked() {
+                        result = HookTrustPromptResult::Skipped;
+                    }
+
+                    if ui.button(tr!("button-cancel")).clicked() {
+                        result = HookTrustPromptResult::Cancelled;
+                    }
+                });
+            });
+
+        result
+    }
+}
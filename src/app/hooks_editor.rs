@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+
+use egui::{ComboBox, DragValue, Ui};
+
+use crate::{
+    hooks::{HookEvent, WorkspaceHook},
+    tr,
+};
+
+/// Edits a workspace's hooks in place. Returns true when something changed that should be saved.
+pub fn update_hooks_editor(ui: &mut Ui, hooks: &mut Vec<WorkspaceHook>) -> bool {
+    let mut changed = false;
+    let mut removed_index = None;
+
+    for (index, hook) in hooks.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            changed |= ui.checkbox(&mut hook.enabled, "").changed();
+            changed |= ui.text_edit_singleline(&mut hook.name).lost_focus();
+
+            ComboBox::from_id_source(("hook_event", index))
+                .selected_text(hook.event.to_string())
+                .show_ui(ui, |ui| {
+                    for event in [HookEvent::AfterOpen, HookEvent::BeforeClose] {
+                        changed |= ui
+                            .selectable_value(&mut hook.event, event, event.to_string())
+                            .changed();
+                    }
+                });
+
+            if ui.small_button(tr!("button-remove")).clicked() {
+                removed_index = Some(index);
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(format!("    {}", tr!("hooks-command")));
+            changed |= ui.text_edit_singleline(&mut hook.command).lost_focus();
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(format!("    {}", tr!("hooks-working-dir")));
+            changed |= ui.text_edit_singleline(&mut hook.working_dir).lost_focus();
+            changed |= ui
+                .checkbox(&mut hook.detached, tr!("hooks-detached"))
+                .changed();
+            ui.add_enabled_ui(!hook.detached, |ui| {
+                ui.label(tr!("hooks-timeout"));
+                changed |= ui
+                    .add(
+                        DragValue::new(&mut hook.timeout_secs)
+                            .clamp_range(1..=3600)
+                            .suffix("s"),
+                    )
+                    .changed();
+            });
+        });
+    }
+
+    if let Some(index) = removed_index {
+        hooks.remove(index);
+        changed = true;
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(tr!("hooks-add"));
+        if ui.small_button(HookEvent::AfterOpen.to_string()).clicked() {
+            hooks.push(WorkspaceHook::new(HookEvent::AfterOpen));
+            changed = true;
+        }
+
+        if ui
+            .small_button(HookEvent::BeforeClose.to_string())
+            .clicked()
+        {
+            hooks.push(WorkspaceHook::new(HookEvent::BeforeClose));
+            changed = true;
+        }
+    });
+
+    changed
+}
+
+/// Edits the environment variables given to SourceTree and the hooks. Returns true when something
+/// changed that should be saved.
+pub fn update_environment_editor(
+    ui: &mut Ui,
+    environment: &mut BTreeMap<String, String>,
+    new_name: &mut String,
+) -> bool {
+    let mut changed = false;
+    let mut removed_name = None;
+
+    for (name, value) in environment.iter_mut() {
+        ui.horizontal(|ui| {
+            ui.label(name.as_str());
+            changed |= ui.text_edit_singleline(value).lost_focus();
+            if ui.small_button(tr!("button-remove")).clicked() {
+                removed_name = Some(name.clone());
+            }
+        });
+    }
+
+    if let Some(name) = removed_name {
+        environment.remove(&name);
+        changed = true;
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(tr!("environment-variable"));
+        ui.text_edit_singleline(new_name);
+
+        let name = new_name.trim().to_owned();
+        if ui
+            .add_enabled(
+                !name.is_empty() && !environment.contains_key(&name),
+                egui::Button::new(tr!("button-add")),
+            )
+            .clicked()
+        {
+            environment.insert(name, String::new());
+            new_name.clear();
+            changed = true;
+        }
+    });
+
+    changed
+}
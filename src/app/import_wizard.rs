@@ -0,0 +1,195 @@
+use std::path::PathBuf;
+
+use egui::{Button, Grid, ScrollArea};
+
+use crate::{
+    roots::{expanded_path_exists, parent_prefixes, remap_paths, PathRemap, PathRoots},
+    tr,
+    workspaces::Workspaces,
+};
+
+pub enum ImportWizardResult {
+    Open,
+    Cancelled,
+    Imported(Box<Workspaces>),
+}
+
+/// Imports workspaces from another file, rewriting path prefixes that don't fit this machine.
+pub struct ImportWizard {
+    roots: PathRoots,
+    source_path: String,
+    imported: Option<Workspaces>,
+    remaps: Vec<PathRemap>,
+    /// Each workspace's name and repo paths, with whether they exist here, as last remapped.
+    preview: Vec<(String, Vec<(String, bool)>)>,
+    error: String,
+}
+
+impl ImportWizard {
+    pub fn new(roots: PathRoots) -> Self {
+        Self {
+            roots,
+            source_path: String::new(),
+            imported: None,
+            remaps: Vec::new(),
+            preview: Vec::new(),
+            error: String::new(),
+        }
+    }
+
+    pub fn show(&mut self, context: &egui::Context) -> ImportWizardResult {
+        let mut result = ImportWizardResult::Open;
+
+        egui::Window::new(tr!("import-title"))
+            .collapsible(false)
+            .resizable(true)
+            .show(context, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("import-file"));
+                    ui.text_edit_singleline(&mut self.source_path);
+                    if ui.button(tr!("button-load")).clicked() {
+                        self.load();
+                    }
+                });
+
+                if !self.error.is_empty() {
+                    ui.label(&self.error);
+                }
+
+                if self.imported.is_some() {
+                    ui.separator();
+                    self.update_remaps(ui);
+                    ui.separator();
+                    self.update_preview(ui);
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            self.imported.is_some(),
+                            Button::new(tr!("button-import-confirm")),
+                        )
+                        .clicked()
+                    {
+                        if let Some(workspaces) = self.remapped_workspaces() {
+                            result = ImportWizardResult::Imported(Box::new(workspaces));
+                        }
+                    }
+
+                    if ui.button(tr!("button-cancel")).clicked() {
+                        result = ImportWizardResult::Cancelled;
+                    }
+                });
+            });
+
+        result
+    }
+
+    fn load(&mut self) {
+        match Workspaces::read_from(&PathBuf::from(self.source_path.trim())) {
+            Ok(workspaces) => {
+                let repo_paths = workspaces
+                    .workspaces
+                    .values()
+                    .flat_map(|workspace| workspace.repo_paths.iter());
+                self.remaps = parent_prefixes(repo_paths)
+                    .iter()
+                    .map(|prefix| PathRemap::new(prefix, prefix))
+                    .collect();
+                self.imported = Some(workspaces);
+                self.error.clear();
+                self.update_preview_paths();
+            }
+            Err(why) => {
+                self.imported = None;
+                self.remaps.clear();
+                self.preview.clear();
+                self.error = tr!("import-error", error = why.to_string());
+            }
+        }
+    }
+
+    fn update_remaps(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr!("import-remaps"));
+        let mut removed_index = None;
+        let mut changed = false;
+        Grid::new("import_remaps").striped(true).show(ui, |ui| {
+            for (index, remap) in self.remaps.iter_mut().enumerate() {
+                changed |= ui.text_edit_singleline(&mut remap.from).changed();
+                ui.label("->");
+                changed |= ui.text_edit_singleline(&mut remap.to).changed();
+                if ui.small_button(tr!("button-remove")).clicked() {
+                    removed_index = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+
+        if let Some(index) = removed_index {
+            self.remaps.remove(index);
+            changed = true;
+        }
+
+        if ui.button(tr!("button-add-rewrite")).clicked() {
+            self.remaps.push(PathRemap::default());
+        }
+
+        if changed {
+            self.update_preview_paths();
+        }
+    }
+
+    fn update_preview(&self, ui: &mut egui::Ui) {
+        ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+            for (name, repo_paths) in self.preview.iter() {
+                ui.strong(name);
+                for (repo_path, exists) in repo_paths.iter() {
+                    let marker = match exists {
+                        true => tr!("import-found"),
+                        false => tr!("import-missing"),
+                    };
+                    ui.label(format!("    {repo_path}  ({marker})"));
+                }
+            }
+        });
+    }
+
+    /// Checks the remapped paths on disk, which is too slow to do every frame.
+    fn update_preview_paths(&mut self) {
+        let Some(workspaces) = self.remapped_workspaces() else {
+            self.preview.clear();
+            return;
+        };
+
+        let mut sorted_workspaces: Vec<_> = workspaces.workspaces.values().collect();
+        sorted_workspaces.sort();
+        self.preview = sorted_workspaces
+            .into_iter()
+            .map(|workspace| {
+                let repo_paths = workspace
+                    .repo_paths
+                    .iter()
+                    .map(|path| (path.clone(), expanded_path_exists(&self.roots, path)))
+                    .collect();
+                (workspace.name.clone(), repo_paths)
+            })
+            .collect();
+    }
+
+    fn remapped_workspaces(&self) -> Option<Workspaces> {
+        let mut workspaces = self.imported.clone()?;
+        let remaps: Vec<_> = self
+            .remaps
+            .iter()
+            .filter(|remap| remap.from != remap.to)
+            .cloned()
+            .collect();
+
+        for workspace in workspaces.workspaces.values_mut() {
+            remap_paths(&mut workspace.repo_paths, &remaps);
+        }
+
+        Some(workspaces)
+    }
+}
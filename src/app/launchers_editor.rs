@@ -0,0 +1,64 @@
+use egui::Ui;
+
+use crate::{launchers::Launcher, tr};
+
+/// Edits which launchers "Open Workspace" uses. Returns true when something changed that should be
+/// saved.
+pub fn update_launchers_editor(ui: &mut Ui, launchers: &mut Vec<Launcher>) -> bool {
+    let mut changed = false;
+    if launchers.is_empty() {
+        launchers.push(Launcher::SourceTree);
+    }
+
+    ui.horizontal(|ui| {
+        for launcher in Launcher::built_in() {
+            let mut checked = launchers.contains(&launcher);
+            if ui.checkbox(&mut checked, launcher.to_string()).changed() {
+                match checked {
+                    true => launchers.push(launcher),
+                    false => launchers.retain(|existing| *existing != launcher),
+                }
+                changed = true;
+            }
+        }
+    });
+
+    let mut removed_index = None;
+    for (index, launcher) in launchers.iter_mut().enumerate() {
+        let Launcher::Command { name, template } = launcher else {
+            continue;
+        };
+
+        ui.horizontal(|ui| {
+            changed |= ui.text_edit_singleline(name).lost_focus();
+            changed |= ui.text_edit_singleline(template).lost_focus();
+            if ui.small_button(tr!("button-remove")).clicked() {
+                removed_index = Some(index);
+            }
+        });
+    }
+
+    if let Some(index) = removed_index {
+        launchers.remove(index);
+        changed = true;
+    }
+
+    ui.horizontal(|ui| {
+        if ui.small_button(tr!("button-add-command")).clicked() {
+            launchers.push(Launcher::Command {
+                name: tr!("launchers-new-command"),
+                template: "editor {repo}".to_owned(),
+            });
+            changed = true;
+        }
+
+        ui.small(tr!("launchers-placeholders"));
+    });
+
+    // Just SourceTree is the default, so don't store it.
+    if *launchers == [Launcher::SourceTree] {
+        launchers.clear();
+    }
+
+    changed
+}
@@ -0,0 +1,143 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use egui::{Color32, RichText, ScrollArea};
+use log::Level;
+
+use crate::{
+    logging::{current_log_path, tail, LogLine},
+    tr,
+};
+
+const LEVELS: [Level; 5] = [
+    Level::Error,
+    Level::Warn,
+    Level::Info,
+    Level::Debug,
+    Level::Trace,
+];
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+const TAIL_BYTES: u64 = 256 * 1024;
+
+pub enum LogPanelResult {
+    Open,
+    Closed,
+}
+
+/// Follows the end of the current log, so a failed switch can be explained without finding the
+/// file.
+pub struct LogPanel {
+    path: Option<PathBuf>,
+    lines: Vec<LogLine>,
+    read_error: Option<String>,
+    shown_levels: [bool; LEVELS.len()],
+    last_read: Option<Instant>,
+}
+
+impl LogPanel {
+    pub fn new() -> Self {
+        Self {
+            path: current_log_path(),
+            lines: Vec::new(),
+            read_error: None,
+            shown_levels: [true, true, true, false, false],
+            last_read: None,
+        }
+    }
+
+    pub fn show(&mut self, context: &egui::Context) -> LogPanelResult {
+        if self
+            .last_read
+            .is_none_or(|last_read| last_read.elapsed() >= REFRESH_INTERVAL)
+        {
+            self.read();
+        }
+        context.request_repaint_after(REFRESH_INTERVAL);
+
+        let mut result = LogPanelResult::Open;
+
+        egui::Window::new(tr!("log-title"))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(640.0)
+            .show(context, |ui| {
+                ui.horizontal(|ui| {
+                    for (level, shown) in LEVELS.iter().zip(self.shown_levels.iter_mut()) {
+                        ui.checkbox(shown, level.as_str());
+                    }
+                });
+
+                if let Some(path) = self.path.as_ref() {
+                    ui.small(path.display().to_string());
+                }
+
+                ui.separator();
+                if let Some(read_error) = self.read_error.as_ref() {
+                    ui.label(read_error);
+                }
+
+                ScrollArea::vertical()
+                    .max_height(360.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for line in self.shown_lines() {
+                            ui.label(line_text(line));
+                        }
+                    });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("button-copy")).clicked() {
+                        let text = self
+                            .shown_lines()
+                            .map(|line| line.text.as_str())
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        ui.output_mut(|output| output.copied_text = text);
+                    }
+
+                    if ui.button(tr!("button-close")).clicked() {
+                        result = LogPanelResult::Closed;
+                    }
+                });
+            });
+
+        result
+    }
+
+    fn read(&mut self) {
+        self.last_read = Some(Instant::now());
+        let Some(path) = self.path.as_ref() else {
+            self.read_error = Some(tr!("log-no-directory"));
+            return;
+        };
+
+        match tail(path, TAIL_BYTES) {
+            Ok(lines) => {
+                self.lines = lines;
+                self.read_error = None;
+            }
+            Err(why) => self.read_error = Some(why.to_string()),
+        }
+    }
+
+    fn shown_lines(&self) -> impl Iterator<Item = &LogLine> {
+        self.lines.iter().filter(|line| {
+            LEVELS
+                .iter()
+                .position(|level| *level == line.level)
+                .is_some_and(|index| self.shown_levels[index])
+        })
+    }
+}
+
+fn line_text(line: &LogLine) -> RichText {
+    let text = RichText::new(&line.text).monospace();
+    match line.level {
+        Level::Error => text.color(Color32::from_rgb(0xe0, 0x40, 0x40)),
+        Level::Warn => text.color(Color32::from_rgb(0xe0, 0xa0, 0x20)),
+        _ => text,
+    }
+}
@@ -0,0 +1,98 @@
+use egui::ScrollArea;
+
+use crate::{tr, workspace_merge::WorkspaceConflict, workspaces::Workspace};
+
+pub enum MergeConflictsResult {
+    Open,
+    Closed,
+    UseTheirs(Box<WorkspaceConflict>),
+}
+
+/// Workspaces edited both here and in the file. Ours is already in place, so only taking theirs
+/// needs doing.
+pub struct MergeConflicts {
+    conflicts: Vec<WorkspaceConflict>,
+}
+
+impl MergeConflicts {
+    pub fn new(conflicts: Vec<WorkspaceConflict>) -> Self {
+        Self { conflicts }
+    }
+
+    /// Later conflicts on the same workspace replace earlier ones.
+    pub fn extend(&mut self, conflicts: Vec<WorkspaceConflict>) {
+        for conflict in conflicts {
+            self.conflicts
+                .retain(|existing| existing.uuid != conflict.uuid);
+            self.conflicts.push(conflict);
+        }
+    }
+
+    pub fn show(&mut self, context: &egui::Context) -> MergeConflictsResult {
+        let mut result = MergeConflictsResult::Open;
+        let mut resolved = None;
+
+        egui::Window::new(tr!("merge-conflicts-title"))
+            .collapsible(false)
+            .resizable(true)
+            .show(context, |ui| {
+                ui.label(tr!("merge-conflicts-message"));
+
+                ui.separator();
+                ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    for (index, conflict) in self.conflicts.iter().enumerate() {
+                        ui.strong(conflict.name());
+                        ui.label(tr!(
+                            "merge-conflicts-mine",
+                            workspace = describe(conflict.ours.as_ref())
+                        ));
+                        ui.label(tr!(
+                            "merge-conflicts-theirs",
+                            workspace = describe(conflict.theirs.as_ref())
+                        ));
+                        ui.horizontal(|ui| {
+                            if ui.button(tr!("button-keep-mine")).clicked() {
+                                resolved = Some((index, false));
+                            }
+
+                            if ui.button(tr!("button-use-theirs")).clicked() {
+                                resolved = Some((index, true));
+                            }
+                        });
+                        ui.separator();
+                    }
+                });
+
+                if ui.button(tr!("button-keep-all-mine")).clicked() {
+                    result = MergeConflictsResult::Closed;
+                }
+            });
+
+        if let Some((index, use_theirs)) = resolved {
+            let conflict = self.conflicts.remove(index);
+            if use_theirs {
+                result = MergeConflictsResult::UseTheirs(Box::new(conflict));
+            } else if self.conflicts.is_empty() {
+                result = MergeConflictsResult::Closed;
+            }
+        }
+
+        result
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+fn describe(workspace: Option<&Workspace>) -> String {
+    match workspace {
+        Some(workspace) => tr!(
+            "merge-conflicts-workspace",
+            name = workspace.name.as_str(),
+            count = workspace.repo_paths.len(),
+            repos = workspace.repo_paths.join(", ")
+        ),
+        None => tr!("merge-conflicts-deleted"),
+    }
+}
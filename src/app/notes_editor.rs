@@ -0,0 +1,70 @@
+use egui::{ComboBox, RichText, TextEdit, Ui};
+
+use crate::{
+    tr,
+    workspaces::{Workspace, WorkspaceColor},
+};
+
+use super::theme::workspace_color;
+
+/// Edits a workspace's icon, colour and notes, or just shows them when it can't be edited.
+/// Returns true when something changed that should be saved.
+pub fn update_notes_editor(ui: &mut Ui, workspace: &mut Workspace, is_editable: bool) -> bool {
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        if is_editable {
+            let icon_label = ui.label(tr!("details-icon"));
+            changed |= ui
+                .add(TextEdit::singleline(&mut workspace.icon).desired_width(32.0))
+                .labelled_by(icon_label.id)
+                .lost_focus();
+
+            ui.label(tr!("details-color"));
+            changed |= update_color_picker(ui, &mut workspace.color);
+        } else if let Some(color) = workspace.color {
+            ui.colored_label(workspace_color(color), color.to_string());
+        }
+    });
+
+    if is_editable {
+        changed |= ui
+            .add(
+                TextEdit::multiline(&mut workspace.notes)
+                    .hint_text(tr!("details-notes-hint"))
+                    .desired_rows(3)
+                    .desired_width(f32::INFINITY),
+            )
+            .lost_focus();
+    } else if !workspace.notes.is_empty() {
+        ui.label(&workspace.notes);
+    }
+
+    for link in workspace.note_links() {
+        ui.hyperlink(link);
+    }
+
+    changed
+}
+
+fn update_color_picker(ui: &mut Ui, color: &mut Option<WorkspaceColor>) -> bool {
+    let mut changed = false;
+    let selected_text = match color {
+        Some(color) => RichText::new(color.to_string()).color(workspace_color(*color)),
+        None => RichText::new(tr!("details-no-color")),
+    };
+
+    ComboBox::from_id_source("workspace_color")
+        .selected_text(selected_text)
+        .show_ui(ui, |ui| {
+            changed |= ui
+                .selectable_value(color, None, tr!("details-no-color"))
+                .changed();
+            for option in WorkspaceColor::all() {
+                let text = RichText::new(option.to_string()).color(workspace_color(option));
+                changed |= ui.selectable_value(color, Some(option), text).changed();
+            }
+        });
+
+    changed
+}
@@ -0,0 +1,87 @@
+use egui::{Key, ScrollArea};
+use uuid::Uuid;
+
+use crate::{resident::tray_entries, tr, workspaces::Workspaces};
+
+pub enum QuickSwitcherResult {
+    Open,
+    Cancelled,
+    Switch(Uuid),
+}
+
+/// A filter box over every workspace, for switching from the keyboard.
+pub struct QuickSwitcher {
+    filter: String,
+    selected: usize,
+}
+
+impl QuickSwitcher {
+    pub fn new() -> Self {
+        Self {
+            filter: String::new(),
+            selected: 0,
+        }
+    }
+
+    pub fn show(
+        &mut self,
+        context: &egui::Context,
+        workspaces: &Workspaces,
+    ) -> QuickSwitcherResult {
+        let matches: Vec<_> = tray_entries(workspaces)
+            .into_iter()
+            .filter_map(|(uuid, _)| workspaces.workspaces.get(&uuid))
+            .filter(|workspace| workspace.matches_filter(&self.filter))
+            .map(|workspace| (workspace.uuid, workspace.label()))
+            .collect();
+
+        let (up, down, enter, escape) = context.input(|input| {
+            (
+                input.key_pressed(Key::ArrowUp),
+                input.key_pressed(Key::ArrowDown),
+                input.key_pressed(Key::Enter),
+                input.key_pressed(Key::Escape),
+            )
+        });
+
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+
+        if down {
+            self.selected += 1;
+        }
+
+        self.selected = self.selected.min(matches.len().saturating_sub(1));
+
+        if escape {
+            return QuickSwitcherResult::Cancelled;
+        }
+
+        if enter {
+            if let Some((uuid, _)) = matches.get(self.selected) {
+                return QuickSwitcherResult::Switch(*uuid);
+            }
+        }
+
+        let mut result = QuickSwitcherResult::Open;
+        egui::Window::new(tr!("switcher-title"))
+            .collapsible(false)
+            .show(context, |ui| {
+                let filter_box = ui.text_edit_singleline(&mut self.filter);
+                if !filter_box.has_focus() {
+                    filter_box.request_focus();
+                }
+
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for (index, (uuid, name)) in matches.iter().enumerate() {
+                        if ui.selectable_label(index == self.selected, name).clicked() {
+                            result = QuickSwitcherResult::Switch(*uuid);
+                        }
+                    }
+                });
+            });
+
+        result
+    }
+}
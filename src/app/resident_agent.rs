@@ -0,0 +1,215 @@
+use log::warn;
+use uuid::Uuid;
+
+use crate::{resident::ResidentConfig, workspaces::Workspaces};
+
+// Only the tray and hotkey make these, and the tray is only on Windows.
+#[cfg_attr(not(all(feature = "resident", windows)), allow(dead_code))]
+pub enum ResidentEvent {
+    ShowSwitcher,
+    Open(Uuid),
+    Quit,
+}
+
+/// The tray icon, global hotkey and notifications of resident mode. Builds without the `resident`
+/// feature do nothing, so the app behaves the same apart from the extras.
+pub struct ResidentAgent {
+    notify_on_switch: bool,
+    #[cfg(feature = "resident")]
+    hotkey: Option<platform::Hotkey>,
+    #[cfg(all(feature = "resident", windows))]
+    tray: Option<platform::Tray>,
+}
+
+impl ResidentAgent {
+    pub fn start(config: &ResidentConfig, workspaces: &Workspaces) -> Self {
+        #[cfg(not(feature = "resident"))]
+        warn!("Built without resident support, so there is no tray icon or hotkey.");
+
+        #[cfg(not(all(feature = "resident", windows)))]
+        let _ = workspaces;
+
+        Self {
+            notify_on_switch: config.notify_on_switch,
+            #[cfg(feature = "resident")]
+            hotkey: platform::Hotkey::register(&config.hotkey)
+                .map_err(|why| warn!("Couldn't register hotkey {}. '{}'", config.hotkey, why))
+                .ok(),
+            #[cfg(all(feature = "resident", windows))]
+            tray: platform::Tray::new(workspaces)
+                .map_err(|why| warn!("Couldn't create tray icon. '{}'", why))
+                .ok(),
+        }
+    }
+
+    /// What the user asked for since the last call. Keeps the tray menu in step with the workspaces.
+    #[cfg(feature = "resident")]
+    pub fn poll(&mut self, workspaces: &Workspaces) -> Vec<ResidentEvent> {
+        let mut events = Vec::new();
+        if self
+            .hotkey
+            .as_ref()
+            .is_some_and(|hotkey| hotkey.was_pressed())
+        {
+            events.push(ResidentEvent::ShowSwitcher);
+        }
+
+        #[cfg(windows)]
+        if let Some(tray) = self.tray.as_mut() {
+            tray.refresh(workspaces);
+            events.extend(tray.events());
+        }
+
+        #[cfg(not(windows))]
+        let _ = workspaces;
+        events
+    }
+
+    #[cfg(not(feature = "resident"))]
+    pub fn poll(&mut self, _workspaces: &Workspaces) -> Vec<ResidentEvent> {
+        Vec::new()
+    }
+
+    pub fn notify(&self, message: &str) {
+        if !self.notify_on_switch {
+            return;
+        }
+
+        #[cfg(feature = "resident")]
+        if let Err(why) = notify_rust::Notification::new()
+            .summary("SourceTree Workspaces")
+            .body(message)
+            .show()
+        {
+            warn!("Couldn't show notification. '{}'", why);
+        }
+
+        #[cfg(not(feature = "resident"))]
+        let _ = message;
+    }
+}
+
+#[cfg(feature = "resident")]
+mod platform {
+    use global_hotkey::{hotkey::HotKey, GlobalHotKeyEvent, GlobalHotKeyManager};
+
+    pub struct Hotkey {
+        // Unregisters the hotkey when dropped.
+        _manager: GlobalHotKeyManager,
+        id: u32,
+    }
+
+    impl Hotkey {
+        pub fn register(hotkey: &str) -> anyhow::Result<Self> {
+            let hotkey: HotKey = hotkey.parse()?;
+            let manager = GlobalHotKeyManager::new()?;
+            manager.register(hotkey)?;
+
+            Ok(Self {
+                _manager: manager,
+                id: hotkey.id(),
+            })
+        }
+
+        pub fn was_pressed(&self) -> bool {
+            GlobalHotKeyEvent::receiver()
+                .try_iter()
+                .any(|event| event.id == self.id)
+        }
+    }
+
+    #[cfg(windows)]
+    pub use self::tray::Tray;
+
+    #[cfg(windows)]
+    mod tray {
+        use tray_icon::{
+            icon::Icon,
+            menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem},
+            ClickEvent, TrayEvent, TrayIcon, TrayIconBuilder,
+        };
+        use uuid::Uuid;
+
+        use crate::{resident::tray_entries, tr, workspaces::Workspaces};
+
+        use super::super::ResidentEvent;
+
+        const ICON_SIZE: u32 = 16;
+
+        pub struct Tray {
+            icon: TrayIcon,
+            entries: Vec<(Uuid, String)>,
+            workspace_items: Vec<(u32, Uuid)>,
+            switcher_id: u32,
+            quit_id: u32,
+        }
+
+        impl Tray {
+            pub fn new(workspaces: &Workspaces) -> anyhow::Result<Self> {
+                let rgba = [0x20, 0x6f, 0xd8, 0xff].repeat((ICON_SIZE * ICON_SIZE) as usize);
+                let icon = TrayIconBuilder::new()
+                    .with_icon(Icon::from_rgba(rgba, ICON_SIZE, ICON_SIZE)?)
+                    .with_tooltip("SourceTree Workspaces")
+                    .build()?;
+
+                let mut tray = Self {
+                    icon,
+                    entries: Vec::new(),
+                    workspace_items: Vec::new(),
+                    switcher_id: 0,
+                    quit_id: 0,
+                };
+                tray.refresh(workspaces);
+                Ok(tray)
+            }
+
+            /// Rebuilds the menu when workspaces have been added, removed or renamed.
+            pub fn refresh(&mut self, workspaces: &Workspaces) {
+                let entries = tray_entries(workspaces);
+                if entries == self.entries {
+                    return;
+                }
+
+                let menu = Menu::new();
+                self.workspace_items.clear();
+                for (uuid, name) in entries.iter() {
+                    let item = MenuItem::new(name, true, None);
+                    menu.append(&item);
+                    self.workspace_items.push((item.id(), *uuid));
+                }
+
+                let switcher = MenuItem::new(tr!("tray-switch"), true, None);
+                let quit = MenuItem::new(tr!("tray-quit"), true, None);
+                menu.append_items(&[&PredefinedMenuItem::separator(), &switcher, &quit]);
+                self.switcher_id = switcher.id();
+                self.quit_id = quit.id();
+
+                self.icon.set_menu(Some(Box::new(menu)));
+                self.entries = entries;
+            }
+
+            pub fn events(&self) -> Vec<ResidentEvent> {
+                let mut events = Vec::new();
+                for event in TrayEvent::receiver().try_iter() {
+                    if event.event == ClickEvent::Double {
+                        events.push(ResidentEvent::ShowSwitcher);
+                    }
+                }
+
+                for event in MenuEvent::receiver().try_iter() {
+                    if event.id == self.switcher_id {
+                        events.push(ResidentEvent::ShowSwitcher);
+                    } else if event.id == self.quit_id {
+                        events.push(ResidentEvent::Quit);
+                    } else if let Some((_, uuid)) =
+                        self.workspace_items.iter().find(|(id, _)| *id == event.id)
+                    {
+                        events.push(ResidentEvent::Open(*uuid));
+                    }
+                }
+
+                events
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use egui::ComboBox;
+
+use crate::{tr, workspaces::Workspace};
+
+pub enum RestorePromptResult {
+    Open,
+    Cancelled,
+    OpenWithoutRestoring,
+    RestoreAndOpen(usize),
+}
+
+/// Asks whether to check out a snapshot's branches before opening a workspace.
+pub struct RestorePrompt {
+    selected_snapshot: usize,
+}
+
+impl RestorePrompt {
+    /// Starts on the newest snapshot.
+    pub fn new(workspace: &Workspace) -> Self {
+        Self {
+            selected_snapshot: workspace.snapshots.len().saturating_sub(1),
+        }
+    }
+
+    pub fn show(&mut self, context: &egui::Context, workspace: &Workspace) -> RestorePromptResult {
+        let mut result = RestorePromptResult::Open;
+
+        egui::Window::new(tr!("restore-prompt-title"))
+            .collapsible(false)
+            .show(context, |ui| {
+                ui.label(tr!(
+                    "restore-prompt-message",
+                    name = workspace.name.as_str()
+                ));
+
+                let selected_text = workspace
+                    .snapshots
+                    .get(self.selected_snapshot)
+                    .map(|snapshot| snapshot.description())
+                    .unwrap_or_default();
+                ComboBox::from_id_source("restore_snapshot")
+                    .selected_text(selected_text)
+                    .width(360.0)
+                    .show_ui(ui, |ui| {
+                        for (index, snapshot) in workspace.snapshots.iter().enumerate().rev() {
+                            ui.selectable_value(
+                                &mut self.selected_snapshot,
+                                index,
+                                snapshot.description(),
+                            );
+                        }
+                    });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("button-restore-and-open")).clicked() {
+                        result = RestorePromptResult::RestoreAndOpen(self.selected_snapshot);
+                    }
+
+                    if ui.button(tr!("button-open-without-restoring")).clicked() {
+                        result = RestorePromptResult::OpenWithoutRestoring;
+                    }
+
+                    if ui.button(tr!("button-cancel")).clicked() {
+                        result = RestorePromptResult::Cancelled;
+                    }
+                });
+            });
+
+        result
+    }
+}
@@ -0,0 +1,85 @@
+use egui::Grid;
+
+use crate::{local_config::LocalConfig, roots::PathRoots, tr};
+
+pub enum RootsEditorResult {
+    Open,
+    Closed,
+    Saved,
+}
+
+/// Edits this machine's path roots, which expand the `${NAME}` part of repo paths.
+pub struct RootsEditor {
+    config: LocalConfig,
+    new_name: String,
+    new_path: String,
+}
+
+impl RootsEditor {
+    pub fn new() -> Self {
+        Self {
+            config: LocalConfig::read().unwrap_or_default(),
+            new_name: String::new(),
+            new_path: String::new(),
+        }
+    }
+
+    pub fn show(&mut self, context: &egui::Context) -> RootsEditorResult {
+        let mut result = RootsEditorResult::Open;
+
+        egui::Window::new(tr!("roots-title"))
+            .collapsible(false)
+            .show(context, |ui| {
+                let mut removed_name = None;
+                Grid::new("path_roots").striped(true).show(ui, |ui| {
+                    for (name, path) in self.config.roots.iter() {
+                        ui.label(format!("${{{name}}}"));
+                        ui.label(path);
+                        if ui.small_button(tr!("button-remove")).clicked() {
+                            removed_name = Some(name.clone());
+                        }
+                        ui.end_row();
+                    }
+
+                    ui.text_edit_singleline(&mut self.new_name);
+                    ui.text_edit_singleline(&mut self.new_path);
+                    if ui.small_button(tr!("button-add")).clicked()
+                        && !self.new_name.trim().is_empty()
+                    {
+                        self.config
+                            .roots
+                            .insert(self.new_name.trim(), self.new_path.trim());
+                        self.new_name.clear();
+                        self.new_path.clear();
+                    }
+                    ui.end_row();
+                });
+
+                if let Some(name) = removed_name {
+                    self.config.roots.remove(&name);
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("button-save")).clicked() {
+                        result = RootsEditorResult::Saved;
+                    }
+
+                    if ui.button(tr!("button-close")).clicked() {
+                        result = RootsEditorResult::Closed;
+                    }
+                });
+            });
+
+        result
+    }
+
+    /// Writes the roots to the local config, returning them. Other windows may have changed the
+    /// rest of the config since this one opened, so only the roots are replaced.
+    pub fn save(&self) -> anyhow::Result<PathRoots> {
+        let mut config = LocalConfig::read_or_default()?;
+        config.roots = self.config.roots.clone();
+        config.write()?;
+        Ok(config.roots)
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+use egui::{DragValue, Ui};
+use uuid::Uuid;
+
+use crate::{roots::PathRoots, rules::WorkspaceRule, tr, workspaces::Workspace};
+
+/// Edits a dynamic workspace's rules in place. Returns true when something changed that should be
+/// saved.
+pub fn update_rules_editor(ui: &mut Ui, rules: &mut Vec<WorkspaceRule>) -> bool {
+    let mut changed = false;
+    let mut removed_index = None;
+
+    for (index, rule) in rules.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            match rule {
+                WorkspaceRule::ReposUnder { root, max_depth } => {
+                    ui.label(tr!("rules-repos-under"));
+                    changed |= ui.text_edit_singleline(root).lost_focus();
+                    ui.label(tr!("rules-depth"));
+                    changed |= ui
+                        .add(DragValue::new(max_depth).clamp_range(0..=32))
+                        .changed();
+                }
+                WorkspaceRule::BookmarkFolder { folder } => {
+                    ui.label(tr!("rules-bookmark-folder"));
+                    changed |= ui.text_edit_singleline(folder).lost_focus();
+                }
+                WorkspaceRule::RemoteMatches { pattern } => {
+                    ui.label(tr!("rules-remote-matches"));
+                    changed |= ui.text_edit_singleline(pattern).lost_focus();
+                }
+            }
+
+            if ui.small_button(tr!("button-remove")).clicked() {
+                removed_index = Some(index);
+            }
+        });
+    }
+
+    if let Some(index) = removed_index {
+        rules.remove(index);
+        changed = true;
+    }
+
+    ui.horizontal(|ui| {
+        ui.label(tr!("rules-add"));
+        if ui.small_button(tr!("button-rule-folder")).clicked() {
+            rules.push(WorkspaceRule::ReposUnder {
+                root: String::new(),
+                max_depth: 2,
+            });
+            changed = true;
+        }
+
+        if ui
+            .small_button(tr!("button-rule-bookmark-folder"))
+            .clicked()
+        {
+            rules.push(WorkspaceRule::BookmarkFolder {
+                folder: String::new(),
+            });
+            changed = true;
+        }
+
+        if ui.small_button(tr!("button-rule-remote")).clicked() {
+            rules.push(WorkspaceRule::RemoteMatches {
+                pattern: "github.com/org/*".to_owned(),
+            });
+            changed = true;
+        }
+    });
+
+    changed
+}
+
+/// The repos a dynamic workspace resolves to, worked out on a background thread since rules may
+/// scan directories or run git.
+pub struct RulePreview {
+    pub workspace_id: Uuid,
+    receiver: Receiver<Vec<String>>,
+    repo_paths: Option<Vec<String>>,
+}
+
+impl RulePreview {
+    pub fn spawn(workspace: &Workspace, roots: &PathRoots) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let workspace_id = workspace.uuid;
+        let workspace = workspace.clone();
+        let roots = roots.clone();
+        // Without rules there's nothing to walk, so there's no need for a thread.
+        if workspace.rules.is_empty() {
+            let _ = sender.send(workspace.resolved_repo_paths(&roots));
+        } else {
+            thread::spawn(move || {
+                let _ = sender.send(workspace.resolved_repo_paths(&roots));
+            });
+        }
+
+        Self {
+            workspace_id,
+            receiver,
+            repo_paths: None,
+        }
+    }
+
+    /// The resolved repos, once they're ready.
+    pub fn repo_paths(&mut self) -> Option<&Vec<String>> {
+        if self.repo_paths.is_none() {
+            self.repo_paths = self.receiver.try_recv().ok();
+        }
+
+        self.repo_paths.as_ref()
+    }
+}
@@ -0,0 +1,203 @@
+use std::path::PathBuf;
+
+use egui::{Button, DragValue, ScrollArea};
+use uuid::Uuid;
+
+use crate::{
+    local_config::LocalConfig,
+    scanner::{spawn_scan, DiscoveredRepo, ScanHandle, ScanOptions, ScanProgress},
+    tr,
+    workspaces::Workspace,
+};
+
+pub enum ScanWizardResult {
+    Open,
+    Cancelled,
+    Created(Box<Workspace>),
+}
+
+/// Finds repos below a directory and makes a workspace from the ones picked.
+pub struct ScanWizard {
+    root: String,
+    max_depth: usize,
+    ignore_patterns: String,
+    workspace_name: String,
+    scan: Option<ScanHandle>,
+    visited: usize,
+    current_directory: PathBuf,
+    found: Vec<(DiscoveredRepo, bool)>,
+}
+
+impl ScanWizard {
+    pub fn new() -> Self {
+        let options = ScanOptions::default();
+        Self {
+            root: String::new(),
+            max_depth: options.max_depth,
+            ignore_patterns: options.ignore_patterns.join(", "),
+            workspace_name: tr!("scan-workspace-name"),
+            scan: None,
+            visited: 0,
+            current_directory: PathBuf::new(),
+            found: Vec::new(),
+        }
+    }
+
+    pub fn is_scanning(&self) -> bool {
+        self.scan.is_some()
+    }
+
+    /// Progress for the status bar.
+    pub fn progress_text(&self) -> String {
+        if self.is_scanning() {
+            tr!(
+                "scan-progress",
+                visited = self.visited,
+                count = self.found.len(),
+                path = self.current_directory.display().to_string()
+            )
+        } else {
+            tr!(
+                "scan-finished",
+                visited = self.visited,
+                count = self.found.len()
+            )
+        }
+    }
+
+    pub fn show(&mut self, context: &egui::Context) -> ScanWizardResult {
+        self.receive_progress();
+        if self.is_scanning() {
+            context.request_repaint();
+        }
+
+        let mut result = ScanWizardResult::Open;
+
+        egui::Window::new(tr!("scan-title"))
+            .collapsible(false)
+            .resizable(true)
+            .show(context, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("scan-root"));
+                    ui.text_edit_singleline(&mut self.root);
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!("scan-depth"));
+                    ui.add(DragValue::new(&mut self.max_depth).clamp_range(1..=32));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!("scan-ignore"));
+                    ui.text_edit_singleline(&mut self.ignore_patterns);
+                });
+
+                ui.horizontal(|ui| {
+                    if self.is_scanning() {
+                        if ui.button(tr!("button-stop")).clicked() {
+                            self.stop_scan();
+                        }
+                    } else if ui.button(tr!("button-scan")).clicked() {
+                        self.start_scan();
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.small_button(tr!("button-select-all")).clicked() {
+                        self.found
+                            .iter_mut()
+                            .for_each(|(_, checked)| *checked = true);
+                    }
+
+                    if ui.small_button(tr!("button-select-none")).clicked() {
+                        self.found
+                            .iter_mut()
+                            .for_each(|(_, checked)| *checked = false);
+                    }
+                });
+
+                ScrollArea::vertical().max_height(280.0).show(ui, |ui| {
+                    for (repo, checked) in self.found.iter_mut() {
+                        ui.checkbox(checked, format!("{}  ({})", repo.path.display(), repo.kind));
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(tr!("details-name"));
+                    ui.text_edit_singleline(&mut self.workspace_name);
+                });
+
+                ui.horizontal(|ui| {
+                    let any_checked = self.found.iter().any(|(_, checked)| *checked);
+                    if ui
+                        .add_enabled(any_checked, Button::new(tr!("button-create-scanned")))
+                        .clicked()
+                    {
+                        result = ScanWizardResult::Created(Box::new(self.create_workspace()));
+                    }
+
+                    if ui.button(tr!("button-cancel")).clicked() {
+                        self.stop_scan();
+                        result = ScanWizardResult::Cancelled;
+                    }
+                });
+            });
+
+        result
+    }
+
+    fn start_scan(&mut self) {
+        self.found.clear();
+        self.visited = 0;
+        self.scan = Some(spawn_scan(ScanOptions {
+            root: PathBuf::from(self.root.trim()),
+            max_depth: self.max_depth,
+            ignore_patterns: self
+                .ignore_patterns
+                .split(',')
+                .map(|pattern| pattern.trim().to_owned())
+                .filter(|pattern| !pattern.is_empty())
+                .collect(),
+        }));
+    }
+
+    fn stop_scan(&mut self) {
+        if let Some(scan) = self.scan.take() {
+            scan.cancel();
+        }
+    }
+
+    fn receive_progress(&mut self) {
+        let Some(scan) = self.scan.as_ref() else {
+            return;
+        };
+
+        let mut finished = false;
+        for progress in scan.progress.try_iter() {
+            match progress {
+                ScanProgress::Visited(visited, directory) => {
+                    self.visited = visited;
+                    self.current_directory = directory;
+                }
+                ScanProgress::Found(repo) => self.found.push((repo, true)),
+                ScanProgress::Finished => finished = true,
+            }
+        }
+
+        if finished {
+            self.scan = None;
+        }
+    }
+
+    fn create_workspace(&self) -> Workspace {
+        let roots = LocalConfig::read().unwrap_or_default().roots;
+        let mut workspace = Workspace::new(&self.workspace_name, Uuid::new_v4());
+        workspace.repo_paths = self
+            .found
+            .iter()
+            .filter(|(_, checked)| *checked)
+            .map(|(repo, _)| roots.contract(&repo.path.display().to_string()))
+            .collect();
+        workspace
+    }
+}
@@ -0,0 +1,194 @@
+use std::path::PathBuf;
+
+use egui::{ComboBox, DragValue, Grid, Slider};
+
+use crate::{
+    i18n::languages,
+    launchers::Launcher,
+    preferences::{Preferences, Theme, MAX_FONT_SCALE, MIN_FONT_SCALE, MIN_WINDOW_SIZE},
+    tr,
+};
+
+pub enum SettingsEditorResult {
+    Open,
+    Closed,
+    Saved,
+}
+
+/// Edits the app's preferences. Window changes take effect the next time it opens.
+pub struct SettingsEditor {
+    pub preferences: Preferences,
+    exec_path: String,
+    centred: bool,
+}
+
+impl SettingsEditor {
+    pub fn new(preferences: &Preferences) -> Self {
+        Self {
+            preferences: preferences.clone(),
+            exec_path: exec_path_text(preferences),
+            centred: preferences.window.position.is_none(),
+        }
+    }
+
+    pub fn show(&mut self, context: &egui::Context) -> SettingsEditorResult {
+        let mut result = SettingsEditorResult::Open;
+
+        egui::Window::new(tr!("settings-title"))
+            .collapsible(false)
+            .show(context, |ui| {
+                Grid::new("settings").num_columns(2).show(ui, |ui| {
+                    ui.label(tr!("settings-language"));
+                    let languages = languages();
+                    let selected_language = languages
+                        .iter()
+                        .find(|(language, _)| {
+                            Some(*language) == self.preferences.language.as_deref()
+                        })
+                        .map_or_else(|| tr!("settings-language-system"), |(_, name)| name.clone());
+                    ComboBox::from_id_source("settings_language")
+                        .selected_text(selected_language)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.preferences.language,
+                                None,
+                                tr!("settings-language-system"),
+                            );
+                            for (language, name) in languages {
+                                ui.selectable_value(
+                                    &mut self.preferences.language,
+                                    Some(language.to_owned()),
+                                    name,
+                                );
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr!("settings-theme"));
+                    ComboBox::from_id_source("settings_theme")
+                        .selected_text(self.preferences.theme.to_string())
+                        .show_ui(ui, |ui| {
+                            for theme in Theme::all() {
+                                ui.selectable_value(
+                                    &mut self.preferences.theme,
+                                    theme,
+                                    theme.to_string(),
+                                );
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(tr!("settings-font-scale"));
+                    ui.add(Slider::new(
+                        &mut self.preferences.font_scale,
+                        MIN_FONT_SCALE..=MAX_FONT_SCALE,
+                    ));
+                    ui.end_row();
+
+                    ui.label(tr!("settings-window-size"));
+                    ui.horizontal(|ui| {
+                        let window = &mut self.preferences.window;
+                        ui.add(
+                            DragValue::new(&mut window.width)
+                                .clamp_range(MIN_WINDOW_SIZE[0]..=f32::MAX),
+                        );
+                        ui.label("x");
+                        ui.add(
+                            DragValue::new(&mut window.height)
+                                .clamp_range(MIN_WINDOW_SIZE[1]..=f32::MAX),
+                        );
+                    });
+                    ui.end_row();
+
+                    ui.label(tr!("settings-window-position"));
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut self.centred, tr!("settings-centred"));
+                        if !self.centred {
+                            let position = self.preferences.window.position.get_or_insert([0.0; 2]);
+                            ui.add(DragValue::new(&mut position[0]));
+                            ui.add(DragValue::new(&mut position[1]));
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(tr!("settings-close-timeout"));
+                    ui.add(
+                        DragValue::new(&mut self.preferences.close_timeout_secs)
+                            .clamp_range(0..=60)
+                            .suffix(" s"),
+                    );
+                    ui.end_row();
+
+                    ui.label(tr!("settings-confirm-delete"));
+                    ui.checkbox(
+                        &mut self.preferences.confirm_delete,
+                        tr!("settings-confirm-delete-description"),
+                    );
+                    ui.end_row();
+
+                    ui.label(tr!("settings-sourcetree"));
+                    let default_exec_path = Preferences::default()
+                        .sourcetree_exec_path()
+                        .map(|path| path.display().to_string())
+                        .unwrap_or_default();
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.exec_path)
+                            .hint_text(default_exec_path)
+                            .desired_width(320.0),
+                    );
+                    ui.end_row();
+
+                    ui.label(tr!("settings-default-launcher"));
+                    ComboBox::from_id_source("settings_default_launcher")
+                        .selected_text(self.preferences.default_launcher.to_string())
+                        .show_ui(ui, |ui| {
+                            for launcher in Launcher::built_in() {
+                                let text = launcher.to_string();
+                                ui.selectable_value(
+                                    &mut self.preferences.default_launcher,
+                                    launcher,
+                                    text,
+                                );
+                            }
+                        });
+                    ui.end_row();
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("button-save")).clicked() {
+                        result = SettingsEditorResult::Saved;
+                    }
+
+                    if ui.button(tr!("button-reset-to-defaults")).clicked() {
+                        *self = SettingsEditor::new(&Preferences::default());
+                    }
+
+                    if ui.button(tr!("button-close")).clicked() {
+                        result = SettingsEditorResult::Closed;
+                    }
+                });
+            });
+
+        if self.centred {
+            self.preferences.window.position = None;
+        }
+        let exec_path = self.exec_path.trim();
+        self.preferences.exec_path = (!exec_path.is_empty()).then(|| PathBuf::from(exec_path));
+
+        result
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        self.preferences.validate()?;
+        self.preferences.write()
+    }
+}
+
+fn exec_path_text(preferences: &Preferences) -> String {
+    preferences
+        .exec_path
+        .as_ref()
+        .map(|path| path.display().to_string())
+        .unwrap_or_default()
+}
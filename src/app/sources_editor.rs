@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+
+use egui::Grid;
+
+use crate::{catalogue::Catalogue, local_config::LocalConfig, tr};
+
+pub enum SourcesEditorResult {
+    Open,
+    Closed,
+    Saved,
+}
+
+/// Chooses where workspaces are loaded from besides the personal file: the team's shared
+/// catalogue, and repos that commit a `.st-workspace`.
+pub struct SourcesEditor {
+    config: LocalConfig,
+    catalogue_path: String,
+    catalogue_read_only: bool,
+    new_project_repo: String,
+}
+
+impl SourcesEditor {
+    pub fn new() -> Self {
+        let config = LocalConfig::read().unwrap_or_default();
+        let catalogue_path = config
+            .catalogue
+            .as_ref()
+            .map(|catalogue| catalogue.path.display().to_string())
+            .unwrap_or_default();
+        let catalogue_read_only = config
+            .catalogue
+            .as_ref()
+            .is_some_and(|catalogue| catalogue.read_only);
+
+        Self {
+            config,
+            catalogue_path,
+            catalogue_read_only,
+            new_project_repo: String::new(),
+        }
+    }
+
+    pub fn show(&mut self, context: &egui::Context) -> SourcesEditorResult {
+        let mut result = SourcesEditorResult::Open;
+
+        egui::Window::new(tr!("sources-title"))
+            .collapsible(false)
+            .show(context, |ui| {
+                ui.strong(tr!("sources-catalogue"));
+                ui.label(tr!("sources-catalogue-note"));
+                ui.horizontal(|ui| {
+                    ui.label(tr!("sources-directory"));
+                    ui.text_edit_singleline(&mut self.catalogue_path);
+                });
+                ui.checkbox(&mut self.catalogue_read_only, tr!("sources-read-only"));
+
+                ui.separator();
+                ui.strong(tr!("sources-project-repos"));
+                ui.label(tr!("sources-project-repos-note"));
+                let mut removed_index = None;
+                Grid::new("project_repos").striped(true).show(ui, |ui| {
+                    for (index, repo_path) in self.config.project_repos.iter().enumerate() {
+                        ui.label(repo_path.display().to_string());
+                        if ui.small_button(tr!("button-remove")).clicked() {
+                            removed_index = Some(index);
+                        }
+                        ui.end_row();
+                    }
+
+                    ui.text_edit_singleline(&mut self.new_project_repo);
+                    if ui.small_button(tr!("button-add")).clicked()
+                        && !self.new_project_repo.trim().is_empty()
+                    {
+                        self.config
+                            .project_repos
+                            .push(PathBuf::from(self.new_project_repo.trim()));
+                        self.new_project_repo.clear();
+                    }
+                    ui.end_row();
+                });
+
+                if let Some(index) = removed_index {
+                    self.config.project_repos.remove(index);
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr!("button-save")).clicked() {
+                        result = SourcesEditorResult::Saved;
+                    }
+
+                    if ui.button(tr!("button-close")).clicked() {
+                        result = SourcesEditorResult::Closed;
+                    }
+                });
+            });
+
+        result
+    }
+
+    /// Writes the sources to the local config, returning it so the workspaces can be reloaded.
+    pub fn save(&mut self) -> anyhow::Result<LocalConfig> {
+        let catalogue_path = self.catalogue_path.trim();
+        self.config.catalogue = (!catalogue_path.is_empty())
+            .then(|| Catalogue::new(&PathBuf::from(catalogue_path), self.catalogue_read_only));
+
+        // Other windows may have changed the rest of the config since this one opened.
+        let mut config = LocalConfig::read_or_default()?;
+        config.catalogue = self.config.catalogue.clone();
+        config.project_repos = self.config.project_repos.clone();
+        config.write()?;
+        Ok(config)
+    }
+}
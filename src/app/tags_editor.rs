@@ -0,0 +1,49 @@
+use egui::{Key, TextEdit, Ui};
+
+use crate::{tr, workspaces::Workspace};
+
+/// Edits a workspace's tags, or just lists them when it can't be edited. Returns true when
+/// something changed that should be saved.
+pub fn update_tags_editor(
+    ui: &mut Ui,
+    workspace: &mut Workspace,
+    new_tag: &mut String,
+    is_editable: bool,
+) -> bool {
+    if !is_editable && workspace.tags.is_empty() {
+        return false;
+    }
+
+    let mut changed = false;
+    let mut removed_index = None;
+
+    ui.horizontal_wrapped(|ui| {
+        ui.label(tr!("details-tags"));
+        for (index, tag) in workspace.tags.iter().enumerate() {
+            ui.label(format!("#{tag}"));
+            if is_editable && ui.small_button("✖").clicked() {
+                removed_index = Some(index);
+            }
+        }
+
+        if is_editable {
+            let tag_box = ui.add(
+                TextEdit::singleline(new_tag)
+                    .hint_text(tr!("details-new-tag"))
+                    .desired_width(80.0),
+            );
+            let entered = tag_box.lost_focus() && ui.input(|input| input.key_pressed(Key::Enter));
+            if entered || ui.small_button(tr!("button-add")).clicked() {
+                changed |= workspace.add_tag(new_tag);
+                new_tag.clear();
+            }
+        }
+    });
+
+    if let Some(index) = removed_index {
+        workspace.tags.remove(index);
+        changed = true;
+    }
+
+    changed
+}
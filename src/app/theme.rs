@@ -0,0 +1,173 @@
+use std::{
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::{Duration, Instant},
+};
+
+use egui::{Color32, FontFamily, FontId, Key, Stroke, TextStyle, Visuals};
+
+use crate::{
+    preferences::{system_prefers_dark, Preferences, Theme, MAX_FONT_SCALE, MIN_FONT_SCALE},
+    workspaces::WorkspaceColor,
+};
+
+const FONT_SCALE_STEP: f32 = 0.1;
+
+/// How often the OS is asked again while following it, since nothing says when it changes.
+const SYSTEM_THEME_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Text sizes at a font scale of 1, which every style is multiplied from.
+const TEXT_SIZES: [(TextStyle, f32, FontFamily); 5] = [
+    (TextStyle::Heading, 28.0, FontFamily::Proportional),
+    (TextStyle::Body, 18.0, FontFamily::Proportional),
+    (TextStyle::Monospace, 14.0, FontFamily::Monospace),
+    (TextStyle::Button, 14.0, FontFamily::Proportional),
+    (TextStyle::Small, 10.0, FontFamily::Proportional),
+];
+
+/// Only asks the OS about dark mode when following it.
+pub fn apply_preferences(ctx: &egui::Context, preferences: &Preferences) {
+    let system_dark = match preferences.theme {
+        Theme::System => system_prefers_dark(),
+        _ => None,
+    };
+    ctx.set_visuals(visuals(preferences.theme.resolve(system_dark)));
+    configure_text_styles(ctx, preferences.font_scale);
+}
+
+/// Keeps the "follow system" theme in step with the OS while the app is running.
+#[derive(Default)]
+pub struct SystemThemeWatcher {
+    last_check: Option<Instant>,
+    system_dark: Option<bool>,
+    /// The OS is asked on a background thread, since that can mean running a program.
+    check: Option<Receiver<Option<bool>>>,
+}
+
+impl SystemThemeWatcher {
+    pub fn update(&mut self, ctx: &egui::Context, preferences: &Preferences) {
+        if preferences.theme != Theme::System {
+            self.last_check = None;
+            self.check = None;
+            return;
+        }
+
+        if let Some(check) = self.check.as_ref() {
+            match check.try_recv() {
+                Ok(system_dark) => {
+                    self.check = None;
+                    if system_dark != self.system_dark {
+                        self.system_dark = system_dark;
+                        ctx.set_visuals(visuals(Theme::System.resolve(system_dark)));
+                    }
+                }
+                Err(mpsc::TryRecvError::Empty) => {
+                    ctx.request_repaint_after(Duration::from_millis(250));
+                    return;
+                }
+                Err(mpsc::TryRecvError::Disconnected) => self.check = None,
+            }
+        }
+
+        ctx.request_repaint_after(SYSTEM_THEME_CHECK_INTERVAL);
+        if self
+            .last_check
+            .is_some_and(|last_check| last_check.elapsed() < SYSTEM_THEME_CHECK_INTERVAL)
+        {
+            return;
+        }
+
+        self.last_check = Some(Instant::now());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(system_prefers_dark());
+        });
+        self.check = Some(receiver);
+        ctx.request_repaint_after(Duration::from_millis(250));
+    }
+}
+
+fn visuals(theme: Theme) -> Visuals {
+    match theme {
+        Theme::Light => Visuals::light(),
+        Theme::HighContrast => high_contrast_visuals(),
+        Theme::Dark | Theme::System => Visuals::dark(),
+    }
+}
+
+/// White on black with yellow selection and focus, and every edge drawn.
+fn high_contrast_visuals() -> Visuals {
+    let yellow = Color32::from_rgb(0xff, 0xd7, 0x00);
+    let mut visuals = Visuals::dark();
+
+    visuals.panel_fill = Color32::BLACK;
+    visuals.window_fill = Color32::BLACK;
+    visuals.extreme_bg_color = Color32::BLACK;
+    visuals.faint_bg_color = Color32::from_gray(24);
+    visuals.hyperlink_color = Color32::from_rgb(0x80, 0xc8, 0xff);
+    visuals.window_stroke = Stroke::new(1.0, Color32::WHITE);
+    visuals.selection.bg_fill = yellow;
+    visuals.selection.stroke = Stroke::new(1.5, Color32::BLACK);
+
+    let widgets = &mut visuals.widgets;
+    for state in [
+        &mut widgets.noninteractive,
+        &mut widgets.inactive,
+        &mut widgets.hovered,
+        &mut widgets.active,
+        &mut widgets.open,
+    ] {
+        state.fg_stroke = Stroke::new(1.5, Color32::WHITE);
+        state.bg_stroke = Stroke::new(1.0, Color32::WHITE);
+    }
+    widgets.noninteractive.bg_fill = Color32::BLACK;
+    widgets.inactive.bg_fill = Color32::BLACK;
+    widgets.hovered.bg_stroke = Stroke::new(2.0, yellow);
+    widgets.active.bg_stroke = Stroke::new(2.0, yellow);
+
+    visuals
+}
+
+/// Mid-tones, so that names stay readable on both light and dark backgrounds.
+pub fn workspace_color(color: WorkspaceColor) -> Color32 {
+    match color {
+        WorkspaceColor::Red => Color32::from_rgb(0xe0, 0x4f, 0x4f),
+        WorkspaceColor::Orange => Color32::from_rgb(0xe8, 0x8a, 0x2e),
+        WorkspaceColor::Yellow => Color32::from_rgb(0xd4, 0xb0, 0x1c),
+        WorkspaceColor::Green => Color32::from_rgb(0x4c, 0xae, 0x4f),
+        WorkspaceColor::Blue => Color32::from_rgb(0x3f, 0x8f, 0xe0),
+        WorkspaceColor::Purple => Color32::from_rgb(0x9c, 0x5f, 0xd6),
+        WorkspaceColor::Grey => Color32::from_gray(0x90),
+    }
+}
+
+fn configure_text_styles(ctx: &egui::Context, font_scale: f32) {
+    let mut style = (*ctx.style()).clone();
+    style.text_styles = TEXT_SIZES
+        .into_iter()
+        .map(|(text_style, size, family)| (text_style, FontId::new(size * font_scale, family)))
+        .collect();
+    ctx.set_style(style);
+}
+
+/// Ctrl with plus, minus or zero grows, shrinks or resets the text. Returns the new scale when
+/// one was pressed.
+pub fn font_scale_shortcut(ctx: &egui::Context, font_scale: f32) -> Option<f32> {
+    let (grow, shrink, reset) = ctx.input(|input| {
+        let command = input.modifiers.command;
+        (
+            command && input.key_pressed(Key::PlusEquals),
+            command && input.key_pressed(Key::Minus),
+            command && input.key_pressed(Key::Num0),
+        )
+    });
+
+    let scaled = match (grow, shrink, reset) {
+        (true, _, _) => font_scale + FONT_SCALE_STEP,
+        (_, true, _) => font_scale - FONT_SCALE_STEP,
+        (_, _, true) => 1.0,
+        _ => return None,
+    };
+    let rounded = (scaled * 10.0).round() / 10.0;
+    Some(rounded.clamp(MIN_FONT_SCALE, MAX_FONT_SCALE))
+}
@@ -0,0 +1,125 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::{
+    error::{parse_xml, read_file, Error},
+    paths::sourcetree_settings_path,
+};
+
+/// SourceTree's bookmarks, a tree of folders and repos.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename = "ArrayOfTreeViewNode")]
+pub struct Bookmarks {
+    #[serde(rename = "TreeViewNode", default)]
+    pub nodes: Vec<BookmarkNode>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct BookmarkNode {
+    #[serde(rename = "Name", default)]
+    pub name: String,
+    /// Only bookmarks have a path. Folders don't.
+    #[serde(rename = "Path", default)]
+    pub path: Option<String>,
+    #[serde(rename = "Children", default)]
+    pub children: BookmarkChildren,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct BookmarkChildren {
+    #[serde(rename = "TreeViewNode", default)]
+    pub nodes: Vec<BookmarkNode>,
+}
+
+impl Bookmarks {
+    pub fn path() -> Option<PathBuf> {
+        Some(sourcetree_settings_path()?.join("bookmarks.xml"))
+    }
+
+    pub fn read() -> anyhow::Result<Bookmarks> {
+        let path = Bookmarks::path().ok_or(Error::PathNotFound("bookmarks file"))?;
+
+        read_from_path(&path)
+    }
+
+    /// Every bookmarked repo path, at any depth.
+    pub fn repo_paths(&self) -> Vec<String> {
+        let mut repo_paths = Vec::new();
+        collect_repo_paths(&self.nodes, &mut repo_paths);
+        repo_paths
+    }
+
+    /// The bookmarked repo paths inside the named folder, including its sub-folders.
+    pub fn folder_repo_paths(&self, folder: &str) -> Vec<String> {
+        let mut repo_paths = Vec::new();
+        let mut pending: Vec<&BookmarkNode> = self.nodes.iter().collect();
+        while let Some(node) = pending.pop() {
+            if node.path.is_none() && node.name.eq_ignore_ascii_case(folder) {
+                collect_repo_paths(&node.children.nodes, &mut repo_paths);
+            } else {
+                pending.extend(node.children.nodes.iter());
+            }
+        }
+        repo_paths
+    }
+}
+
+fn collect_repo_paths(nodes: &[BookmarkNode], repo_paths: &mut Vec<String>) {
+    for node in nodes {
+        if let Some(path) = node.path.as_ref().filter(|path| !path.is_empty()) {
+            repo_paths.push(path.clone());
+        }
+        collect_repo_paths(&node.children.nodes, repo_paths);
+    }
+}
+
+fn read_from_path(path: &Path) -> anyhow::Result<Bookmarks> {
+    let contents = read_file(path)?;
+    let bookmarks: Bookmarks = parse_xml(path, &contents)?;
+    Ok(bookmarks)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_xml_rs::from_str;
+
+    use super::*;
+
+    #[test]
+    fn should_load_bookmark_folders() {
+        let bookmarks_doc = r#"<?xml version="1.0"?>
+            <ArrayOfTreeViewNode xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+              <TreeViewNode xsi:type="BookmarkFolderNode">
+                <Level>0</Level>
+                <Name>Client A</Name>
+                <Children>
+                  <TreeViewNode xsi:type="BookmarkNode">
+                    <Level>1</Level>
+                    <Name>api</Name>
+                    <Children />
+                    <Path>C:\clients\a\api</Path>
+                    <RepoType>Git</RepoType>
+                  </TreeViewNode>
+                </Children>
+              </TreeViewNode>
+              <TreeViewNode xsi:type="BookmarkNode">
+                <Level>0</Level>
+                <Name>tools</Name>
+                <Children />
+                <Path>C:\dev\tools</Path>
+              </TreeViewNode>
+            </ArrayOfTreeViewNode>"#;
+
+        let bookmarks: Bookmarks = from_str(bookmarks_doc).unwrap();
+        assert_eq!(
+            bookmarks.repo_paths(),
+            vec![r"C:\clients\a\api", r"C:\dev\tools"]
+        );
+        assert_eq!(
+            bookmarks.folder_repo_paths("client a"),
+            vec![r"C:\clients\a\api"]
+        );
+        assert!(bookmarks.folder_repo_paths("Client B").is_empty());
+    }
+}
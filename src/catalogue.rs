@@ -0,0 +1,144 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    git::{is_git_work_tree, run_git},
+    tr,
+    workspaces::Workspaces,
+};
+
+const CATALOGUE_FILE_NAME: &str = "st-workspaces.json";
+
+/// A team's shared workspaces, kept in any directory. When the directory is a git checkout it can
+/// be pulled and committed locally, so no hosted service is needed.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct Catalogue {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+impl Catalogue {
+    pub fn new(path: &Path, read_only: bool) -> Self {
+        Self {
+            path: path.to_owned(),
+            read_only,
+        }
+    }
+
+    pub fn file_path(&self) -> PathBuf {
+        self.path.join(CATALOGUE_FILE_NAME)
+    }
+
+    pub fn is_git_repo(&self) -> bool {
+        is_git_work_tree(&self.path)
+    }
+
+    /// Reads the shared workspaces. A catalogue that hasn't been written yet is empty.
+    pub fn read(&self) -> anyhow::Result<Workspaces> {
+        if !self.file_path().exists() {
+            return Ok(Workspaces::default());
+        }
+
+        Workspaces::read_from(&self.file_path())
+    }
+
+    pub fn write(&self, workspaces: &Workspaces) -> anyhow::Result<()> {
+        if self.read_only {
+            bail!(tr!("error-catalogue-read-only"));
+        }
+
+        workspaces.write_to(&self.file_path())
+    }
+
+    /// Fast-forwards the catalogue checkout from its upstream.
+    pub fn pull(&self) -> anyhow::Result<String> {
+        if !self.is_git_repo() {
+            bail!(tr!("error-catalogue-not-git"));
+        }
+
+        run_git(&self.path, &["pull", "--ff-only"])
+    }
+
+    /// Commits the catalogue file. Returns false if there was nothing to commit.
+    pub fn commit(&self, message: &str) -> anyhow::Result<bool> {
+        if self.read_only {
+            bail!(tr!("error-catalogue-read-only"));
+        }
+
+        if !self.is_git_repo() {
+            bail!(tr!("error-catalogue-not-git"));
+        }
+
+        let changes = run_git(
+            &self.path,
+            &["status", "--porcelain", "--", CATALOGUE_FILE_NAME],
+        )?;
+        if changes.trim().is_empty() {
+            return Ok(false);
+        }
+
+        run_git(&self.path, &["add", "--", CATALOGUE_FILE_NAME])?;
+        run_git(
+            &self.path,
+            &["commit", "-m", message, "--", CATALOGUE_FILE_NAME],
+        )?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::workspaces::Workspace;
+
+    fn create_test_catalogue() -> Catalogue {
+        let path = std::env::temp_dir().join(format!("st-workspaces-catalogue-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&path).unwrap();
+        run_git(&path, &["init", "--quiet"]).unwrap();
+        run_git(&path, &["config", "user.name", "Test"]).unwrap();
+        run_git(&path, &["config", "user.email", "test@example.com"]).unwrap();
+        Catalogue::new(&path, false)
+    }
+
+    fn create_test_workspaces() -> Workspaces {
+        let mut spaces = Workspaces::default();
+        let mut space = Workspace::new("Team Workspace", Uuid::new_v4());
+        space.repo_paths.push("${CODE}/shared-one".to_owned());
+        spaces.workspaces.insert(space.uuid, space);
+        spaces
+    }
+
+    #[test]
+    fn should_read_empty_catalogue() -> anyhow::Result<()> {
+        let catalogue = create_test_catalogue();
+        assert!(catalogue.read()?.workspaces.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn should_commit_catalogue() -> anyhow::Result<()> {
+        let catalogue = create_test_catalogue();
+        catalogue.write(&create_test_workspaces())?;
+
+        assert!(catalogue.commit("Add team workspace")?);
+        assert!(!catalogue.commit("Nothing changed")?);
+
+        let log = run_git(&catalogue.path, &["log", "--oneline"])?;
+        assert!(log.contains("Add team workspace"));
+        assert_eq!(catalogue.read()?.workspaces.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn should_refuse_writing_read_only_catalogue() {
+        let mut catalogue = create_test_catalogue();
+        catalogue.read_only = true;
+        assert!(catalogue.write(&create_test_workspaces()).is_err());
+        assert!(catalogue.commit("Not allowed").is_err());
+    }
+}
@@ -1 +1,111 @@
+use std::path::{Path, PathBuf};
 
+use serde::Deserialize;
+
+use crate::{
+    error::{parse_xml, read_file, Error},
+    paths::sourcetree_settings_path,
+};
+
+/// SourceTree's custom actions, which is how it runs st-workspaces.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename = "ArrayOfCustomAction")]
+pub struct CustomActions {
+    #[serde(rename = "CustomAction", default)]
+    pub actions: Vec<CustomAction>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "PascalCase")]
+pub struct CustomAction {
+    #[serde(default)]
+    pub caption: String,
+    #[serde(default)]
+    pub target: String,
+    #[serde(default)]
+    pub arguments: String,
+}
+
+impl CustomActions {
+    pub fn path() -> Option<PathBuf> {
+        Some(sourcetree_settings_path()?.join("customactions.xml"))
+    }
+
+    pub fn read() -> anyhow::Result<CustomActions> {
+        let path = CustomActions::path().ok_or(Error::PathNotFound("custom actions file"))?;
+        read_from_path(&path)
+    }
+
+    /// The actions that run st-workspaces, going by the name of the program. Targets are Windows
+    /// paths, so they're split by hand.
+    pub fn st_workspaces_actions(&self) -> Vec<&CustomAction> {
+        self.actions
+            .iter()
+            .filter(|action| {
+                let file_name = action.target.rsplit(['/', '\\']).next().unwrap_or_default();
+                let stem = file_name
+                    .rsplit_once('.')
+                    .map_or(file_name, |(stem, _)| stem);
+                stem.eq_ignore_ascii_case("st-workspaces")
+            })
+            .collect()
+    }
+}
+
+impl CustomAction {
+    pub fn targets(&self, program: &Path) -> bool {
+        let target = Path::new(&self.target);
+        if target == program {
+            return true;
+        }
+
+        match (
+            std::fs::canonicalize(target),
+            std::fs::canonicalize(program),
+        ) {
+            (Ok(target), Ok(program)) => target == program,
+            _ => false,
+        }
+    }
+}
+
+pub fn read_from_path(path: &Path) -> anyhow::Result<CustomActions> {
+    let contents = read_file(path)?;
+    let custom_actions: CustomActions = parse_xml(path, &contents)?;
+    Ok(custom_actions)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_xml_rs::from_str;
+
+    use super::*;
+
+    #[test]
+    fn should_find_st_workspaces_actions() {
+        let actions_doc = r#"<?xml version="1.0"?>
+            <ArrayOfCustomAction xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+                <CustomAction>
+                    <Caption>Save Workspace</Caption>
+                    <OpenInSeparateWindow>false</OpenInSeparateWindow>
+                    <ShowFullOutput>false</ShowFullOutput>
+                    <Target>C:\tools\st-workspaces.exe</Target>
+                    <Arguments>auto-update-and-close</Arguments>
+                </CustomAction>
+                <CustomAction>
+                    <Caption>Open in Editor</Caption>
+                    <Target>C:\tools\editor.exe</Target>
+                    <Arguments>$REPO</Arguments>
+                </CustomAction>
+            </ArrayOfCustomAction>"#;
+
+        let custom_actions: CustomActions = from_str(actions_doc).unwrap();
+        let actions = custom_actions.st_workspaces_actions();
+
+        assert_eq!(custom_actions.actions.len(), 2);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].arguments, "auto-update-and-close");
+        assert!(actions[0].targets(Path::new(r"C:\tools\st-workspaces.exe")));
+        assert!(!actions[0].targets(Path::new(r"C:\other\st-workspaces.exe")));
+    }
+}
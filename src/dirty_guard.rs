@@ -0,0 +1,269 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    git::{is_git_work_tree, run_git},
+    git_batch::{RepoOutcome, RepoResult},
+    tr,
+};
+
+/// A git operation left half done in a working copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InProgress {
+    Rebase,
+    Merge,
+    CherryPick,
+    Revert,
+    Bisect,
+}
+
+impl std::fmt::Display for InProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InProgress::Rebase => write!(f, "rebase"),
+            InProgress::Merge => write!(f, "merge"),
+            InProgress::CherryPick => write!(f, "cherry-pick"),
+            InProgress::Revert => write!(f, "revert"),
+            InProgress::Bisect => write!(f, "bisect"),
+        }
+    }
+}
+
+/// What is going on in a repo that's about to be closed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoState {
+    pub repo_path: String,
+    /// Changed and untracked files.
+    pub changed_files: usize,
+    pub stash_count: usize,
+    pub in_progress: Option<InProgress>,
+}
+
+impl RepoState {
+    /// Whether closing the repo could leave work behind.
+    pub fn needs_attention(&self) -> bool {
+        self.changed_files > 0 || self.in_progress.is_some()
+    }
+
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.changed_files > 0 {
+            parts.push(tr!("repo-state-changes", count = self.changed_files));
+        }
+
+        if let Some(in_progress) = self.in_progress {
+            parts.push(tr!(
+                "repo-state-in-progress",
+                operation = in_progress.to_string()
+            ));
+        }
+
+        if self.stash_count > 0 {
+            parts.push(tr!("repo-state-stashes", count = self.stash_count));
+        }
+
+        match parts.is_empty() {
+            true => tr!("repo-state-clean"),
+            false => parts.join(", "),
+        }
+    }
+}
+
+/// A stash made when switching away from a workspace, to be popped when it is opened again.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PendingStash {
+    pub repo_path: String,
+    /// The stash message, which is how the stash is found again.
+    pub message: String,
+}
+
+pub fn inspect_repo(repo_path: &str) -> anyhow::Result<RepoState> {
+    let path = Path::new(repo_path);
+    let status = run_git(path, &["status", "--porcelain"])?;
+    let stashes = run_git(path, &["stash", "list"])?;
+    let git_dir = PathBuf::from(run_git(path, &["rev-parse", "--absolute-git-dir"])?.trim());
+
+    Ok(RepoState {
+        repo_path: repo_path.to_owned(),
+        changed_files: status.lines().filter(|line| !line.is_empty()).count(),
+        stash_count: stashes.lines().filter(|line| !line.is_empty()).count(),
+        in_progress: detect_in_progress(&git_dir),
+    })
+}
+
+/// The repos that need attention before they're closed. Repos that can't be read as git working
+/// copies are left out.
+pub fn repos_needing_attention(repo_paths: &[String]) -> Vec<RepoState> {
+    repo_paths
+        .iter()
+        .filter(|repo_path| is_git_work_tree(Path::new(repo_path)))
+        .filter_map(|repo_path| inspect_repo(repo_path).ok())
+        .filter(|state| state.needs_attention())
+        .collect()
+}
+
+/// Stashes the repo's changes, including untracked files, under a message naming the workspace.
+pub fn stash_changes(repo_path: &str, workspace_name: &str, workspace_id: Uuid) -> RepoResult {
+    let result = |outcome, summary: &str, output: &str| RepoResult {
+        repo_path: repo_path.to_owned(),
+        outcome,
+        summary: summary.to_owned(),
+        output: output.to_owned(),
+    };
+
+    let path = Path::new(repo_path);
+    match inspect_repo(repo_path) {
+        Ok(state) if state.in_progress.is_some() => {
+            return result(
+                RepoOutcome::Failed,
+                &tr!("repo-not-stashed", state = state.summary()),
+                "",
+            )
+        }
+        Ok(state) if state.changed_files == 0 => {
+            return result(RepoOutcome::Skipped, &tr!("repo-nothing-to-stash"), "")
+        }
+        Ok(_) => {}
+        Err(why) => {
+            return result(
+                RepoOutcome::Failed,
+                &tr!("repo-error-reading-status"),
+                &why.to_string(),
+            )
+        }
+    }
+
+    let message = stash_message(workspace_name, workspace_id);
+    match run_git(
+        path,
+        &[
+            "stash",
+            "push",
+            "--include-untracked",
+            "--message",
+            &message,
+        ],
+    ) {
+        Ok(output) => result(RepoOutcome::Succeeded, &message, &output),
+        Err(why) => result(
+            RepoOutcome::Failed,
+            &tr!("repo-error-stashing"),
+            &why.to_string(),
+        ),
+    }
+}
+
+/// Pops the stash made when the workspace was switched away from.
+pub fn pop_stash(stash: &PendingStash) -> RepoResult {
+    let result = |outcome, summary: &str, output: &str| RepoResult {
+        repo_path: stash.repo_path.clone(),
+        outcome,
+        summary: summary.to_owned(),
+        output: output.to_owned(),
+    };
+
+    let path = Path::new(&stash.repo_path);
+    let stashes = match run_git(path, &["stash", "list", "--format=%gd%x09%s"]) {
+        Ok(stashes) => stashes,
+        Err(why) => {
+            return result(
+                RepoOutcome::Failed,
+                &tr!("repo-error-listing-stashes"),
+                &why.to_string(),
+            )
+        }
+    };
+
+    let reference = stashes.lines().find_map(|line| {
+        let (reference, subject) = line.split_once('\t')?;
+        subject.ends_with(&stash.message).then_some(reference)
+    });
+
+    let Some(reference) = reference else {
+        return result(RepoOutcome::Skipped, &tr!("repo-stash-gone"), "");
+    };
+
+    match run_git(path, &["stash", "pop", "--quiet", reference]) {
+        Ok(output) => result(RepoOutcome::Succeeded, &tr!("repo-restored-stash"), &output),
+        Err(why) => result(
+            RepoOutcome::Failed,
+            &tr!("repo-conflict-restoring-stash"),
+            &why.to_string(),
+        ),
+    }
+}
+
+fn stash_message(workspace_name: &str, workspace_id: Uuid) -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    format!("st-workspaces: {workspace_name} ({workspace_id}) {seconds}")
+}
+
+fn detect_in_progress(git_dir: &Path) -> Option<InProgress> {
+    if git_dir.join("rebase-merge").exists() || git_dir.join("rebase-apply").exists() {
+        Some(InProgress::Rebase)
+    } else if git_dir.join("MERGE_HEAD").exists() {
+        Some(InProgress::Merge)
+    } else if git_dir.join("CHERRY_PICK_HEAD").exists() {
+        Some(InProgress::CherryPick)
+    } else if git_dir.join("REVERT_HEAD").exists() {
+        Some(InProgress::Revert)
+    } else if git_dir.join("BISECT_LOG").exists() {
+        Some(InProgress::Bisect)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git_batch::tests::create_test_repo;
+
+    #[test]
+    fn should_find_repos_needing_attention() {
+        let clean = create_test_repo("clean");
+        let changed = create_test_repo("changed");
+        let merging = create_test_repo("merging");
+        std::fs::write(changed.join("new.txt"), "new\n").unwrap();
+        let head = run_git(&merging, &["rev-parse", "HEAD"]).unwrap();
+        std::fs::write(merging.join(".git/MERGE_HEAD"), head).unwrap();
+
+        let repo_paths: Vec<_> = [&clean, &changed, &merging]
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        let states = repos_needing_attention(&repo_paths);
+
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[0].changed_files, 1);
+        assert_eq!(states[1].in_progress, Some(InProgress::Merge));
+    }
+
+    #[test]
+    fn should_stash_and_pop_labelled_changes() {
+        let repo = create_test_repo("stash");
+        let repo_path = repo.display().to_string();
+        std::fs::write(repo.join("README.md"), "changed\n").unwrap();
+        std::fs::write(repo.join("untracked.txt"), "new\n").unwrap();
+
+        let stashed = stash_changes(&repo_path, "Hotfix", Uuid::new_v4());
+        assert_eq!(stashed.outcome, RepoOutcome::Succeeded);
+        assert!(!inspect_repo(&repo_path).unwrap().needs_attention());
+
+        let stash = PendingStash {
+            repo_path: repo_path.clone(),
+            message: stashed.summary,
+        };
+        assert_eq!(pop_stash(&stash).outcome, RepoOutcome::Succeeded);
+        assert_eq!(inspect_repo(&repo_path).unwrap().changed_files, 2);
+        assert_eq!(pop_stash(&stash).outcome, RepoOutcome::Skipped);
+    }
+}
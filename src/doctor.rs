@@ -0,0 +1,503 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use uuid::Uuid;
+
+use crate::{
+    custom_actions::{self, CustomActions},
+    error::{parse_xml, read_file},
+    local_config::LocalConfig,
+    open_tabs::OpenTabs,
+    paths::{log_dir_path, sourcetree_settings_path},
+    preferences::Preferences,
+    roots::PathRoots,
+    tr,
+    workspaces::Workspaces,
+};
+
+/// The SourceTree release this version of st-workspaces was made against.
+pub const SUPPORTED_SOURCETREE_VERSION: (u32, u32) = (3, 4);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl Display for CheckStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckStatus::Pass => write!(f, "PASS"),
+            CheckStatus::Warn => write!(f, "WARN"),
+            CheckStatus::Fail => write!(f, "FAIL"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+    /// What to do about a warning or failure.
+    pub fix: Option<String>,
+}
+
+impl CheckResult {
+    fn pass(name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Pass,
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: impl Into<String>, message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Warn,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: impl Into<String>, message: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Fail,
+            message: message.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Checks everything switching workspaces relies on, in the order it's relied on.
+pub fn run_checks() -> Vec<CheckResult> {
+    let settings_path = sourcetree_settings_path();
+    let mut results = vec![check_settings_path(settings_path.as_deref())];
+    if results[0].status == CheckStatus::Fail {
+        return results;
+    }
+
+    let program = std::env::current_exe().unwrap_or_default();
+    if let Some(path) = OpenTabs::path() {
+        results.push(check_open_tabs(&path));
+    }
+    if let Some(path) = Workspaces::path() {
+        results.push(check_workspaces_file(&path));
+    }
+    if let Some(path) = CustomActions::path() {
+        results.push(check_custom_actions(&path, &program));
+    }
+    let exec_path = Preferences::read()
+        .unwrap_or_default()
+        .sourcetree_exec_path();
+    results.push(check_sourcetree(exec_path.as_deref()));
+    if let Some(path) = log_dir_path() {
+        results.push(check_log_dir(&path));
+    }
+
+    let workspaces = Workspaces::read().unwrap_or_default();
+    let roots = LocalConfig::read().unwrap_or_default().roots;
+    results.push(check_repo_paths(&workspaces, &roots));
+    results
+}
+
+pub fn has_failures(results: &[CheckResult]) -> bool {
+    results
+        .iter()
+        .any(|result| result.status == CheckStatus::Fail)
+}
+
+/// The results as plain text, for the command line and for pasting into a support question.
+pub fn report(results: &[CheckResult]) -> String {
+    let mut lines = vec![tr!(
+        "doctor-report-title",
+        version = env!("CARGO_PKG_VERSION")
+    )];
+    for result in results {
+        lines.push(format!(
+            "[{}] {}: {}",
+            result.status, result.name, result.message
+        ));
+        if let Some(fix) = result.fix.as_ref() {
+            lines.push(format!("       {}", tr!("doctor-fix", fix = fix.as_str())));
+        }
+    }
+
+    lines.join("\n")
+}
+
+pub fn check_settings_path(settings_path: Option<&Path>) -> CheckResult {
+    let name = tr!("check-settings-path");
+    match settings_path {
+        Some(path) if path.is_dir() => CheckResult::pass(name, path.display().to_string()),
+        Some(path) => CheckResult::fail(
+            name,
+            tr!("check-path-missing", path = path.display().to_string()),
+            tr!("check-settings-path-fix"),
+        ),
+        None => CheckResult::fail(
+            name,
+            tr!("check-settings-path-no-app-data"),
+            tr!("check-settings-path-no-app-data-fix"),
+        ),
+    }
+}
+
+pub fn check_open_tabs(path: &Path) -> CheckResult {
+    let name = tr!("check-open-tabs");
+    if !path.exists() {
+        return CheckResult::warn(
+            name,
+            tr!("check-path-missing-yet", path = path.display().to_string()),
+            tr!("check-open-tabs-missing-fix"),
+        );
+    }
+
+    match read_file(path).and_then(|contents| parse_xml::<OpenTabs>(path, &contents)) {
+        Ok(open_tabs) => CheckResult::pass(
+            name,
+            tr!(
+                "check-open-tabs-pass",
+                count = open_tabs.tabs.len(),
+                path = path.display().to_string()
+            ),
+        ),
+        Err(why) => CheckResult::fail(name, why.to_string(), tr!("check-open-tabs-fail-fix")),
+    }
+}
+
+pub fn check_workspaces_file(path: &Path) -> CheckResult {
+    let name = tr!("check-workspaces-file");
+    if !path.exists() {
+        return CheckResult::warn(
+            name,
+            tr!("check-path-missing-yet", path = path.display().to_string()),
+            tr!("check-workspaces-file-missing-fix"),
+        );
+    }
+
+    let workspaces = match Workspaces::read_from(path) {
+        Ok(workspaces) => workspaces,
+        Err(why) => {
+            return CheckResult::fail(name, why.to_string(), tr!("check-workspaces-file-fail-fix"))
+        }
+    };
+
+    let problems = schema_problems(&workspaces);
+    if problems.is_empty() {
+        return CheckResult::pass(
+            name,
+            tr!(
+                "check-workspaces-file-pass",
+                count = workspaces.workspaces.len(),
+                path = path.display().to_string()
+            ),
+        );
+    }
+
+    CheckResult::warn(
+        name,
+        problems.join(" "),
+        tr!("check-workspaces-file-problems-fix"),
+    )
+}
+
+/// What parses but still isn't right, such as a workspace stored under another workspace's id.
+pub fn schema_problems(workspaces: &Workspaces) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut uuids: Vec<&Uuid> = workspaces.workspaces.keys().collect();
+    uuids.sort();
+
+    for uuid in uuids {
+        let workspace = &workspaces.workspaces[uuid];
+        if workspace.uuid != *uuid {
+            problems.push(tr!(
+                "check-problem-wrong-id",
+                uuid = workspace.uuid.to_string(),
+                id = uuid.to_string()
+            ));
+        }
+
+        if workspace.name.trim().is_empty() {
+            problems.push(tr!("check-problem-no-name", uuid = uuid.to_string()));
+        }
+    }
+
+    if !workspaces.current_workspace.is_nil()
+        && !workspaces
+            .workspaces
+            .contains_key(&workspaces.current_workspace)
+    {
+        problems.push(tr!(
+            "check-problem-missing-current",
+            uuid = workspaces.current_workspace.to_string()
+        ));
+    }
+
+    problems
+}
+
+pub fn check_custom_actions(path: &Path, program: &Path) -> CheckResult {
+    let name = tr!("check-custom-actions");
+    let program_path = program.display().to_string();
+    let install_fix = tr!(
+        "check-custom-actions-install-fix",
+        path = program_path.as_str()
+    );
+
+    if !path.exists() {
+        return CheckResult::warn(name, tr!("check-custom-actions-missing"), install_fix);
+    }
+
+    let custom_actions = match custom_actions::read_from_path(path) {
+        Ok(custom_actions) => custom_actions,
+        Err(why) => {
+            return CheckResult::fail(name, why.to_string(), tr!("check-custom-actions-fail-fix"))
+        }
+    };
+
+    let actions = custom_actions.st_workspaces_actions();
+    if actions.is_empty() {
+        return CheckResult::warn(name, tr!("check-custom-actions-none"), install_fix);
+    }
+
+    let elsewhere: Vec<_> = actions
+        .iter()
+        .filter(|action| !action.targets(program))
+        .map(|action| {
+            tr!(
+                "check-custom-actions-runs",
+                caption = action.caption.as_str(),
+                target = action.target.as_str()
+            )
+        })
+        .collect();
+    if !elsewhere.is_empty() {
+        return CheckResult::fail(
+            name,
+            tr!(
+                "check-custom-actions-elsewhere",
+                actions = elsewhere.join(", "),
+                path = program_path.as_str()
+            ),
+            tr!(
+                "check-custom-actions-elsewhere-fix",
+                path = program_path.as_str()
+            ),
+        );
+    }
+
+    CheckResult::pass(
+        name,
+        tr!(
+            "check-custom-actions-pass",
+            count = actions.len(),
+            path = program_path.as_str()
+        ),
+    )
+}
+
+pub fn check_sourcetree(exec_path: Option<&Path>) -> CheckResult {
+    let name = tr!("check-sourcetree");
+    let (major, minor) = SUPPORTED_SOURCETREE_VERSION;
+    let supported = format!("{major}.{minor}");
+    let install_fix = tr!("check-sourcetree-install-fix", version = supported.as_str());
+
+    let Some(exec_path) = exec_path.filter(|path| path.exists()) else {
+        let location = exec_path
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        return CheckResult::fail(
+            name,
+            tr!("check-sourcetree-missing", path = location),
+            install_fix,
+        );
+    };
+
+    let installed = exec_path.parent().and_then(sourcetree_version);
+    match installed {
+        Some(version) if (version.0, version.1) == SUPPORTED_SOURCETREE_VERSION => {
+            CheckResult::pass(
+                name,
+                tr!(
+                    "check-sourcetree-pass",
+                    version = format!("{}.{}.{}", version.0, version.1, version.2),
+                    path = exec_path.display().to_string()
+                ),
+            )
+        }
+        Some(version) => CheckResult::warn(
+            name,
+            tr!(
+                "check-sourcetree-untested",
+                version = format!("{}.{}.{}", version.0, version.1, version.2),
+                app_version = env!("CARGO_PKG_VERSION")
+            ),
+            tr!("check-sourcetree-supported", version = supported.as_str()),
+        ),
+        None => CheckResult::warn(
+            name,
+            tr!(
+                "check-sourcetree-unknown-version",
+                path = exec_path.display().to_string()
+            ),
+            tr!("check-sourcetree-supported", version = supported.as_str()),
+        ),
+    }
+}
+
+/// The newest `app-x.y.z` directory that SourceTree's installer leaves next to its launcher.
+pub fn sourcetree_version(install_dir: &Path) -> Option<(u32, u32, u32)> {
+    std::fs::read_dir(install_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let mut parts = name.strip_prefix("app-")?.split('.');
+            let version = (
+                parts.next()?.parse().ok()?,
+                parts.next()?.parse().ok()?,
+                parts.next().and_then(|part| part.parse().ok()).unwrap_or(0),
+            );
+            Some(version)
+        })
+        .max()
+}
+
+pub fn check_log_dir(path: &Path) -> CheckResult {
+    let name = tr!("check-log-dir");
+    let probe = path.join(format!("doctor-{}.tmp", Uuid::new_v4()));
+    let writable = std::fs::create_dir_all(path)
+        .and_then(|_| std::fs::write(&probe, "doctor"))
+        .and_then(|_| std::fs::remove_file(&probe));
+
+    match writable {
+        Ok(()) => CheckResult::pass(name, path.display().to_string()),
+        Err(why) => CheckResult::fail(
+            name,
+            tr!(
+                "check-log-dir-fail",
+                path = path.display().to_string(),
+                error = why.to_string()
+            ),
+            tr!("check-log-dir-fail-fix"),
+        ),
+    }
+}
+
+pub fn check_repo_paths(workspaces: &Workspaces, roots: &PathRoots) -> CheckResult {
+    let name = tr!("check-repo-paths");
+    let mut missing = Vec::new();
+    let mut count = 0;
+    let mut sorted: Vec<_> = workspaces.workspaces.values().collect();
+    sorted.sort();
+    for workspace in sorted {
+        for repo_path in workspace.repo_paths.iter() {
+            count += 1;
+            let expanded = PathBuf::from(roots.expand(repo_path));
+            if !expanded.is_dir() {
+                missing.push(format!("{}: {}", workspace.name, expanded.display()));
+            }
+        }
+    }
+
+    if missing.is_empty() {
+        return CheckResult::pass(name, tr!("check-repo-paths-pass", count = count));
+    }
+
+    CheckResult::warn(
+        name,
+        tr!(
+            "check-repo-paths-missing",
+            missing = missing.len(),
+            count = count,
+            paths = missing.join(", ")
+        ),
+        tr!("check-repo-paths-missing-fix"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::workspaces::Workspace;
+
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let path = std::env::temp_dir().join(format!("st-workspaces-doctor-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn should_find_newest_sourcetree_version() {
+        let install_dir = temp_dir();
+        for name in ["app-3.3.9", "app-3.4.12", "app-3.4.2", "packages"] {
+            std::fs::create_dir(install_dir.join(name)).unwrap();
+        }
+
+        assert_eq!(sourcetree_version(&install_dir), Some((3, 4, 12)));
+        std::fs::remove_dir_all(install_dir).unwrap();
+    }
+
+    #[test]
+    fn should_fail_unparseable_workspaces() {
+        let dir = temp_dir();
+        let path = dir.join("st-workspaces.json");
+        std::fs::write(&path, "{\n  \"current_workspace\": 3\n}").unwrap();
+
+        let result = check_workspaces_file(&path);
+
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.message.contains("line 2"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn should_find_schema_problems() {
+        let mut workspaces = Workspaces::default();
+        let workspace = Workspace::new(" ", Uuid::new_v4());
+        workspaces.workspaces.insert(Uuid::new_v4(), workspace);
+        workspaces.current_workspace = Uuid::new_v4();
+
+        assert_eq!(schema_problems(&workspaces).len(), 3);
+    }
+
+    #[test]
+    fn should_warn_about_missing_repo_paths() {
+        let dir = temp_dir();
+        let mut workspace = Workspace::new("Mixed", Uuid::new_v4());
+        workspace.repo_paths = vec![
+            dir.display().to_string(),
+            dir.join("gone").display().to_string(),
+        ];
+        let mut workspaces = Workspaces::default();
+        workspaces.workspaces.insert(workspace.uuid, workspace);
+
+        let result = check_repo_paths(&workspaces, &PathRoots::default());
+
+        assert_eq!(result.status, CheckStatus::Warn);
+        assert!(result.message.starts_with("1 of 2 are missing."));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn should_report_fixes() {
+        let results = vec![
+            CheckResult::pass("First", "fine"),
+            CheckResult::fail("Second", "broken", "mend it"),
+        ];
+
+        let report = report(&results);
+
+        assert!(has_failures(&results));
+        assert!(report.contains("[PASS] First: fine"));
+        assert!(report.contains("[FAIL] Second: broken\n       Fix: mend it"));
+    }
+}
@@ -0,0 +1,320 @@
+use std::{
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+use xml::common::Position;
+
+use crate::tr;
+
+/// The failures worth telling apart, each with what's needed to track it down. Functions still
+/// return `anyhow::Result`, so these are found by downcasting.
+#[derive(Debug, Error)]
+pub enum Error {
+    PathNotFound(&'static str),
+    Io {
+        operation: &'static str,
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    Parse {
+        format: &'static str,
+        path: PathBuf,
+        /// Counting from 1, when the parser knows where it stopped.
+        line: Option<u64>,
+        column: Option<u64>,
+        message: String,
+    },
+    ProcessExit {
+        command: String,
+        code: Option<i32>,
+        output: String,
+    },
+    Launch {
+        program: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            Error::PathNotFound(what) => tr!("error-path-not-found", what = *what),
+            Error::Io {
+                operation,
+                path,
+                source,
+            } => tr!(
+                "error-io",
+                operation = *operation,
+                path = path.display().to_string(),
+                error = source.to_string()
+            ),
+            Error::Parse {
+                format,
+                path,
+                line,
+                column,
+                message,
+            } => tr!(
+                "error-parse",
+                path = path.display().to_string(),
+                format = *format,
+                position = position(*line, *column),
+                error = message.as_str()
+            ),
+            Error::ProcessExit {
+                command,
+                code,
+                output,
+            } => tr!(
+                "error-process-exit",
+                command = command.as_str(),
+                code = exit_code(*code),
+                output = output.as_str()
+            ),
+            Error::Launch { program, source } => tr!(
+                "error-launch",
+                program = program.as_str(),
+                error = source.to_string()
+            ),
+        };
+
+        write!(f, "{message}")
+    }
+}
+
+impl Error {
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Error::Io { source, .. } if source.kind() == io::ErrorKind::NotFound)
+    }
+
+    /// Labelled fields for the GUI's details and copied diagnostics.
+    pub fn details(&self) -> Vec<(String, String)> {
+        let fields = match self {
+            Error::PathNotFound(what) => vec![("error-detail-missing", what.to_string())],
+            Error::Io {
+                operation,
+                path,
+                source,
+            } => vec![
+                ("error-detail-operation", operation.to_string()),
+                ("error-detail-path", path.display().to_string()),
+                ("error-detail-kind", format!("{:?}", source.kind())),
+            ],
+            Error::Parse {
+                format,
+                path,
+                line,
+                column,
+                message,
+            } => vec![
+                ("error-detail-format", format.to_string()),
+                ("error-detail-path", path.display().to_string()),
+                (
+                    "error-detail-line",
+                    line.map(|line| line.to_string()).unwrap_or_default(),
+                ),
+                (
+                    "error-detail-column",
+                    column.map(|column| column.to_string()).unwrap_or_default(),
+                ),
+                ("error-detail-message", message.clone()),
+            ],
+            Error::ProcessExit {
+                command,
+                code,
+                output,
+            } => vec![
+                ("error-detail-command", command.clone()),
+                ("error-detail-exit-code", exit_code(*code)),
+                ("error-detail-output", output.clone()),
+            ],
+            Error::Launch { program, source } => vec![
+                ("error-detail-program", program.clone()),
+                ("error-detail-kind", format!("{:?}", source.kind())),
+            ],
+        };
+
+        fields
+            .into_iter()
+            .map(|(key, value)| (tr!(key), value))
+            .collect()
+    }
+
+    pub fn launch(command: &Command, source: io::Error) -> Error {
+        Error::Launch {
+            program: command.get_program().to_string_lossy().into_owned(),
+            source,
+        }
+    }
+
+    /// Fails with the command's exit code and error output unless it succeeded.
+    pub fn check_exit(command: String, output: &Output) -> Result<(), Error> {
+        if output.status.success() {
+            return Ok(());
+        }
+
+        Err(Error::ProcessExit {
+            command,
+            code: output.status.code(),
+            output: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+        })
+    }
+}
+
+/// Whether the error is a file that isn't there, as opposed to one that couldn't be read.
+pub fn is_not_found(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<Error>()
+        .is_some_and(|error| error.is_not_found())
+}
+
+pub fn read_file(path: &Path) -> Result<String, Error> {
+    std::fs::read_to_string(path).map_err(|source| Error::Io {
+        operation: "read",
+        path: path.to_owned(),
+        source,
+    })
+}
+
+pub fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), Error> {
+    std::fs::write(path, contents).map_err(|source| Error::Io {
+        operation: "write",
+        path: path.to_owned(),
+        source,
+    })
+}
+
+pub fn parse_json<T: DeserializeOwned>(path: &Path, contents: &str) -> Result<T, Error> {
+    serde_json::from_str(contents).map_err(|why| Error::Parse {
+        format: "JSON",
+        path: path.to_owned(),
+        line: Some(why.line() as u64).filter(|line| *line > 0),
+        column: Some(why.column() as u64).filter(|_| why.line() > 0),
+        message: why.to_string(),
+    })
+}
+
+pub fn parse_xml<T: DeserializeOwned>(path: &Path, contents: &str) -> Result<T, Error> {
+    serde_xml_rs::from_str(contents).map_err(|why| {
+        let position = match &why {
+            serde_xml_rs::Error::Syntax { source } => Some(source.position()),
+            _ => None,
+        };
+
+        Error::Parse {
+            format: "XML",
+            path: path.to_owned(),
+            line: position.map(|position| position.row + 1),
+            column: position.map(|position| position.column + 1),
+            message: why.to_string(),
+        }
+    })
+}
+
+/// Everything known about a failure, for pasting into a bug report.
+pub fn diagnostics(error: &anyhow::Error) -> String {
+    let mut lines = vec![
+        format!("st-workspaces {}", env!("CARGO_PKG_VERSION")),
+        format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        String::new(),
+        tr!("diagnostics-error", error = error.to_string()),
+    ];
+
+    for cause in error.chain().skip(1) {
+        lines.push(tr!("diagnostics-caused-by", cause = cause.to_string()));
+    }
+
+    if let Some(error) = error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<Error>())
+    {
+        lines.push(String::new());
+        for (label, value) in error.details() {
+            lines.push(format!("{label}: {value}"));
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn position(line: Option<u64>, column: Option<u64>) -> String {
+    match (line, column) {
+        (Some(line), Some(column)) => tr!("error-position-column", line = line, column = column),
+        (Some(line), None) => tr!("error-position-line", line = line),
+        _ => String::new(),
+    }
+}
+
+pub fn exit_code(code: Option<i32>) -> String {
+    match code {
+        Some(code) => tr!("error-exit-code", code = code),
+        None => tr!("error-no-exit-code"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Example {
+        name: String,
+    }
+
+    #[test]
+    fn should_give_json_position() {
+        let contents = "{\n  \"name\": 3\n}";
+        let error = parse_json::<Example>(Path::new("example.json"), contents).unwrap_err();
+
+        let Error::Parse { line, column, .. } = &error else {
+            panic!("Expected a parse error, got {error:?}");
+        };
+        assert_eq!(*line, Some(2));
+        assert!(column.is_some());
+        assert!(error.to_string().contains("example.json as JSON at line 2"));
+    }
+
+    #[test]
+    fn should_give_xml_position() {
+        let contents = "<Example>\n<name>one\n</nam></Example>";
+        let error = parse_xml::<Example>(Path::new("example.xml"), contents).unwrap_err();
+
+        let Error::Parse { line, .. } = &error else {
+            panic!("Expected a parse error, got {error:?}");
+        };
+        assert_eq!(*line, Some(3));
+    }
+
+    #[test]
+    fn should_include_details_in_diagnostics() {
+        let error = anyhow::Error::from(Error::ProcessExit {
+            command: "taskkill /IM SourceTree.exe".to_owned(),
+            code: Some(1),
+            output: "Access denied.".to_owned(),
+        })
+        .context("Couldn't close SourceTree.");
+
+        let diagnostics = diagnostics(&error);
+
+        assert!(diagnostics.contains("Error: Couldn't close SourceTree."));
+        assert!(diagnostics.contains("Caused by: taskkill /IM SourceTree.exe exited with code 1."));
+        assert!(diagnostics.contains("Exit code: code 1"));
+    }
+
+    #[test]
+    fn should_recognise_missing_files() {
+        let error = read_file(Path::new("does-not-exist.json")).unwrap_err();
+        assert!(error.is_not_found());
+    }
+}
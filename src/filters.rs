@@ -0,0 +1,217 @@
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    tr,
+    workspaces::{normalize_tag, Workspace, Workspaces, ARCHIVED_TAG},
+};
+
+/// What narrows the workspace list, in the window and on the command line.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct WorkspaceFilter {
+    /// Searched for in the name, notes, icon and tags.
+    #[serde(default)]
+    pub text: String,
+    /// A workspace needs every one of these.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub show_archived: bool,
+}
+
+impl WorkspaceFilter {
+    pub fn with_tags<'a>(tags: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            tags: tags.into_iter().filter_map(normalize_tag).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Archived workspaces only match when they're asked for, by tag or otherwise.
+    pub fn matches(&self, workspace: &Workspace) -> bool {
+        let shows_archived = self.show_archived || self.tags.iter().any(|tag| tag == ARCHIVED_TAG);
+        (shows_archived || !workspace.is_archived())
+            && self.tags.iter().all(|tag| workspace.has_tag(tag))
+            && workspace.matches_filter(&self.text)
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|selected| selected == tag)
+    }
+
+    pub fn toggle_tag(&mut self, tag: &str) {
+        match self.has_tag(tag) {
+            true => self.tags.retain(|selected| selected != tag),
+            false => self.tags.push(tag.to_owned()),
+        }
+    }
+}
+
+/// A filter kept under a name, for going back to it from the list.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FilterPreset {
+    pub name: String,
+    pub filter: WorkspaceFilter,
+}
+
+/// Saves the filter under the name, replacing any preset already called that.
+pub fn save_preset(presets: &mut Vec<FilterPreset>, name: &str, filter: &WorkspaceFilter) {
+    let name = name.trim();
+    let preset = FilterPreset {
+        name: name.to_owned(),
+        filter: filter.clone(),
+    };
+
+    match presets.iter_mut().find(|preset| preset.name == name) {
+        Some(existing) => *existing = preset,
+        None => presets.push(preset),
+    }
+}
+
+/// Every workspace the filter matches, by name.
+pub fn matching_workspaces<'a>(
+    workspaces: &'a Workspaces,
+    filter: &WorkspaceFilter,
+) -> Vec<&'a Workspace> {
+    let mut matching: Vec<_> = workspaces
+        .workspaces
+        .values()
+        .filter(|workspace| filter.matches(workspace))
+        .collect();
+    matching.sort();
+    matching
+}
+
+/// The one workspace the filter matches with the given name, or with any name when there isn't
+/// one. An exact name wins over others that only contain it.
+pub fn pick_workspace<'a>(
+    workspaces: &'a Workspaces,
+    name: Option<&str>,
+    filter: &WorkspaceFilter,
+) -> anyhow::Result<&'a Workspace> {
+    let mut candidates = matching_workspaces(workspaces, filter);
+    if let Some(name) = name {
+        let name = name.to_lowercase();
+        let exact: Vec<_> = candidates
+            .iter()
+            .copied()
+            .filter(|workspace| workspace.name.to_lowercase() == name)
+            .collect();
+        candidates = match exact.is_empty() {
+            true => candidates
+                .into_iter()
+                .filter(|workspace| workspace.name.to_lowercase().contains(&name))
+                .collect(),
+            false => exact,
+        };
+    }
+
+    match candidates.as_slice() {
+        [workspace] => Ok(workspace),
+        [] => bail!(tr!("cli-no-match")),
+        _ => {
+            let names: Vec<_> = candidates
+                .iter()
+                .map(|workspace| workspace.name.as_str())
+                .collect();
+            bail!(tr!(
+                "cli-several-match",
+                count = candidates.len(),
+                names = names.join(", ")
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn create_workspaces() -> Workspaces {
+        let mut workspaces = Workspaces::default();
+        for (name, tags) in [
+            ("Billing", vec!["client-a", "hotfix"]),
+            ("Billing Reports", vec!["client-a"]),
+            ("Old Billing", vec!["client-a", ARCHIVED_TAG]),
+            ("Website", vec!["client-b"]),
+        ] {
+            let mut workspace = Workspace::new(name, Uuid::new_v4());
+            for tag in tags {
+                workspace.add_tag(tag);
+            }
+            workspaces.workspaces.insert(workspace.uuid, workspace);
+        }
+        workspaces
+    }
+
+    fn names(workspaces: Vec<&Workspace>) -> Vec<&str> {
+        workspaces
+            .into_iter()
+            .map(|workspace| workspace.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn should_hide_archived_unless_asked() {
+        let workspaces = create_workspaces();
+
+        let filter = WorkspaceFilter::with_tags(["Client-A"]);
+        assert_eq!(
+            names(matching_workspaces(&workspaces, &filter)),
+            vec!["Billing", "Billing Reports"]
+        );
+
+        let filter = WorkspaceFilter::with_tags(["client-a", ARCHIVED_TAG]);
+        assert_eq!(
+            names(matching_workspaces(&workspaces, &filter)),
+            vec!["Old Billing"]
+        );
+
+        let filter = WorkspaceFilter {
+            show_archived: true,
+            ..Default::default()
+        };
+        assert_eq!(matching_workspaces(&workspaces, &filter).len(), 4);
+    }
+
+    #[test]
+    fn should_pick_one_workspace() {
+        let workspaces = create_workspaces();
+        let filter = WorkspaceFilter::with_tags(["client-a"]);
+
+        assert_eq!(
+            pick_workspace(&workspaces, Some("billing"), &filter)
+                .unwrap()
+                .name,
+            "Billing"
+        );
+        assert_eq!(
+            pick_workspace(&workspaces, Some("report"), &filter)
+                .unwrap()
+                .name,
+            "Billing Reports"
+        );
+        assert!(pick_workspace(&workspaces, None, &filter).is_err());
+        assert!(pick_workspace(&workspaces, Some("website"), &filter).is_err());
+    }
+
+    #[test]
+    fn should_replace_presets_by_name() {
+        let mut presets = Vec::new();
+        save_preset(
+            &mut presets,
+            "Client A",
+            &WorkspaceFilter::with_tags(["client-a"]),
+        );
+        save_preset(
+            &mut presets,
+            " Client A ",
+            &WorkspaceFilter::with_tags(["hotfix"]),
+        );
+
+        assert_eq!(presets.len(), 1);
+        assert_eq!(presets[0].filter.tags, vec!["hotfix"]);
+    }
+}
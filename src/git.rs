@@ -0,0 +1,29 @@
+use std::{path::Path, process::Command};
+
+use crate::error::Error;
+
+/// Runs `git` inside the given directory, returning its standard output. Git is never allowed to
+/// prompt, since nobody would see it.
+pub fn run_git(repo_path: &Path, args: &[&str]) -> anyhow::Result<String> {
+    let mut command = Command::new("git");
+    command
+        .arg("-C")
+        .arg(repo_path)
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0");
+    let output = command
+        .output()
+        .map_err(|why| Error::launch(&command, why))?;
+
+    Error::check_exit(
+        format!("git {} in {}", args.join(" "), repo_path.display()),
+        &output,
+    )?;
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Whether the directory is the top of a git working copy.
+pub fn is_git_work_tree(path: &Path) -> bool {
+    path.join(".git").exists()
+}
@@ -0,0 +1,360 @@
+use std::{
+    collections::VecDeque,
+    path::Path,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use crate::{git::run_git, tr};
+
+/// How many repos are worked on at once unless told otherwise.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Something to do in every repo of a workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitOperation {
+    Fetch,
+    Pull,
+    Status,
+    /// Checks out the branch in every repo that has it, locally or on a remote.
+    Checkout(String),
+    /// Creates and checks out the same new branch in every repo.
+    CreateBranch(String),
+}
+
+impl std::fmt::Display for GitOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GitOperation::Fetch => write!(f, "{}", tr!("git-fetch")),
+            GitOperation::Pull => write!(f, "{}", tr!("git-pull")),
+            GitOperation::Status => write!(f, "{}", tr!("git-status")),
+            GitOperation::Checkout(branch) => {
+                write!(f, "{}", tr!("git-checkout", branch = branch.as_str()))
+            }
+            GitOperation::CreateBranch(branch) => {
+                write!(f, "{}", tr!("git-create-branch", branch = branch.as_str()))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoOutcome {
+    Succeeded,
+    /// Nothing to do in this repo, like checking out a branch it doesn't have.
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoResult {
+    pub repo_path: String,
+    pub outcome: RepoOutcome,
+    /// One line for the results table.
+    pub summary: String,
+    /// Everything git said, for when the summary isn't enough.
+    pub output: String,
+}
+
+impl RepoResult {
+    fn new(repo_path: &str, outcome: RepoOutcome, summary: &str, output: &str) -> Self {
+        Self {
+            repo_path: repo_path.to_owned(),
+            outcome,
+            summary: summary.to_owned(),
+            output: output.to_owned(),
+        }
+    }
+}
+
+/// Runs the operation in one repo.
+pub fn run_operation(repo_path: &str, operation: &GitOperation) -> RepoResult {
+    let path = Path::new(repo_path);
+    if !path.is_dir() {
+        return RepoResult::new(repo_path, RepoOutcome::Failed, &tr!("repo-not-found"), "");
+    }
+
+    let result = match operation {
+        GitOperation::Fetch => run_git(path, &["fetch", "--all", "--prune"])
+            .map(|output| (RepoOutcome::Succeeded, tr!("repo-fetched"), output)),
+        GitOperation::Pull => run_git(path, &["pull", "--ff-only"]).map(|output| {
+            let summary = match output.lines().last() {
+                Some(line) => line.trim().to_owned(),
+                None => tr!("repo-pulled"),
+            };
+            (RepoOutcome::Succeeded, summary, output)
+        }),
+        GitOperation::Status => run_git(path, &["status", "--short", "--branch"])
+            .map(|output| (RepoOutcome::Succeeded, summarize_status(&output), output)),
+        GitOperation::Checkout(branch) => checkout(path, branch),
+        GitOperation::CreateBranch(branch) => create_branch(path, branch),
+    };
+
+    match result {
+        Ok((outcome, summary, output)) => RepoResult::new(repo_path, outcome, &summary, &output),
+        Err(why) => {
+            let output = why.to_string();
+            let summary = output.lines().last().unwrap_or_default().to_owned();
+            RepoResult::new(repo_path, RepoOutcome::Failed, &summary, &output)
+        }
+    }
+}
+
+/// Runs the operation across the repos with at most `concurrency` running at once. Results are in
+/// the same order as the repos.
+pub fn run_batch(
+    repo_paths: &[String],
+    operation: &GitOperation,
+    concurrency: usize,
+) -> Vec<RepoResult> {
+    let (sender, receiver) = mpsc::channel();
+    start_workers(repo_paths, operation, concurrency, sender);
+
+    let mut results: Vec<Option<RepoResult>> = vec![None; repo_paths.len()];
+    for (index, result) in receiver.iter() {
+        results[index] = Some(result);
+    }
+    results.into_iter().flatten().collect()
+}
+
+/// Starts the operation on background threads. Each result is sent with the index of its repo as
+/// soon as it's done.
+pub fn spawn_batch(
+    repo_paths: &[String],
+    operation: &GitOperation,
+    concurrency: usize,
+) -> Receiver<(usize, RepoResult)> {
+    let (sender, receiver) = mpsc::channel();
+    start_workers(repo_paths, operation, concurrency, sender);
+    receiver
+}
+
+fn start_workers(
+    repo_paths: &[String],
+    operation: &GitOperation,
+    concurrency: usize,
+    sender: Sender<(usize, RepoResult)>,
+) {
+    let jobs: VecDeque<(usize, String)> = repo_paths.iter().cloned().enumerate().collect();
+    let worker_count = concurrency.clamp(1, jobs.len().max(1));
+    let jobs = Arc::new(Mutex::new(jobs));
+
+    for _ in 0..worker_count {
+        let jobs = jobs.clone();
+        let sender = sender.clone();
+        let operation = operation.clone();
+        thread::spawn(move || loop {
+            let Some((index, repo_path)) = jobs.lock().unwrap().pop_front() else {
+                break;
+            };
+
+            let result = run_operation(&repo_path, &operation);
+            if sender.send((index, result)).is_err() {
+                break;
+            }
+        });
+    }
+}
+
+fn checkout(path: &Path, branch: &str) -> anyhow::Result<(RepoOutcome, String, String)> {
+    check_branch_name(path, branch)?;
+    if current_branch(path).as_deref() == Some(branch) {
+        return Ok((
+            RepoOutcome::Skipped,
+            tr!("repo-already-on-branch"),
+            String::new(),
+        ));
+    }
+
+    if !has_local_branch(path, branch) && !has_remote_branch(path, branch)? {
+        return Ok((
+            RepoOutcome::Skipped,
+            tr!("repo-no-such-branch"),
+            String::new(),
+        ));
+    }
+
+    // Checking out a remote-only branch makes a local tracking branch for it.
+    run_git(path, &["checkout", "--quiet", branch, "--"]).map(|output| {
+        (
+            RepoOutcome::Succeeded,
+            tr!("repo-checked-out", branch = branch),
+            output,
+        )
+    })
+}
+
+fn create_branch(path: &Path, branch: &str) -> anyhow::Result<(RepoOutcome, String, String)> {
+    check_branch_name(path, branch)?;
+    if has_local_branch(path, branch) {
+        return Ok((
+            RepoOutcome::Skipped,
+            tr!("repo-branch-exists"),
+            String::new(),
+        ));
+    }
+
+    run_git(path, &["checkout", "--quiet", "-b", branch]).map(|output| {
+        (
+            RepoOutcome::Succeeded,
+            tr!("repo-created-branch", branch = branch),
+            output,
+        )
+    })
+}
+
+/// Refuses names git wouldn't take for a branch, such as ones starting with `-` that would be read
+/// as options.
+pub fn check_branch_name(path: &Path, branch: &str) -> anyhow::Result<()> {
+    run_git(path, &["check-ref-format", "--branch", branch])?;
+    Ok(())
+}
+
+/// The checked out branch, or `None` when the head is detached.
+pub fn current_branch(path: &Path) -> Option<String> {
+    run_git(path, &["symbolic-ref", "--quiet", "--short", "HEAD"])
+        .ok()
+        .map(|output| output.trim().to_owned())
+}
+
+fn has_local_branch(path: &Path, branch: &str) -> bool {
+    let reference = format!("refs/heads/{branch}");
+    run_git(path, &["show-ref", "--verify", "--quiet", &reference]).is_ok()
+}
+
+fn has_remote_branch(path: &Path, branch: &str) -> anyhow::Result<bool> {
+    let pattern = format!("refs/remotes/*/{branch}");
+    let output = run_git(path, &["for-each-ref", "--format=%(refname)", &pattern])?;
+    Ok(!output.trim().is_empty())
+}
+
+fn summarize_status(output: &str) -> String {
+    let mut lines = output.lines();
+    let branch = lines
+        .next()
+        .and_then(|line| line.strip_prefix("## "))
+        .unwrap_or_default();
+    let changes = lines.filter(|line| !line.trim().is_empty()).count();
+
+    tr!("repo-status", branch = branch, count = changes)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::path::PathBuf;
+
+    use uuid::Uuid;
+
+    use super::*;
+
+    /// Makes a repo with one commit on `main`, for testing against.
+    pub(crate) fn create_test_repo(name: &str) -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("st-workspaces-git-{}", Uuid::new_v4()))
+            .join(name);
+        std::fs::create_dir_all(&path).unwrap();
+        run_git(&path, &["init", "--quiet", "--initial-branch=main"]).unwrap();
+        run_git(&path, &["config", "user.name", "Test"]).unwrap();
+        run_git(&path, &["config", "user.email", "test@example.com"]).unwrap();
+        std::fs::write(path.join("README.md"), "test\n").unwrap();
+        run_git(&path, &["add", "README.md"]).unwrap();
+        run_git(&path, &["commit", "--quiet", "-m", "Initial commit"]).unwrap();
+        path
+    }
+
+    fn as_string(path: &Path) -> String {
+        path.display().to_string()
+    }
+
+    #[test]
+    fn should_checkout_branch_where_it_exists() {
+        let with_branch = create_test_repo("with-branch");
+        let without_branch = create_test_repo("without-branch");
+        run_git(&with_branch, &["branch", "hotfix"]).unwrap();
+
+        let repo_paths = vec![as_string(&with_branch), as_string(&without_branch)];
+        let operation = GitOperation::Checkout("hotfix".to_owned());
+        let results = run_batch(&repo_paths, &operation, 2);
+
+        assert_eq!(results[0].outcome, RepoOutcome::Succeeded);
+        assert_eq!(results[1].outcome, RepoOutcome::Skipped);
+        assert_eq!(current_branch(&with_branch).as_deref(), Some("hotfix"));
+        assert_eq!(current_branch(&without_branch).as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn should_create_branch_everywhere() {
+        let repo_paths: Vec<_> = (0..3)
+            .map(|index| as_string(&create_test_repo(&format!("repo-{index}"))))
+            .collect();
+
+        let operation = GitOperation::CreateBranch("feature/thing".to_owned());
+        let results = run_batch(&repo_paths, &operation, 2);
+        assert!(results
+            .iter()
+            .all(|result| result.outcome == RepoOutcome::Succeeded));
+
+        let results = run_batch(&repo_paths, &operation, 2);
+        assert!(results
+            .iter()
+            .all(|result| result.outcome == RepoOutcome::Skipped));
+    }
+
+    #[test]
+    fn should_refuse_branch_names_like_options() {
+        let repo = create_test_repo("options");
+        let repo_paths = vec![as_string(&repo)];
+
+        for branch in ["--orphan=main", "-f", "bad..name"] {
+            let operation = GitOperation::CreateBranch(branch.to_owned());
+            assert_eq!(
+                run_batch(&repo_paths, &operation, 1)[0].outcome,
+                RepoOutcome::Failed
+            );
+            let operation = GitOperation::Checkout(branch.to_owned());
+            assert_eq!(
+                run_batch(&repo_paths, &operation, 1)[0].outcome,
+                RepoOutcome::Failed
+            );
+        }
+        assert_eq!(current_branch(&repo).as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn should_fetch_and_checkout_remote_branch() {
+        let upstream = create_test_repo("upstream");
+        run_git(&upstream, &["branch", "release"]).unwrap();
+        let clone = upstream.parent().unwrap().join("clone");
+        run_git(
+            upstream.parent().unwrap(),
+            &["clone", "--quiet", &as_string(&upstream), "clone"],
+        )
+        .unwrap();
+
+        let repo_paths = vec![as_string(&clone)];
+        let results = run_batch(&repo_paths, &GitOperation::Fetch, 1);
+        assert_eq!(results[0].outcome, RepoOutcome::Succeeded);
+
+        let results = run_batch(
+            &repo_paths,
+            &GitOperation::Checkout("release".to_owned()),
+            1,
+        );
+        assert_eq!(results[0].outcome, RepoOutcome::Succeeded);
+        assert_eq!(current_branch(&clone).as_deref(), Some("release"));
+    }
+
+    #[test]
+    fn should_report_status_and_missing_repos() {
+        let repo = create_test_repo("status");
+        std::fs::write(repo.join("new.txt"), "new\n").unwrap();
+
+        let repo_paths = vec![as_string(&repo), as_string(&repo.join("missing"))];
+        let results = run_batch(&repo_paths, &GitOperation::Status, 4);
+        assert_eq!(results[0].summary, "main, 1 change");
+        assert_eq!(results[1].outcome, RepoOutcome::Failed);
+    }
+}
@@ -0,0 +1,249 @@
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::Duration,
+};
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    error::exit_code,
+    process::{run_with_timeout, shell_command, spawn_detached},
+    roots::PathRoots,
+    tr,
+};
+
+pub const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    AfterOpen,
+    BeforeClose,
+}
+
+impl std::fmt::Display for HookEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HookEvent::AfterOpen => write!(f, "{}", tr!("hook-after-open")),
+            HookEvent::BeforeClose => write!(f, "{}", tr!("hook-before-close")),
+        }
+    }
+}
+
+/// A command run when a workspace is opened or closed, like starting a docker-compose stack or
+/// opening an IDE.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceHook {
+    pub name: String,
+    pub event: HookEvent,
+    /// A command line run through the platform's shell.
+    pub command: String,
+    /// Where the command runs. May start with a path root.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub working_dir: String,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    /// Start the command and move on without waiting, for things that keep running like an IDE.
+    #[serde(default)]
+    pub detached: bool,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl WorkspaceHook {
+    pub fn new(event: HookEvent) -> Self {
+        Self {
+            name: tr!("hook-new-name"),
+            event,
+            command: String::new(),
+            working_dir: String::new(),
+            enabled: true,
+            detached: false,
+            timeout_secs: DEFAULT_HOOK_TIMEOUT_SECS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookResult {
+    pub name: String,
+    pub succeeded: bool,
+    pub summary: String,
+}
+
+/// Runs one hook, logging what it printed.
+pub fn run_hook(
+    hook: &WorkspaceHook,
+    roots: &PathRoots,
+    env: &BTreeMap<String, String>,
+) -> HookResult {
+    let result = |succeeded, summary: String| HookResult {
+        name: hook.name.clone(),
+        succeeded,
+        summary,
+    };
+
+    let working_dir = roots.expand(&hook.working_dir);
+    let working_dir = (!working_dir.is_empty()).then_some(Path::new(&working_dir));
+    let mut command = shell_command(&hook.command, working_dir, env);
+    info!("Running {} hook '{}'...", hook.event, hook.name);
+
+    if hook.detached {
+        return match spawn_detached(&mut command) {
+            Ok(_) => result(true, tr!("hook-started")),
+            Err(why) => {
+                warn!("Couldn't start hook '{}'. '{}'", hook.name, why);
+                result(false, tr!("hook-error-starting", error = why.to_string()))
+            }
+        };
+    }
+
+    match run_with_timeout(&mut command, Duration::from_secs(hook.timeout_secs)) {
+        Ok(output) => {
+            if !output.output.trim().is_empty() {
+                info!("Hook '{}' output:\n{}", hook.name, output.output.trim_end());
+            }
+
+            if output.timed_out {
+                warn!("Hook '{}' timed out.", hook.name);
+                result(false, tr!("hook-timed-out", seconds = hook.timeout_secs))
+            } else if output.succeeded() {
+                result(true, tr!("hook-finished"))
+            } else {
+                warn!("Hook '{}' failed with {:?}.", hook.name, output.code);
+                result(false, tr!("hook-exited", code = exit_code(output.code)))
+            }
+        }
+        Err(why) => {
+            warn!("Couldn't run hook '{}'. '{}'", hook.name, why);
+            result(false, tr!("hook-error-running", error = why.to_string()))
+        }
+    }
+}
+
+/// Runs the enabled hooks for an event in order. A failing hook doesn't stop the ones after it.
+pub fn run_hooks(
+    hooks: &[WorkspaceHook],
+    event: HookEvent,
+    roots: &PathRoots,
+    env: &BTreeMap<String, String>,
+) -> Vec<HookResult> {
+    hooks
+        .iter()
+        .filter(|hook| hook.enabled && hook.event == event)
+        .map(|hook| run_hook(hook, roots, env))
+        .collect()
+}
+
+/// Runs the hooks on a worker thread, so a slow one doesn't hold anything up. Their results are
+/// sent together once the last one is done.
+pub fn spawn_hooks(
+    hooks: Vec<WorkspaceHook>,
+    event: HookEvent,
+    roots: PathRoots,
+    env: BTreeMap<String, String>,
+) -> Receiver<Vec<HookResult>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(run_hooks(&hooks, event, &roots, &env));
+    });
+    receiver
+}
+
+/// Whether any hook would run for the event.
+pub fn has_hooks_for(hooks: &[WorkspaceHook], event: HookEvent) -> bool {
+    hooks.iter().any(|hook| hook.enabled && hook.event == event)
+}
+
+/// Identifies the hooks and the environment they run with, so that trusting them lasts only until
+/// a command or variable changes.
+pub fn hooks_fingerprint(hooks: &[WorkspaceHook], env: &BTreeMap<String, String>) -> Uuid {
+    let contents = serde_json::to_string(&(hooks, env)).unwrap_or_default();
+    Uuid::new_v5(&Uuid::NAMESPACE_OID, contents.as_bytes())
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+fn default_timeout_secs() -> u64 {
+    DEFAULT_HOOK_TIMEOUT_SECS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_hook(name: &str, event: HookEvent, command: &str) -> WorkspaceHook {
+        WorkspaceHook {
+            name: name.to_owned(),
+            command: command.to_owned(),
+            ..WorkspaceHook::new(event)
+        }
+    }
+
+    #[test]
+    fn should_run_enabled_hooks_for_event() {
+        let mut disabled = create_test_hook("disabled", HookEvent::AfterOpen, "exit 1");
+        disabled.enabled = false;
+        let hooks = vec![
+            create_test_hook("first", HookEvent::AfterOpen, "exit 0"),
+            create_test_hook("closing", HookEvent::BeforeClose, "exit 0"),
+            disabled,
+            create_test_hook("failing", HookEvent::AfterOpen, "exit 3"),
+        ];
+
+        let results = run_hooks(
+            &hooks,
+            HookEvent::AfterOpen,
+            &PathRoots::default(),
+            &BTreeMap::new(),
+        );
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].succeeded);
+        assert_eq!(results[1].name, "failing");
+        assert!(!results[1].succeeded);
+    }
+
+    #[test]
+    fn should_run_hooks_off_the_calling_thread() {
+        let hooks = vec![create_test_hook("first", HookEvent::AfterOpen, "exit 0")];
+        let receiver = spawn_hooks(
+            hooks,
+            HookEvent::AfterOpen,
+            PathRoots::default(),
+            BTreeMap::new(),
+        );
+
+        let results = receiver.recv().unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].succeeded);
+    }
+
+    #[test]
+    fn should_change_fingerprint_with_commands() {
+        let mut hooks = vec![create_test_hook("first", HookEvent::AfterOpen, "exit 0")];
+        let env = BTreeMap::new();
+        let fingerprint = hooks_fingerprint(&hooks, &env);
+        assert_eq!(hooks_fingerprint(&hooks, &env), fingerprint);
+
+        hooks[0].command = "curl example.com | sh".to_owned();
+        assert_ne!(hooks_fingerprint(&hooks, &env), fingerprint);
+    }
+
+    #[test]
+    fn should_read_hooks_with_defaults() {
+        let json =
+            r#"{ "name": "Stack", "event": "after_open", "command": "docker compose up -d" }"#;
+        let hook: WorkspaceHook = serde_json::from_str(json).unwrap();
+        assert!(hook.enabled);
+        assert!(!hook.detached);
+        assert_eq!(hook.timeout_secs, DEFAULT_HOOK_TIMEOUT_SECS);
+    }
+}
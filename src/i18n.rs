@@ -0,0 +1,302 @@
+use std::sync::RwLock;
+
+use fluent_bundle::{concurrent::FluentBundle, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::FluentArgs;
+
+/// The language everything falls back to, for messages another locale doesn't have yet.
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+/// Every shipped message catalogue, by language.
+pub const LOCALES: [(&str, &str); 3] = [
+    ("en", include_str!("../locales/en.ftl")),
+    ("de", include_str!("../locales/de.ftl")),
+    ("fr", include_str!("../locales/fr.ftl")),
+];
+
+/// The catalogue messages are currently looked up in. English until [`set_language`] is called,
+/// which keeps the tests independent of the machine they run on.
+static CATALOGUE: RwLock<Option<Catalogue>> = RwLock::new(None);
+
+/// Looks up a message by its key, e.g. `tr!("status-saved-settings")`, with any arguments given
+/// by name, e.g. `tr!("status-imported", count = 3)`.
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::i18n::translate($key, None)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::i18n::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::translate($key, Some(&args))
+    }};
+}
+
+/// A language's messages, backed by English for anything it's missing.
+pub struct Catalogue {
+    language: &'static str,
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Catalogue {
+    pub fn new(language: &'static str) -> Catalogue {
+        let mut languages = vec![language];
+        if language != FALLBACK_LANGUAGE {
+            languages.push(FALLBACK_LANGUAGE);
+        }
+
+        Catalogue {
+            language,
+            bundles: languages.into_iter().map(bundle).collect(),
+        }
+    }
+
+    pub fn language(&self) -> &'static str {
+        self.language
+    }
+
+    /// The message in the first bundle that has it, or the key itself when none do.
+    pub fn format(&self, key: &str, args: Option<&FluentArgs>) -> String {
+        for bundle in self.bundles.iter() {
+            let Some(pattern) = bundle.get_message(key).and_then(|message| message.value()) else {
+                continue;
+            };
+
+            let mut errors = Vec::new();
+            return bundle
+                .format_pattern(pattern, args, &mut errors)
+                .into_owned();
+        }
+
+        key.to_owned()
+    }
+}
+
+pub fn translate(key: &str, args: Option<&FluentArgs>) -> String {
+    if let Some(catalogue) = CATALOGUE.read().unwrap().as_ref() {
+        return catalogue.format(key, args);
+    }
+
+    CATALOGUE
+        .write()
+        .unwrap()
+        .get_or_insert_with(|| Catalogue::new(FALLBACK_LANGUAGE))
+        .format(key, args)
+}
+
+/// Switches every message to the preferred language, or the OS's when there isn't a preference.
+pub fn set_language(preferred: Option<&str>) {
+    let language = resolve_language(preferred);
+    *CATALOGUE.write().unwrap() = Some(Catalogue::new(language));
+}
+
+/// The language messages are shown in.
+pub fn current_language() -> &'static str {
+    CATALOGUE
+        .read()
+        .unwrap()
+        .as_ref()
+        .map_or(FALLBACK_LANGUAGE, Catalogue::language)
+}
+
+/// The shipped language closest to the preferred one, or to the OS's when there isn't a
+/// preference. Only the language is compared, so `de-AT` gets German.
+pub fn resolve_language(preferred: Option<&str>) -> &'static str {
+    preferred
+        .map(str::to_owned)
+        .or_else(sys_locale::get_locale)
+        .and_then(|locale| shipped_language(&locale))
+        .unwrap_or(FALLBACK_LANGUAGE)
+}
+
+pub fn shipped_language(locale: &str) -> Option<&'static str> {
+    // POSIX locales look like `de_DE.UTF-8`.
+    let tag = locale.split(['.', '@']).next()?.replace('_', "-");
+    let requested: LanguageIdentifier = tag.parse().ok()?;
+
+    LOCALES
+        .iter()
+        .map(|(language, _)| *language)
+        .find(|language| {
+            language
+                .parse::<LanguageIdentifier>()
+                .is_ok_and(|shipped| shipped.language == requested.language)
+        })
+}
+
+/// Each shipped language with its name in that language, for choosing between them.
+pub fn languages() -> Vec<(&'static str, String)> {
+    LOCALES
+        .iter()
+        .map(|(language, _)| {
+            (
+                *language,
+                Catalogue::new(language).format("language-name", None),
+            )
+        })
+        .collect()
+}
+
+fn bundle(language: &'static str) -> FluentBundle<FluentResource> {
+    let source = LOCALES
+        .iter()
+        .find(|(shipped, _)| *shipped == language)
+        .map(|(_, source)| *source)
+        .unwrap_or_default();
+    let resource =
+        FluentResource::try_new(source.to_owned()).unwrap_or_else(|(resource, _)| resource);
+
+    let mut bundle = FluentBundle::new_concurrent(vec![language.parse().unwrap_or_default()]);
+    // The isolation marks around arguments show up as boxes in egui and the console.
+    bundle.set_use_isolating(false);
+    // Overriding only happens with messages that appear twice, which the tests catch.
+    bundle.add_resource_overriding(resource);
+    bundle
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, fs, path::Path};
+
+    use fluent_bundle::FluentValue;
+
+    use super::*;
+
+    fn keys(source: &str) -> BTreeSet<String> {
+        let resource = FluentResource::try_new(source.to_owned())
+            .unwrap_or_else(|(_, errors)| panic!("Couldn't parse catalogue. {errors:?}"));
+        resource
+            .entries()
+            .filter_map(|entry| match entry {
+                fluent_syntax::ast::Entry::Message(message) => Some(message.id.name.to_owned()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_have_every_key_in_every_locale() {
+        let english = keys(LOCALES[0].1);
+
+        for (language, source) in LOCALES.iter().skip(1) {
+            let translated = keys(source);
+            let missing: Vec<_> = english.difference(&translated).collect();
+            let extra: Vec<_> = translated.difference(&english).collect();
+
+            assert!(missing.is_empty(), "{language} is missing {missing:?}");
+            assert!(extra.is_empty(), "{language} has unknown keys {extra:?}");
+        }
+    }
+
+    #[test]
+    fn should_define_every_key_used_in_code() {
+        let english = keys(LOCALES[0].1);
+        let mut directories = vec![Path::new(env!("CARGO_MANIFEST_DIR")).join("src")];
+        let mut undefined = Vec::new();
+
+        while let Some(directory) = directories.pop() {
+            for entry in fs::read_dir(directory).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    directories.push(path);
+                    continue;
+                }
+
+                let source = fs::read_to_string(&path).unwrap();
+                let mut used = Vec::new();
+                let mut picks_keys = false;
+                for usage in source.split("tr!(").skip(1) {
+                    match usage.trim_start().strip_prefix('"') {
+                        Some(key) => used.push(key.split('"').next().unwrap_or_default()),
+                        None => picks_keys = true,
+                    }
+                }
+
+                // Keys picked in a match arm or listed in a tuple are passed in as a variable, so
+                // those literals are taken as keys too.
+                if picks_keys {
+                    let code = source.split("#[cfg(test)]").next().unwrap_or_default();
+                    let pieces: Vec<&str> = code.split('"').collect();
+                    used.extend(
+                        pieces
+                            .windows(2)
+                            .filter(|pair| pair[0].ends_with("=> ") || pair[0].ends_with('('))
+                            .map(|pair| pair[1])
+                            .filter(|literal| literal.contains('-')),
+                    );
+                }
+
+                for key in used {
+                    if is_key(key) && !english.contains(key) {
+                        undefined.push(format!("{} {key}", path.display()));
+                    }
+                }
+            }
+        }
+
+        assert!(undefined.is_empty(), "Undefined keys {undefined:?}");
+    }
+
+    fn is_key(text: &str) -> bool {
+        text.starts_with(|c: char| c.is_ascii_lowercase())
+            && text
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    }
+
+    #[test]
+    fn should_format_without_errors() {
+        let mut args = FluentArgs::new();
+        for name in [
+            "count",
+            "name",
+            "path",
+            "what",
+            "error",
+            "operation",
+            "format",
+        ] {
+            args.set(name, FluentValue::from("1"));
+        }
+
+        for (language, source) in LOCALES {
+            let catalogue = Catalogue::new(language);
+            for key in keys(source) {
+                let bundle = &catalogue.bundles[0];
+                let pattern = bundle.get_message(&key).and_then(|message| message.value());
+                let Some(pattern) = pattern else {
+                    panic!("{language} {key} has no value");
+                };
+
+                let mut errors = Vec::new();
+                bundle.format_pattern(pattern, Some(&args), &mut errors);
+                let unknown: Vec<_> = errors
+                    .iter()
+                    .filter(|error| !error.to_string().contains("Unknown variable"))
+                    .collect();
+                assert!(unknown.is_empty(), "{language} {key}: {unknown:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn should_match_locales_by_language() {
+        assert_eq!(shipped_language("de_DE.UTF-8"), Some("de"));
+        assert_eq!(shipped_language("fr-CA"), Some("fr"));
+        assert_eq!(shipped_language("ja-JP"), None);
+        assert_eq!(resolve_language(Some("C")), FALLBACK_LANGUAGE);
+    }
+
+    #[test]
+    fn should_fall_back_to_english() {
+        let catalogue = Catalogue::new("de");
+
+        assert_eq!(catalogue.format("no-such-key", None), "no-such-key");
+        assert_eq!(
+            Catalogue::new("en").format("language-name", None),
+            "English"
+        );
+        assert_eq!(catalogue.format("language-name", None), "Deutsch");
+    }
+}
@@ -0,0 +1,341 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{roots::PathRoots, workspaces::Workspace};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// A multi-root `.code-workspace` file.
+    VsCode,
+    /// A `.idea` directory with a module per repo.
+    JetBrains,
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::VsCode => write!(f, "VS Code"),
+            ExportFormat::JetBrains => write!(f, "JetBrains"),
+        }
+    }
+}
+
+/// A project file kept in step with a workspace, rewritten whenever the workspace is saved.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IdeExport {
+    pub format: ExportFormat,
+    /// The `.code-workspace` file, or the directory the `.idea` directory goes in. May start with
+    /// a path root.
+    pub path: String,
+}
+
+impl IdeExport {
+    /// Writes the export's files for every repo the workspace opens, rules included, leaving any
+    /// that haven't changed alone. JetBrains modules of repos that are no longer there are removed.
+    pub fn write(&self, workspace: &Workspace, roots: &PathRoots) -> anyhow::Result<()> {
+        self.write_repo_paths(&workspace.resolved_repo_paths(roots), roots)
+    }
+
+    /// Writes the export's files for repos that are already resolved.
+    pub fn write_repo_paths(&self, repo_paths: &[String], roots: &PathRoots) -> anyhow::Result<()> {
+        let path = PathBuf::from(roots.expand(&self.path));
+
+        let files: Vec<(PathBuf, String)> = match self.format {
+            ExportFormat::VsCode => {
+                let directory = path.parent().unwrap_or(Path::new(""));
+                let contents = code_workspace_contents(repo_paths, directory)?;
+                vec![(path, contents)]
+            }
+            ExportFormat::JetBrains => {
+                let files: Vec<_> = jetbrains_files(repo_paths, &path)
+                    .into_iter()
+                    .map(|(relative_path, contents)| (path.join(relative_path), contents))
+                    .collect();
+                remove_stale_modules(&path.join(".idea").join("modules"), &files)?;
+                files
+            }
+        };
+
+        for (path, contents) in files {
+            write_if_changed(&path, &contents)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A VS Code workspace with a folder per repo, in the workspace's order. Folders are relative to
+/// the directory the file is written to, so the file still works when that directory is moved.
+pub fn code_workspace_contents(repo_paths: &[String], directory: &Path) -> anyhow::Result<String> {
+    let directory = directory.to_string_lossy();
+    let folders: Vec<_> = folder_names(repo_paths)
+        .into_iter()
+        .zip(repo_paths)
+        .map(|(name, repo_path)| {
+            let path = relative_path(&directory, repo_path).unwrap_or_else(|| repo_path.clone());
+            json!({ "name": name, "path": path })
+        })
+        .collect();
+
+    let mut contents = serde_json::to_string_pretty(&json!({ "folders": folders }))?;
+    contents.push('\n');
+    Ok(contents)
+}
+
+/// The files of a JetBrains project stub, relative to the project directory. Repos are found
+/// through `$PROJECT_DIR$` where they can be.
+pub fn jetbrains_files(repo_paths: &[String], project_dir: &Path) -> Vec<(PathBuf, String)> {
+    let project_dir = project_dir.to_string_lossy();
+    let names = folder_names(repo_paths);
+    let mut modules = String::new();
+    let mut files = Vec::new();
+
+    for (name, repo_path) in names.iter().zip(repo_paths) {
+        let module_path = format!("$PROJECT_DIR$/.idea/modules/{}.iml", escape_xml(name));
+        modules.push_str(&format!(
+            "      <module fileurl=\"file://{module_path}\" filepath=\"{module_path}\" />\n"
+        ));
+
+        let module = format!(
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+                "<module type=\"WEB_MODULE\" version=\"4\">\n",
+                "  <component name=\"NewModuleRootManager\">\n",
+                "    <content url=\"file://{}\" />\n",
+                "    <orderEntry type=\"sourceFolder\" forTests=\"false\" />\n",
+                "  </component>\n",
+                "</module>\n",
+            ),
+            escape_xml(&module_content_path(&project_dir, repo_path))
+        );
+        files.push((
+            Path::new(".idea")
+                .join("modules")
+                .join(format!("{name}.iml")),
+            module,
+        ));
+    }
+
+    let modules_xml = format!(
+        concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<project version=\"4\">\n",
+            "  <component name=\"ProjectModuleManager\">\n",
+            "    <modules>\n",
+            "{}",
+            "    </modules>\n",
+            "  </component>\n",
+            "</project>\n",
+        ),
+        modules
+    );
+    files.insert(0, (Path::new(".idea").join("modules.xml"), modules_xml));
+    files
+}
+
+/// A name per repo from its directory, with a number added when two repos share one.
+fn folder_names(repo_paths: &[String]) -> Vec<String> {
+    let mut used = BTreeSet::new();
+    repo_paths
+        .iter()
+        .map(|repo_path| {
+            let base = repo_path
+                .trim_end_matches(['/', '\\'])
+                .rsplit(['/', '\\'])
+                .next()
+                .filter(|name| !name.is_empty())
+                .unwrap_or("repo")
+                .to_owned();
+
+            let mut name = base.clone();
+            let mut number = 2;
+            while !used.insert(name.to_lowercase()) {
+                name = format!("{base}-{number}");
+                number += 1;
+            }
+            name
+        })
+        .collect()
+}
+
+fn module_content_path(project_dir: &str, repo_path: &str) -> String {
+    match relative_path(project_dir, repo_path).as_deref() {
+        Some(".") => "$PROJECT_DIR$".to_owned(),
+        Some(relative) => format!("$PROJECT_DIR$/{relative}"),
+        None => repo_path.replace('\\', "/"),
+    }
+}
+
+/// The path from `directory` to `path` with `/` separators, or `None` when there isn't one, such
+/// as when they're on different drives or either isn't absolute.
+fn relative_path(directory: &str, path: &str) -> Option<String> {
+    let directory = directory.replace('\\', "/");
+    let path = path.replace('\\', "/");
+    let (directory_root, directory) = split_root(&directory)?;
+    let (root, path) = split_root(&path)?;
+    if !directory_root.eq_ignore_ascii_case(root) {
+        return None;
+    }
+
+    fn components(path: &str) -> Vec<&str> {
+        path.split('/')
+            .filter(|component| !component.is_empty() && *component != ".")
+            .collect()
+    }
+    let directory = components(directory);
+    let path = components(path);
+    if directory.contains(&"..") || path.contains(&"..") {
+        return None;
+    }
+
+    let common = directory
+        .iter()
+        .zip(&path)
+        .take_while(|(left, right)| left == right)
+        .count();
+    let mut relative = vec![".."; directory.len() - common];
+    relative.extend(&path[common..]);
+    match relative.is_empty() {
+        true => Some(".".to_owned()),
+        false => Some(relative.join("/")),
+    }
+}
+
+/// Splits an absolute path into its root, `/` or a drive like `C:`, and the rest. Network paths
+/// aren't split, since their share is part of the root.
+fn split_root(path: &str) -> Option<(&str, &str)> {
+    if path.starts_with("//") {
+        return None;
+    }
+    if let Some(rest) = path.strip_prefix('/') {
+        return Some(("/", rest));
+    }
+
+    let bytes = path.as_bytes();
+    match bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && &bytes[1..3] == b":/" {
+        true => Some((&path[..2], &path[3..])),
+        false => None,
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Removes the `.iml` files in the modules directory that the export no longer writes.
+fn remove_stale_modules(modules_dir: &Path, files: &[(PathBuf, String)]) -> anyhow::Result<()> {
+    let Ok(entries) = std::fs::read_dir(modules_dir) else {
+        return Ok(());
+    };
+
+    for entry in entries {
+        let path = entry?.path();
+        let is_module = path.extension().is_some_and(|extension| extension == "iml");
+        if is_module && !files.iter().any(|(file, _)| *file == path) {
+            std::fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_if_changed(path: &Path, contents: &str) -> anyhow::Result<()> {
+    if std::fs::read_to_string(path).ok().as_deref() == Some(contents) {
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(path, contents)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_repo_paths() -> Vec<String> {
+        vec![
+            "/src/api".to_owned(),
+            r"D:\work\web\".to_owned(),
+            "/other/api".to_owned(),
+        ]
+    }
+
+    #[test]
+    fn should_name_folders_after_repo_directories() {
+        let names = folder_names(&create_test_repo_paths());
+        assert_eq!(names, vec!["api", "web", "api-2"]);
+    }
+
+    #[test]
+    fn should_write_deterministic_code_workspace() {
+        let directory = Path::new("/src/workspaces");
+        let contents = code_workspace_contents(&create_test_repo_paths(), directory).unwrap();
+        assert_eq!(
+            contents,
+            code_workspace_contents(&create_test_repo_paths(), directory).unwrap()
+        );
+
+        let value: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        assert_eq!(value["folders"][0]["path"], "../api");
+        assert_eq!(value["folders"][1]["name"], "web");
+        assert_eq!(value["folders"][1]["path"], r"D:\work\web\");
+        assert_eq!(value["folders"][2]["path"], "../../other/api");
+    }
+
+    #[test]
+    fn should_write_jetbrains_module_per_repo() {
+        let files = jetbrains_files(&create_test_repo_paths(), Path::new("/src"));
+        assert_eq!(files.len(), 4);
+        assert_eq!(files[0].0, Path::new(".idea").join("modules.xml"));
+        assert!(files[0].1.contains("$PROJECT_DIR$/.idea/modules/api-2.iml"));
+        assert!(files[1].1.contains("file://$PROJECT_DIR$/api\""));
+        assert!(files[2].1.contains("file://D:/work/web/"));
+        assert!(files[3].1.contains("file://$PROJECT_DIR$/../other/api\""));
+    }
+
+    #[test]
+    fn should_only_relate_paths_with_the_same_root() {
+        assert_eq!(
+            relative_path(r"C:\work\workspaces", r"c:\work\api").as_deref(),
+            Some("../api")
+        );
+        assert_eq!(relative_path("/src/api", "/src/api/").as_deref(), Some("."));
+        assert_eq!(relative_path(r"C:\work", r"D:\work\api"), None);
+        assert_eq!(relative_path("/src", r"\\server\share\api"), None);
+        assert_eq!(relative_path("/src", "api"), None);
+    }
+
+    #[test]
+    fn should_remove_modules_of_removed_repos() -> anyhow::Result<()> {
+        let directory =
+            std::env::temp_dir().join(format!("st-workspaces-jetbrains-{}", uuid::Uuid::new_v4()));
+        let export = IdeExport {
+            format: ExportFormat::JetBrains,
+            path: directory.display().to_string(),
+        };
+        let mut workspace = Workspace::new("Export", uuid::Uuid::new_v4());
+        workspace.repo_paths = create_test_repo_paths();
+        export.write(&workspace, &PathRoots::default())?;
+
+        let modules = directory.join(".idea").join("modules");
+        assert!(modules.join("api-2.iml").exists());
+
+        workspace.repo_paths.pop();
+        export.write(&workspace, &PathRoots::default())?;
+        assert!(modules.join("api.iml").exists());
+        assert!(!modules.join("api-2.iml").exists());
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use fs2::FileExt;
+use interprocess::local_socket::{LocalSocketListener, LocalSocketStream};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{error::Error, paths::sourcetree_settings_path, tr};
+
+/// How long a forwarded request may take, which includes closing SourceTree.
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// What a new invocation asks the running instance to do instead of doing it itself.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "request", rename_all = "snake_case")]
+pub enum InstanceRequest {
+    Show,
+    /// Save SourceTree's tabs into the current workspace, as `auto-update` does, and show the
+    /// window unless the invocation would have closed straight after.
+    AutoUpdate {
+        show: bool,
+    },
+    /// Switch to the workspace picked with `open`, as if it had been picked in the window.
+    Open {
+        uuid: Uuid,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct InstanceResponse {
+    pub handled: bool,
+    pub message: String,
+}
+
+/// An advisory lock on the settings directory, held by whichever instance is reading and writing
+/// the workspaces. The operating system drops it if the process dies, so a stale lock file is
+/// simply taken over.
+pub struct InstanceLock {
+    file: File,
+}
+
+impl InstanceLock {
+    pub fn path() -> Option<PathBuf> {
+        Some(sourcetree_settings_path()?.join("st-workspaces.lock"))
+    }
+
+    /// Takes the lock, or returns `None` when another instance holds it.
+    pub fn try_acquire() -> anyhow::Result<Option<InstanceLock>> {
+        let path =
+            InstanceLock::path().ok_or(Error::PathNotFound("SourceTree settings directory"))?;
+        InstanceLock::try_acquire_at(&path)
+    }
+
+    /// Waits for another instance to let go of the lock.
+    pub fn acquire_with_timeout(timeout: Duration) -> anyhow::Result<Option<InstanceLock>> {
+        let start_time = Instant::now();
+        loop {
+            if let Some(lock) = InstanceLock::try_acquire()? {
+                return Ok(Some(lock));
+            }
+
+            if start_time.elapsed() > timeout {
+                return Ok(None);
+            }
+
+            thread::sleep(Duration::from_millis(200));
+        }
+    }
+
+    pub fn try_acquire_at(path: &Path) -> anyhow::Result<Option<InstanceLock>> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+
+        if file.try_lock_exclusive().is_err() {
+            return Ok(None);
+        }
+
+        // Only for whoever looks at the file, since the lock itself is what counts.
+        file.set_len(0)?;
+        write!(file, "{}", std::process::id())?;
+        Ok(Some(InstanceLock { file }))
+    }
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/// Requests from later invocations, each with somewhere to send the answer.
+pub struct InstanceServer {
+    pub requests: Receiver<(InstanceRequest, Sender<InstanceResponse>)>,
+}
+
+impl InstanceServer {
+    /// Listens for other invocations. Only the holder of the [`InstanceLock`] should call this.
+    pub fn start(_lock: &InstanceLock) -> anyhow::Result<InstanceServer> {
+        let name = socket_name().ok_or(Error::PathNotFound("SourceTree settings directory"))?;
+        InstanceServer::start_at(&name)
+    }
+
+    pub fn start_at(name: &str) -> anyhow::Result<InstanceServer> {
+        // Holding the lock means nobody else is listening, so a socket file left by a crashed
+        // instance can go.
+        if !cfg!(windows) {
+            let _ = std::fs::remove_file(name);
+        }
+
+        let listener = LocalSocketListener::bind(name)?;
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(why) => {
+                        warn!("Couldn't accept another instance. '{}'", why);
+                        continue;
+                    }
+                };
+
+                if let Err(why) = handle_connection(stream, &sender) {
+                    warn!("Couldn't answer another instance. '{}'", why);
+                }
+            }
+        });
+
+        Ok(InstanceServer { requests: receiver })
+    }
+}
+
+/// Asks the running instance to handle the request.
+pub fn forward(request: &InstanceRequest) -> anyhow::Result<InstanceResponse> {
+    let name = socket_name().ok_or(Error::PathNotFound("SourceTree settings directory"))?;
+    forward_to(&name, request)
+}
+
+pub fn forward_to(name: &str, request: &InstanceRequest) -> anyhow::Result<InstanceResponse> {
+    let mut stream = LocalSocketStream::connect(name)?;
+    writeln!(stream, "{}", serde_json::to_string(request)?)?;
+
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    Ok(serde_json::from_str(&line)?)
+}
+
+/// A named pipe on Windows, and a socket file next to the settings elsewhere.
+fn socket_name() -> Option<String> {
+    let settings_path = sourcetree_settings_path()?;
+    if cfg!(windows) {
+        // Pipes are shared by everyone on the machine.
+        let user = std::env::var("USERNAME").unwrap_or_default();
+        Some(format!("st-workspaces-{}", user))
+    } else {
+        Some(
+            settings_path
+                .join("st-workspaces.sock")
+                .display()
+                .to_string(),
+        )
+    }
+}
+
+fn handle_connection(
+    stream: LocalSocketStream,
+    sender: &Sender<(InstanceRequest, Sender<InstanceResponse>)>,
+) -> anyhow::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let request: InstanceRequest = serde_json::from_str(&line)?;
+    info!("Another instance asked for {:?}.", request);
+
+    let (reply_sender, reply_receiver) = mpsc::channel();
+    sender.send((request, reply_sender))?;
+    let response = reply_receiver
+        .recv_timeout(RESPONSE_TIMEOUT)
+        .unwrap_or_else(|_| InstanceResponse {
+            handled: false,
+            message: tr!("instance-no-answer"),
+        });
+
+    let mut stream = reader.into_inner();
+    writeln!(stream, "{}", serde_json::to_string(&response)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_let_one_holder_have_the_lock() {
+        let path = std::env::temp_dir().join(format!("st-workspaces-{}.lock", Uuid::new_v4()));
+        let lock = InstanceLock::try_acquire_at(&path).unwrap();
+        assert!(lock.is_some());
+        assert!(InstanceLock::try_acquire_at(&path).unwrap().is_none());
+
+        drop(lock);
+        assert!(InstanceLock::try_acquire_at(&path).unwrap().is_some());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn should_forward_requests_to_running_instance() {
+        let name = match cfg!(windows) {
+            true => format!("st-workspaces-test-{}", Uuid::new_v4()),
+            false => std::env::temp_dir()
+                .join(format!("st-workspaces-{}.sock", Uuid::new_v4()))
+                .display()
+                .to_string(),
+        };
+        let server = InstanceServer::start_at(&name).unwrap();
+
+        thread::spawn(move || {
+            let (request, reply) = server.requests.recv().unwrap();
+            reply
+                .send(InstanceResponse {
+                    handled: request == InstanceRequest::AutoUpdate { show: false },
+                    message: "Updated".to_owned(),
+                })
+                .unwrap();
+        });
+
+        let response = forward_to(&name, &InstanceRequest::AutoUpdate { show: false }).unwrap();
+        assert!(response.handled);
+        assert_eq!(response.message, "Updated");
+    }
+}
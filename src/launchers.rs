@@ -0,0 +1,334 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{write_file, Error},
+    ide_export::{code_workspace_contents, ExportFormat},
+    local_config::LocalConfig,
+    open_tabs::OpenTabs,
+    paths::sourcetree_settings_path,
+    preferences::Preferences,
+    process::{shell_command, spawn_detached},
+    tr,
+    workspaces::Workspace,
+};
+
+/// Something a workspace's repos can be opened in.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Launcher {
+    SourceTree,
+    /// A generated multi-root `.code-workspace` opened in VS Code.
+    VsCode,
+    /// A terminal in each repo.
+    Terminal,
+    FileManager,
+    /// A command line where `{repo}` runs it once per repo, and `{repos}` and `{name}` are
+    /// replaced with every repo and the workspace's name. Each is quoted for the shell, so the
+    /// template shouldn't quote them again.
+    Command {
+        name: String,
+        template: String,
+    },
+}
+
+impl std::fmt::Display for Launcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Launcher::SourceTree => write!(f, "SourceTree"),
+            Launcher::VsCode => write!(f, "VS Code"),
+            Launcher::Terminal => write!(f, "{}", tr!("launcher-terminal")),
+            Launcher::FileManager => write!(f, "{}", tr!("launcher-file-manager")),
+            Launcher::Command { name, .. } => write!(f, "{name}"),
+        }
+    }
+}
+
+impl Launcher {
+    /// The launchers that need no setup.
+    pub fn built_in() -> [Launcher; 4] {
+        [
+            Launcher::SourceTree,
+            Launcher::VsCode,
+            Launcher::Terminal,
+            Launcher::FileManager,
+        ]
+    }
+
+    /// Opens the workspace's resolved repos. SourceTree is waited on, everything else is left
+    /// running.
+    pub fn launch(&self, workspace: &Workspace, repo_paths: Vec<String>) -> anyhow::Result<()> {
+        match self {
+            Launcher::SourceTree => launch_sourcetree(workspace, repo_paths),
+            Launcher::VsCode => {
+                let path = write_code_workspace(workspace, &repo_paths)?;
+                spawn_detached(
+                    Command::new(code_program())
+                        .arg(path)
+                        .envs(&workspace.environment),
+                )
+            }
+            Launcher::Terminal => {
+                for repo_path in repo_paths.iter() {
+                    let mut command = terminal_command(Path::new(repo_path));
+                    command.envs(&workspace.environment);
+                    spawn_detached(&mut command)?;
+                }
+                Ok(())
+            }
+            Launcher::FileManager => {
+                for repo_path in repo_paths.iter() {
+                    spawn_detached(Command::new(file_manager_program()).arg(repo_path))?;
+                }
+                Ok(())
+            }
+            Launcher::Command { template, .. } => {
+                for (command_line, working_dir) in
+                    expand_template(template, &workspace.name, &repo_paths)
+                {
+                    let working_dir = working_dir.as_deref().map(Path::new);
+                    let mut command =
+                        shell_command(&command_line, working_dir, &workspace.environment);
+                    spawn_detached(&mut command)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The command lines a template makes, with the directory each runs in.
+pub fn expand_template(
+    template: &str,
+    workspace_name: &str,
+    repo_paths: &[String],
+) -> Vec<(String, Option<String>)> {
+    let all_repos = repo_paths
+        .iter()
+        .map(|repo_path| quote(repo_path))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let name = quote(workspace_name);
+    let fill = |repo: &str| {
+        fill_template(
+            template,
+            &[("{repos}", &all_repos), ("{name}", &name), ("{repo}", repo)],
+        )
+    };
+
+    if !template.contains("{repo}") {
+        return vec![(fill(""), None)];
+    }
+
+    repo_paths
+        .iter()
+        .map(|repo_path| (fill(&quote(repo_path)), Some(repo_path.clone())))
+        .collect()
+}
+
+/// Replaces the placeholders in one pass, so a value that happens to contain one isn't replaced
+/// again.
+fn fill_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut filled = String::new();
+    let mut rest = template;
+    while !rest.is_empty() {
+        match values
+            .iter()
+            .find(|(placeholder, _)| rest.starts_with(placeholder))
+        {
+            Some((placeholder, value)) => {
+                filled.push_str(value);
+                rest = &rest[placeholder.len()..];
+            }
+            None => {
+                let next = rest.chars().next().unwrap_or_default();
+                filled.push(next);
+                rest = &rest[next.len_utf8()..];
+            }
+        }
+    }
+    filled
+}
+
+fn launch_sourcetree(workspace: &Workspace, repo_paths: Vec<String>) -> anyhow::Result<()> {
+    let open_tabs = OpenTabs {
+        tabs: repo_paths,
+        workspace_id: Some(workspace.uuid),
+    };
+    OpenTabs::write(&open_tabs).context(tr!("error-writing-open-tabs"))?;
+
+    let exec_path = Preferences::read()
+        .unwrap_or_default()
+        .sourcetree_exec_path()
+        .ok_or(Error::PathNotFound("SourceTree executable"))?;
+    let mut command = Command::new(exec_path);
+    let mut child = command
+        .envs(&workspace.environment)
+        .spawn()
+        .map_err(|why| Error::launch(&command, why))
+        .context(tr!("error-launching-sourcetree"))?;
+
+    child
+        .wait()
+        .map_err(|why| Error::launch(&command, why))
+        .context(tr!("error-waiting-for-sourcetree"))?;
+
+    Ok(())
+}
+
+/// The workspace's own VS Code export when it has one, otherwise a file in the settings directory.
+fn write_code_workspace(workspace: &Workspace, repo_paths: &[String]) -> anyhow::Result<PathBuf> {
+    let roots = LocalConfig::read().unwrap_or_default().roots;
+    if let Some(export) = workspace
+        .exports
+        .iter()
+        .find(|export| export.format == ExportFormat::VsCode)
+    {
+        export.write_repo_paths(repo_paths, &roots)?;
+        return Ok(PathBuf::from(roots.expand(&export.path)));
+    }
+
+    let directory = sourcetree_settings_path()
+        .ok_or(Error::PathNotFound("SourceTree settings directory"))?
+        .join("code-workspaces");
+    std::fs::create_dir_all(&directory)?;
+
+    let path = directory.join(format!("{}.code-workspace", workspace.uuid));
+    write_file(&path, code_workspace_contents(repo_paths, &directory)?)?;
+    Ok(path)
+}
+
+fn terminal_command(repo_path: &Path) -> Command {
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", "cmd"]);
+        command
+    } else if cfg!(target_os = "macos") {
+        let mut command = Command::new("open");
+        command.args(["-a", "Terminal"]).arg(repo_path);
+        command
+    } else {
+        Command::new("x-terminal-emulator")
+    };
+
+    command.current_dir(repo_path);
+    command
+}
+
+/// VS Code's launcher, which is a batch file on Windows and can't be found without its extension.
+fn code_program() -> &'static str {
+    match cfg!(windows) {
+        true => "code.cmd",
+        false => "code",
+    }
+}
+
+fn file_manager_program() -> &'static str {
+    if cfg!(windows) {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    }
+}
+
+/// Quotes a value as one argument for [`shell_command`]'s shell, so whatever is in a workspace's
+/// name or paths can't end the argument or start another command.
+fn quote(value: &str) -> String {
+    match cfg!(windows) {
+        true => quote_for_cmd(value),
+        false => quote_for_sh(value),
+    }
+}
+
+fn quote_for_sh(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// `cmd` has no escape for `"` or `%` inside quotes. A doubled `""` stays quoted and is read as
+/// one `"` by the program, and a `%` is stepped out of the quotes to be escaped with `^`. Line
+/// breaks would end the command, so they become spaces.
+fn quote_for_cmd(value: &str) -> String {
+    let value = value
+        .replace(['\r', '\n'], " ")
+        .replace('"', "\"\"")
+        .replace('%', "\"^%\"");
+    format!("\"{value}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_expand_template_once_per_repo() {
+        let repo_paths = ["/src/one".to_owned(), "/src/two".to_owned()];
+        let commands = expand_template("idea {repo} --title {name}", "Hotfix", &repo_paths);
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(
+            commands[0].0,
+            format!("idea {} --title {}", quote("/src/one"), quote("Hotfix"))
+        );
+        assert_eq!(commands[1].1.as_deref(), Some("/src/two"));
+    }
+
+    #[test]
+    fn should_expand_template_once_for_all_repos() {
+        let repo_paths = ["/src/one".to_owned(), "/src/two".to_owned()];
+        let commands = expand_template("subl {repos}", "Hotfix", &repo_paths);
+        assert_eq!(
+            commands,
+            vec![(
+                format!("subl {} {}", quote("/src/one"), quote("/src/two")),
+                None
+            )]
+        );
+    }
+
+    #[test]
+    fn should_quote_names_that_look_like_commands() {
+        assert_eq!(quote_for_sh("Hotfix"), "'Hotfix'");
+        assert_eq!(
+            quote_for_sh("it's; rm -rf ~ $(id)"),
+            r"'it'\''s; rm -rf ~ $(id)'"
+        );
+
+        assert_eq!(quote_for_cmd("Hotfix"), r#""Hotfix""#);
+        assert_eq!(quote_for_cmd("a\" & calc & \"b"), r#""a"" & calc & ""b""#);
+        assert_eq!(quote_for_cmd("%PATH%\ndel"), r#"""^%"PATH"^%" del""#);
+
+        let repo_paths = ["/src/{name}".to_owned()];
+        let commands = expand_template("open {repo} {name}", "{repo}", &repo_paths);
+        assert_eq!(
+            commands[0].0,
+            format!("open {} {}", quote("/src/{name}"), quote("{repo}"))
+        );
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn should_pass_names_through_the_shell_unchanged() {
+        let name = "it's \"quoted\"; echo $HOME `id`";
+        let commands = expand_template("printf %s {name}", name, &[]);
+        assert_eq!(commands.len(), 1);
+        let output = shell_command(&commands[0].0, None, &Default::default())
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), name);
+    }
+
+    #[test]
+    fn should_read_launchers_by_kind() {
+        let json = r#"[{ "kind": "vs_code" }, { "kind": "command", "name": "Fork", "template": "fork {repo}" }]"#;
+        let launchers: Vec<Launcher> = serde_json::from_str(json).unwrap();
+        assert_eq!(launchers[0], Launcher::VsCode);
+        assert_eq!(launchers[1].to_string(), "Fork");
+    }
+}
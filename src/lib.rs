@@ -1,6 +1,34 @@
 pub mod app;
+pub mod bookmarks;
+pub mod catalogue;
 pub mod custom_actions;
+pub mod dirty_guard;
+pub mod doctor;
+pub mod error;
+pub mod filters;
+pub mod git;
+pub mod git_batch;
+pub mod hooks;
+pub mod i18n;
+pub mod ide_export;
+pub mod instance;
+pub mod launchers;
+pub mod local_config;
+pub mod logging;
 pub mod open_tabs;
 pub mod paths;
+pub mod preferences;
+pub mod process;
+pub mod project_file;
+pub mod resident;
+pub mod roots;
+pub mod rules;
+pub mod scanner;
+pub mod selection;
+pub mod session;
+pub mod snapshots;
 pub mod sourcetree_actions;
+pub mod tabs_watcher;
+pub mod ui_state;
+pub mod workspace_merge;
 pub mod workspaces;
@@ -0,0 +1,67 @@
+use std::{collections::BTreeSet, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    catalogue::Catalogue,
+    error::{is_not_found, parse_json, read_file, write_file, Error},
+    logging::LoggingConfig,
+    paths::sourcetree_settings_path,
+    resident::ResidentConfig,
+    roots::PathRoots,
+    tabs_watcher::TabsWatcherConfig,
+};
+
+/// Settings that belong to this machine rather than to the workspaces, so they are kept out of
+/// `st-workspaces.json`.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct LocalConfig {
+    #[serde(default)]
+    pub roots: PathRoots,
+    #[serde(default)]
+    pub catalogue: Option<Catalogue>,
+    /// Repos to look in for a committed `.st-workspace`, besides those already in a workspace.
+    #[serde(default)]
+    pub project_repos: Vec<PathBuf>,
+    #[serde(default)]
+    pub resident: ResidentConfig,
+    #[serde(default)]
+    pub tabs_watcher: TabsWatcherConfig,
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    /// The [`hooks_fingerprint`]s of team and project hooks that were allowed to run here.
+    ///
+    /// [`hooks_fingerprint`]: crate::hooks::hooks_fingerprint
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub trusted_hooks: BTreeSet<Uuid>,
+}
+
+impl LocalConfig {
+    pub fn path() -> Option<PathBuf> {
+        Some(sourcetree_settings_path()?.join("st-workspaces-local.json"))
+    }
+
+    pub fn read() -> anyhow::Result<LocalConfig> {
+        let path = LocalConfig::path().ok_or(Error::PathNotFound("local config file"))?;
+        let contents = read_file(&path)?;
+        let config: LocalConfig = parse_json(&path, &contents)?;
+        Ok(config)
+    }
+
+    /// Like [`LocalConfig::read`], but a config that hasn't been written yet is the default one.
+    /// A config that can't be parsed is still an error, so it isn't mistaken for an empty one.
+    pub fn read_or_default() -> anyhow::Result<LocalConfig> {
+        match LocalConfig::read() {
+            Err(why) if is_not_found(&why) => Ok(LocalConfig::default()),
+            result => result,
+        }
+    }
+
+    pub fn write(&self) -> anyhow::Result<()> {
+        let path = LocalConfig::path().ok_or(Error::PathNotFound("local config file"))?;
+        let contents = serde_json::to_string_pretty(self)?;
+        write_file(&path, contents)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use flexi_logger::{
+    Age, Cleanup, Criterion, DeferredNow, Duplicate, FileSpec, Logger, LoggerHandle, Naming,
+    WriteMode,
+};
+use log::{warn, Level, Record};
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, paths::log_dir_path};
+
+const BASENAME: &str = "st-workspaces";
+
+/// Where the log goes and how much of it is kept.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LoggingConfig {
+    /// A flexi_logger spec, such as `info` or `info, st_workspaces::git=trace`.
+    #[serde(default = "default_level")]
+    pub level: String,
+    /// The current file is rotated once it's this big, or at the end of the day.
+    #[serde(default = "default_max_file_size_kb")]
+    pub max_file_size_kb: u64,
+    /// Rotated files older than this are deleted at startup.
+    #[serde(default = "default_max_age_days")]
+    pub max_age_days: u64,
+    #[serde(default = "default_max_files")]
+    pub max_files: usize,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: default_level(),
+            max_file_size_kb: default_max_file_size_kb(),
+            max_age_days: default_max_age_days(),
+            max_files: default_max_files(),
+        }
+    }
+}
+
+/// Starts logging to the settings directory. The handle must be kept until the program ends, or
+/// buffered lines are lost. Verbose runs log everything from debug up, to the console as well.
+pub fn start(config: &LoggingConfig, verbose: bool) -> anyhow::Result<LoggerHandle> {
+    let log_dir = log_dir_path().ok_or(Error::PathNotFound("SourceTree settings directory"))?;
+    remove_old_logs(
+        &log_dir,
+        Duration::from_secs(config.max_age_days * 24 * 60 * 60),
+    );
+
+    let spec = match verbose {
+        true => "debug",
+        false => config.level.as_str(),
+    };
+    let (logger, spec_error) = match Logger::try_with_str(spec) {
+        Ok(logger) => (logger, None),
+        Err(why) => (Logger::try_with_str(default_level())?, Some(why)),
+    };
+
+    let handle = logger
+        .log_to_file(
+            FileSpec::default()
+                .directory(log_dir)
+                .basename(BASENAME)
+                .suppress_timestamp(),
+        )
+        // Every process shares the current file, so starting one mustn't rotate it away from the rest.
+        .append()
+        .format_for_files(line_format)
+        .rotate(
+            Criterion::AgeOrSize(Age::Day, config.max_file_size_kb.max(1) * 1024),
+            Naming::Timestamps,
+            Cleanup::KeepLogFiles(config.max_files.max(1)),
+        )
+        .duplicate_to_stderr(match verbose {
+            true => Duplicate::All,
+            false => Duplicate::None,
+        })
+        .write_mode(WriteMode::BufferAndFlush)
+        .start()?;
+
+    // Only logged now, so it ends up in the file it's about.
+    if let Some(why) = spec_error {
+        warn!("Log level '{}' isn't valid, so using info. '{}'", spec, why);
+    }
+    Ok(handle)
+}
+
+/// The file being written to, which the log panel tails.
+pub fn current_log_path() -> Option<PathBuf> {
+    Some(log_dir_path()?.join(format!("{BASENAME}_rCURRENT.log")))
+}
+
+/// `timestamp LEVEL [module] message`, so the level can be picked back out of each line.
+fn line_format(
+    write: &mut dyn Write,
+    now: &mut DeferredNow,
+    record: &Record,
+) -> std::io::Result<()> {
+    write!(
+        write,
+        "{} {:<5} [{}] {}",
+        now.format("%Y-%m-%d %H:%M:%S%.3f"),
+        record.level(),
+        record.module_path().unwrap_or("<unnamed>"),
+        record.args()
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    pub level: Level,
+    pub text: String,
+}
+
+/// The end of the log, up to `max_bytes` of it. Lines that carry on a multi-line message take the
+/// level of the line they continue.
+pub fn tail(path: &Path, max_bytes: u64) -> anyhow::Result<Vec<LogLine>> {
+    let mut file = std::fs::File::open(path).map_err(|source| Error::Io {
+        operation: "open",
+        path: path.to_owned(),
+        source,
+    })?;
+    let length = file.metadata()?.len();
+    let start = length.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start))?;
+
+    let mut buffer = Vec::new();
+    file.read_to_end(&mut buffer)?;
+    let contents = String::from_utf8_lossy(&buffer);
+    let mut lines = contents.lines();
+    if start > 0 {
+        // Most likely started part way through a line.
+        lines.next();
+    }
+
+    Ok(parse_lines(lines))
+}
+
+pub fn parse_lines<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<LogLine> {
+    let mut level = Level::Info;
+    lines
+        .map(|line| {
+            if let Some(line_level) = line
+                .split_whitespace()
+                .nth(2)
+                .and_then(|token| token.parse().ok())
+            {
+                level = line_level;
+            }
+
+            LogLine {
+                level,
+                text: line.to_owned(),
+            }
+        })
+        .collect()
+}
+
+fn remove_old_logs(log_dir: &Path, max_age: Duration) {
+    let Ok(entries) = std::fs::read_dir(log_dir) else {
+        return;
+    };
+
+    let now = SystemTime::now();
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let is_old = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .is_ok_and(|modified| now.duration_since(modified).is_ok_and(|age| age > max_age));
+        let is_log = path.extension().is_some_and(|extension| extension == "log");
+
+        if is_old && is_log {
+            if let Err(why) = std::fs::remove_file(&path) {
+                warn!("Couldn't remove old log {}. '{}'", path.display(), why);
+            }
+        }
+    }
+}
+
+fn default_level() -> String {
+    "info".to_owned()
+}
+
+fn default_max_file_size_kb() -> u64 {
+    1024
+}
+
+fn default_max_age_days() -> u64 {
+    30
+}
+
+fn default_max_files() -> usize {
+    10
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn should_pick_levels_out_of_lines() {
+        let contents = "2023-04-01 10:00:00.000 INFO  [st_workspaces::app] Opening\n\
+            2023-04-01 10:00:01.000 WARN  [st_workspaces::git] Couldn't pull\n\
+            carried on\n\
+            2023-04-01 10:00:02.000 DEBUG [st_workspaces::app] Done";
+
+        let lines = parse_lines(contents.lines());
+
+        let levels: Vec<_> = lines.iter().map(|line| line.level).collect();
+        assert_eq!(
+            levels,
+            vec![Level::Info, Level::Warn, Level::Warn, Level::Debug]
+        );
+    }
+
+    #[test]
+    fn should_tail_end_of_log() {
+        let path = std::env::temp_dir().join(format!("st-workspaces-{}.log", Uuid::new_v4()));
+        let contents: String = (0..100)
+            .map(|index| format!("2023-04-01 10:00:00.000 INFO  [test] line {index}\n"))
+            .collect();
+        std::fs::write(&path, contents).unwrap();
+
+        let lines = tail(&path, 200).unwrap();
+
+        assert!(lines.len() < 5);
+        assert_eq!(
+            lines.last().unwrap().text,
+            "2023-04-01 10:00:00.000 INFO  [test] line 99"
+        );
+        assert!(lines[0].text.starts_with("2023-04-01"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,46 +1,138 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
-use std::{env, fs};
+use std::{env, time::Duration};
 
-use anyhow::{anyhow, bail};
-use flexi_logger::{FileSpec, Logger, WriteMode};
+use anyhow::bail;
+use flexi_logger::LoggerHandle;
+use log::{info, warn};
+use uuid::Uuid;
 
-use log::{error, info, warn};
 use st_workspaces::{
     app::SourceTreeWorkspacesApp,
-    open_tabs::OpenTabs,
-    paths::sourcetree_settings_path,
-    sourcetree_actions::{self, CloseResult},
-    workspaces::{Workspace, Workspaces},
+    doctor,
+    filters::{matching_workspaces, pick_workspace, WorkspaceFilter},
+    i18n,
+    instance::{self, InstanceLock, InstanceRequest, InstanceServer},
+    local_config::LocalConfig,
+    logging,
+    preferences::{Preferences, MIN_WINDOW_SIZE},
+    session, tabs_watcher, tr,
+    workspaces::Workspaces,
 };
-use uuid::Uuid;
+
+/// How long to wait for another one-shot run, such as `auto-update-and-close`, to finish.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Subcommands that print their results rather than showing the window.
+const CLI_COMMANDS: [&str; 4] = ["doctor", "settings", "list", "open"];
 
 struct AppConfig {
     close_after_update: bool,
     update_current_workspace: bool,
+    resident: bool,
+    /// Picked with `open`, for the window to switch to.
+    open_workspace: Option<Uuid>,
 }
 
 fn main() -> Result<(), anyhow::Error> {
-    setup_logging()?;
+    i18n::set_language(Preferences::read().unwrap_or_default().language.as_deref());
+
+    let args: Vec<String> = env::args().collect();
+    if args
+        .get(1)
+        .is_some_and(|arg| CLI_COMMANDS.contains(&arg.as_str()))
+    {
+        attach_parent_console();
+    }
+
+    if args.get(1).is_some_and(|arg| arg == "settings") {
+        return run_settings(&args[2..]);
+    }
+
+    if args.get(1).is_some_and(|arg| arg == "list") {
+        return run_list(&args[2..]);
+    }
+
+    // Before logging, since an unwritable log directory is one of the things it looks for.
+    if args.get(1).is_some_and(|arg| arg == "doctor") {
+        return run_doctor();
+    }
+
+    let verbose = env::args().any(|arg| arg == "--verbose" || arg == "-v");
+    let _logger = setup_logging(verbose);
 
-    // We have to get the last workspace id before trying to close SourceTree, as SourceTree
-    // overwrites the OpenTabs file.
-    let last_workspace_id = discover_last_workspace_id();
-    let app_config = parse_app_config();
-    close_sourcetree(app_config.update_current_workspace)?;
+    // Started alongside SourceTree, e.g. from an after-open hook, so it mustn't close it.
+    if args.get(1).is_some_and(|arg| arg == "watch-tabs") {
+        return tabs_watcher::run_headless();
+    }
 
-    let mut workspaces = get_workspaces();
-    if let Some(workspace_id) = last_workspace_id {
-        update_last_workspace(&mut workspaces, workspace_id);
-        workspaces.write()?;
-        save_open_tabs(&workspaces)
+    let mut app_config = parse_app_config();
+    if args.get(1).is_some_and(|arg| arg == "open") {
+        app_config.open_workspace = Some(pick_open_workspace(&args[2..])?);
     }
 
+    let Some(lock) = acquire_instance(&app_config)? else {
+        return Ok(());
+    };
+
+    let workspaces = session::sync_last_session(app_config.update_current_workspace)?;
+
     if app_config.close_after_update {
         return Ok(());
     }
 
-    launch_app(workspaces)
+    let server = InstanceServer::start(&lock)
+        .map_err(|why| warn!("Couldn't listen for other instances. '{}'", why))
+        .ok();
+    launch_app(
+        workspaces,
+        app_config.resident,
+        server,
+        app_config.open_workspace,
+    )
+}
+
+/// Takes the instance lock, or hands the invocation to the GUI that already has it. `None` means
+/// the running GUI dealt with it.
+fn acquire_instance(app_config: &AppConfig) -> Result<Option<InstanceLock>, anyhow::Error> {
+    if let Some(lock) = InstanceLock::try_acquire()? {
+        return Ok(Some(lock));
+    }
+
+    let request = match (
+        app_config.open_workspace,
+        app_config.update_current_workspace,
+    ) {
+        (Some(uuid), _) => InstanceRequest::Open { uuid },
+        (None, true) => InstanceRequest::AutoUpdate {
+            show: !app_config.close_after_update,
+        },
+        (None, false) => InstanceRequest::Show,
+    };
+
+    match instance::forward(&request) {
+        Ok(response) if response.handled => {
+            info!(
+                "The running instance handled {:?}. {}",
+                request, response.message
+            );
+            if app_config.open_workspace.is_some() {
+                println!("{}", response.message);
+            }
+            return Ok(None);
+        }
+        Ok(response) => warn!(
+            "The running instance didn't handle {:?}. {}",
+            request, response.message
+        ),
+        Err(why) => info!("Couldn't reach the running instance. '{}'", why),
+    }
+
+    // Whoever has the lock isn't a GUI, so it will be done shortly.
+    match InstanceLock::acquire_with_timeout(LOCK_TIMEOUT)? {
+        Some(lock) => Ok(Some(lock)),
+        None => bail!(tr!("cli-instance-running")),
+    }
 }
 
 fn parse_app_config() -> AppConfig {
@@ -48,166 +140,174 @@ fn parse_app_config() -> AppConfig {
     let auto_update = args.contains(&"auto-update".to_owned());
     let auto_update_and_close = args.contains(&"auto-update-and-close".to_owned());
     let update_current_workspace = auto_update || auto_update_and_close;
+    let mut resident = args.contains(&"--resident".to_owned())
+        || LocalConfig::read().is_ok_and(|config| config.resident.enabled);
+
+    // Without the tray there'd be no way back to the hidden window, nor to quit it.
+    if resident && !cfg!(all(feature = "resident", windows)) {
+        warn!("Resident mode needs the tray, which this build doesn't have. Starting normally.");
+        resident = false;
+    }
 
     AppConfig {
         close_after_update: auto_update_and_close,
         update_current_workspace,
+        resident,
+        open_workspace: None,
     }
 }
 
-fn setup_logging() -> Result<(), anyhow::Error> {
-    let log_path = sourcetree_settings_path()
-        .ok_or_else(|| anyhow!("Couldn't find settings path to make log path."))?
-        .join("log");
-    let _logger = Logger::try_with_str("info, my::critical::module=trace")?
-        .log_to_file(FileSpec::default().directory(log_path))
-        .write_mode(WriteMode::BufferAndFlush)
-        .start()?;
+/// Release builds have no console of their own on Windows, so borrow the one they were run from.
+#[cfg(windows)]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
 
-    Ok(())
-}
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
 
-fn discover_last_workspace_id() -> Option<Uuid> {
-    match OpenTabs::read() {
-        Ok(open_tabs) => {
-            info!("Was able to open tabs.");
-            let last_workspace = Workspace::from(&open_tabs);
-            if !last_workspace.uuid.is_nil() {
-                info!("Last workspace id is {:?}", last_workspace.uuid);
-                return Some(last_workspace.uuid);
-            }
-            None
-        }
-        Err(why) => {
-            info!("Wasn't able to open tabs file --> {}", why);
-            None
-        }
+    // Fails when there's no parent console, e.g. when run from SourceTree, which is fine.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
     }
 }
 
-fn save_open_tabs(workspaces: &Workspaces) {
-    if let Some(current_workspace) = workspaces.current_workspace() {
-        let open_tabs = OpenTabs::from(current_workspace);
-        let write_result = OpenTabs::write(&open_tabs);
-        match write_result {
-            Ok(_) => info!("Saved current open tabs"),
-            Err(why) => warn!("Couldn't save current open tabs. '{}'", why),
-        }
+#[cfg(not(windows))]
+fn attach_parent_console() {}
+
+fn run_doctor() -> Result<(), anyhow::Error> {
+    let results = doctor::run_checks();
+    println!("{}", doctor::report(&results));
+
+    if doctor::has_failures(&results) {
+        std::process::exit(1);
     }
-}
 
-fn get_workspaces() -> Workspaces {
-    let mut workspaces = Workspaces::read().unwrap_or_default();
-    workspaces.force_valid_workspace();
-    workspaces
+    Ok(())
 }
 
-fn close_sourcetree(wait_for_open_tabs_change: bool) -> Result<(), anyhow::Error> {
-    let open_tabs_path = OpenTabs::path().ok_or(anyhow!(
-        "Couldn't get open tabs path trying to close SourceTree."
-    ))?;
-    let open_tabs_metadata = fs::metadata(&open_tabs_path);
-
-    // try to close SourceTree first, as this should never be up at the same time.
-    let close_result = sourcetree_actions::close_sourcetree();
+/// `settings [get <key> | set <key> <value> | reset [key]]`, printing every setting by default.
+fn run_settings(args: &[String]) -> Result<(), anyhow::Error> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    // Resetting everything is the way out of a file that no longer parses, so it needn't be read.
+    let mut preferences = match args.as_slice() {
+        ["reset"] => Preferences::default(),
+        _ => Preferences::read_or_default()?,
+    };
 
-    match close_result {
-        Ok(CloseResult::Closed) => info!("Closed SourceTree."),
-        Ok(CloseResult::ProcessNotRunning) => {
-            info!("Didn't close SourceTree, because it wasn't running")
+    match args.as_slice() {
+        [] => {
+            if let Some(path) = Preferences::path() {
+                println!("{}", path.display());
+            }
+            println!("{}", serde_json::to_string_pretty(&preferences)?);
+            return Ok(());
         }
-        Err(why) => {
-            error!("Error occurred closing SourceTree, '{}'", why);
-            bail!("Can't recover because we don't know the state of SourceTree at this point.")
+        ["get", key] => {
+            println!("{}", preferences.get(key)?);
+            return Ok(());
         }
+        ["set", key, value] => preferences.set(key, value)?,
+        ["reset"] => {}
+        ["reset", key] => preferences.reset(key)?,
+        _ => bail!(tr!("cli-settings-usage")),
     }
 
-    if wait_for_open_tabs_change {
-        match open_tabs_metadata {
-            Ok(initial_metadata) => {
-                let start_time = std::time::Instant::now();
-                loop {
-                    let duration = std::time::Instant::now() - start_time;
-                    if duration > std::time::Duration::from_secs(4) {
-                        break;
-                    }
-
-                    if let Ok(latest_metadata) = fs::metadata(&open_tabs_path) {
-                        if latest_metadata.modified().unwrap()
-                            != initial_metadata.modified().unwrap()
-                        {
-                            break;
-                        }
-                    }
-
-                    std::thread::sleep(std::time::Duration::from_millis(200));
-                }
-            }
-            Err(why) => {
-                warn!(
-                    "Couldn't get open tabs metadata '{}'. Waiting 4 seconds.",
-                    why
-                );
+    preferences.write()?;
+    println!("{}", tr!("status-saved-settings"));
+    Ok(())
+}
 
-                std::thread::sleep(std::time::Duration::from_secs(4));
-            }
+/// `list [--tag <tag>]... [--all]`, printing the matching workspaces by name. Archived ones are
+/// left out unless `--all` or `--tag archived` is given.
+fn run_list(args: &[String]) -> Result<(), anyhow::Error> {
+    let Some((_, filter)) = parse_filter_args(args).filter(|(others, _)| others.is_empty()) else {
+        bail!(tr!("cli-list-usage"));
+    };
+
+    let workspaces = session::load_workspaces()?;
+    for workspace in matching_workspaces(&workspaces, &filter) {
+        match workspace.tags.is_empty() {
+            true => println!("{}", workspace.label()),
+            false => println!("{}  #{}", workspace.label(), workspace.tags.join(" #")),
         }
     }
 
     Ok(())
 }
 
-fn update_last_workspace(workspaces: &mut Workspaces, last_workspace_id: Uuid) {
-    info!("Updating last workspace...");
-
-    match OpenTabs::read() {
-        Ok(open_tabs) => {
-            info!("Was able to open tabs.");
-            let mut last_workspace = Workspace::from(&open_tabs);
-
-            if workspaces.workspaces.contains_key(&last_workspace_id) {
-                info!(
-                    "Last workspace {} in saved workspace. Updating with latest.",
-                    last_workspace_id
-                );
-
-                last_workspace.uuid = last_workspace_id;
-                last_workspace.name = workspaces.workspaces[&last_workspace_id].name.clone();
-            } else {
-                info!(
-                    "Last workspace {} not in saved workspaces. Creating new workspace.",
-                    last_workspace_id
-                );
-
-                last_workspace.name = "Last Workspace".to_owned();
-            };
-
-            info!("The last workspace is {:?}", last_workspace);
-            workspaces
-                .workspaces
-                .insert(last_workspace.uuid, last_workspace);
-        }
-        Err(why) => {
-            error!(
-                "Couldn't open SourceTree's Open Tabs from last session. '{}'",
-                why
-            );
+/// `open [name] [--tag <tag>]... [--all]`, picking the one workspace that matches. The window then
+/// switches to it just as if it had been picked there, or asks the running one to.
+fn pick_open_workspace(args: &[String]) -> Result<Uuid, anyhow::Error> {
+    let Some((others, filter)) = parse_filter_args(args).filter(|(others, _)| others.len() <= 1)
+    else {
+        bail!(tr!("cli-open-usage"));
+    };
+    let name = others.first().map(String::as_str);
+
+    // Picked before anything else, so that a typo doesn't close SourceTree for nothing.
+    Ok(pick_workspace(&session::load_workspaces()?, name, &filter)?.uuid)
+}
+
+/// Splits `--tag <tag>` and `--all` from the other arguments. `None` when a `--tag` has no tag.
+fn parse_filter_args(args: &[String]) -> Option<(Vec<String>, WorkspaceFilter)> {
+    let mut others = Vec::new();
+    let mut tags = Vec::new();
+    let mut show_archived = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--tag" => tags.push(args.next()?.as_str()),
+            "--all" => show_archived = true,
+            _ => match arg.strip_prefix("--tag=") {
+                Some(tag) => tags.push(tag),
+                None => others.push(arg.clone()),
+            },
         }
     }
+
+    let mut filter = WorkspaceFilter::with_tags(tags);
+    filter.show_archived = show_archived;
+    Some((others, filter))
+}
+
+/// Carries on without a log rather than failing the invocation, e.g. SourceTree's custom action.
+fn setup_logging(verbose: bool) -> Option<LoggerHandle> {
+    let config = LocalConfig::read().unwrap_or_default().logging;
+    logging::start(&config, verbose)
+        .map_err(|why| eprintln!("Couldn't start logging. '{why}'"))
+        .ok()
 }
 
-fn launch_app(workspaces: Workspaces) -> Result<(), anyhow::Error> {
+fn launch_app(
+    workspaces: Workspaces,
+    resident: bool,
+    server: Option<InstanceServer>,
+    open_workspace: Option<Uuid>,
+) -> Result<(), anyhow::Error> {
+    let window = Preferences::read().unwrap_or_default().window;
     let options = eframe::NativeOptions {
-        initial_window_size: Some(egui::vec2(800.0, 600.0)),
-        min_window_size: Some(egui::vec2(640.0, 480.0)),
-        centered: true,
+        initial_window_size: Some(egui::vec2(window.width, window.height)),
+        initial_window_pos: window.position.map(egui::Pos2::from),
+        min_window_size: Some(MIN_WINDOW_SIZE.into()),
+        centered: window.position.is_none(),
         ..Default::default()
     };
 
     eframe::run_native(
         "SourceTree Workspaces App",
         options,
-        Box::new(|cc| Box::new(SourceTreeWorkspacesApp::new(cc, workspaces))),
+        Box::new(move |cc| {
+            Box::new(SourceTreeWorkspacesApp::new(
+                cc,
+                workspaces,
+                resident,
+                server,
+                open_workspace,
+            ))
+        }),
     )
-    .or_else(|_| bail!("Error running ui"))
+    .or_else(|_| bail!(tr!("cli-ui-failed")))
 }
@@ -1,9 +1,14 @@
-use anyhow::bail;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::paths::sourcetree_settings_path;
-use std::path::PathBuf;
+use crate::{
+    error::{parse_xml, read_file, write_file, Error},
+    paths::sourcetree_settings_path,
+    roots::PathRoots,
+    tr,
+    workspaces::Workspace,
+};
+use std::path::{Path, PathBuf};
 
 #[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename = "ArrayOfString")]
@@ -15,41 +20,49 @@ pub struct OpenTabs {
 
 impl OpenTabs {
     pub fn path() -> Option<PathBuf> {
-        let Some(settings_path) = sourcetree_settings_path() else {
-            return None;
-        };
-
-        Some(settings_path.join("opentabs.xml"))
+        Some(sourcetree_settings_path()?.join("opentabs.xml"))
     }
 
     pub fn write(open_tabs: &OpenTabs) -> anyhow::Result<()> {
-        let Some(path) = OpenTabs::path() else {
-            bail!("Error getting open tabs file path for reading.");
-        };
+        let path = OpenTabs::path().ok_or(Error::PathNotFound("open tabs file"))?;
 
         write_to_path(&path, open_tabs)?;
         Ok(())
     }
 
     pub fn read() -> anyhow::Result<OpenTabs> {
-        let Some(path) = OpenTabs::path() else {
-            bail!("Error getting open tabs file path for reading.");
-        };
+        let path = OpenTabs::path().ok_or(Error::PathNotFound("open tabs file"))?;
 
         let open_tabs = read_from_path(&path)?;
         Ok(open_tabs)
     }
+
+    /// The tabs that open the workspace on this machine.
+    pub fn for_workspace(workspace: &Workspace, roots: &PathRoots) -> Self {
+        OpenTabs {
+            tabs: workspace.resolved_repo_paths(roots),
+            workspace_id: Some(workspace.uuid),
+        }
+    }
+
+    /// A new workspace holding the tabs, under the id they were opened with when there is one.
+    pub fn to_workspace(&self, roots: &PathRoots) -> Workspace {
+        let uuid = self.workspace_id.unwrap_or_else(Uuid::new_v4);
+        let mut workspace = Workspace::new(&tr!("workspace-new-name"), uuid);
+        workspace.set_repo_paths_from_tabs(&self.tabs, roots);
+        workspace
+    }
 }
 
-fn write_to_path(path: &PathBuf, open_tabs: &OpenTabs) -> anyhow::Result<()> {
+fn write_to_path(path: &Path, open_tabs: &OpenTabs) -> anyhow::Result<()> {
     let contents = serde_xml_rs::to_string(&open_tabs)?;
-    std::fs::write(path, contents)?;
+    write_file(path, contents)?;
     Ok(())
 }
 
-fn read_from_path(path: &PathBuf) -> anyhow::Result<OpenTabs> {
-    let contents = std::fs::read_to_string(path)?;
-    let open_tabs: OpenTabs = serde_xml_rs::from_str(&contents)?;
+fn read_from_path(path: &Path) -> anyhow::Result<OpenTabs> {
+    let contents = read_file(path)?;
+    let open_tabs: OpenTabs = parse_xml(path, &contents)?;
     Ok(open_tabs)
 }
 
@@ -10,6 +10,10 @@ pub fn sourcetree_settings_path() -> Option<PathBuf> {
     None
 }
 
+pub fn log_dir_path() -> Option<PathBuf> {
+    Some(sourcetree_settings_path()?.join("log"))
+}
+
 pub fn sourcetree_exec_path() -> Option<PathBuf> {
     if let Some(base_dirs) = BaseDirs::new() {
         let data_local_dir_path = base_dirs.data_local_dir();
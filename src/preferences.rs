@@ -0,0 +1,369 @@
+use std::{path::PathBuf, process::Command, time::Duration};
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    error::{is_not_found, parse_json, read_file, write_file, Error},
+    i18n::shipped_language,
+    launchers::Launcher,
+    paths::{sourcetree_exec_path, sourcetree_settings_path},
+    tr,
+};
+
+pub const MIN_FONT_SCALE: f32 = 0.5;
+pub const MAX_FONT_SCALE: f32 = 3.0;
+pub const MIN_WINDOW_SIZE: [f32; 2] = [640.0, 480.0];
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    /// Light or dark to match the OS.
+    System,
+}
+
+impl std::fmt::Display for Theme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let key = match self {
+            Theme::Dark => "theme-dark",
+            Theme::Light => "theme-light",
+            Theme::HighContrast => "theme-high-contrast",
+            Theme::System => "theme-system",
+        };
+        write!(f, "{}", tr!(key))
+    }
+}
+
+impl Theme {
+    pub fn all() -> [Theme; 4] {
+        [
+            Theme::Dark,
+            Theme::Light,
+            Theme::HighContrast,
+            Theme::System,
+        ]
+    }
+
+    /// The theme to draw with, given whether the OS is in dark mode. Dark when that's unknown.
+    pub fn resolve(self, system_dark: Option<bool>) -> Theme {
+        match self {
+            Theme::System if system_dark == Some(false) => Theme::Light,
+            Theme::System => Theme::Dark,
+            theme => theme,
+        }
+    }
+}
+
+/// Whether the OS is set to dark mode, when that can be found out.
+pub fn system_prefers_dark() -> Option<bool> {
+    if cfg!(windows) {
+        let mut command = Command::new("reg");
+        command.args([
+            "query",
+            "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize",
+            "/v",
+            "AppsUseLightTheme",
+        ]);
+        hide_window(&mut command);
+        let output = command.output().ok()?;
+        parse_apps_use_light_theme(&String::from_utf8_lossy(&output.stdout)).map(|light| !light)
+    } else {
+        let output = Command::new("gsettings")
+            .args(["get", "org.gnome.desktop.interface", "color-scheme"])
+            .output()
+            .ok()?;
+        let scheme = String::from_utf8_lossy(&output.stdout);
+        output.status.success().then(|| scheme.contains("dark"))
+    }
+}
+
+/// Picks the value out of `reg query`'s output, such as `AppsUseLightTheme    REG_DWORD    0x0`.
+fn parse_apps_use_light_theme(output: &str) -> Option<bool> {
+    let line = output
+        .lines()
+        .find(|line| line.trim_start().starts_with("AppsUseLightTheme"))?;
+    let value = line.split_whitespace().last()?;
+    let value = u32::from_str_radix(value.trim_start_matches("0x"), 16).ok()?;
+    Some(value != 0)
+}
+
+#[cfg(windows)]
+fn hide_window(command: &mut Command) {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    command.creation_flags(CREATE_NO_WINDOW);
+}
+
+#[cfg(not(windows))]
+fn hide_window(_command: &mut Command) {}
+
+/// Where the window opens. Without a position it's centred.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct WindowGeometry {
+    #[serde(default = "default_width")]
+    pub width: f32,
+    #[serde(default = "default_height")]
+    pub height: f32,
+    #[serde(default)]
+    pub position: Option<[f32; 2]>,
+}
+
+impl Default for WindowGeometry {
+    fn default() -> Self {
+        Self {
+            width: default_width(),
+            height: default_height(),
+            position: None,
+        }
+    }
+}
+
+/// How the app looks and behaves, picked in Settings or with `st-workspaces settings`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Preferences {
+    #[serde(default)]
+    pub theme: Theme,
+    /// Multiplies every text size.
+    #[serde(default = "default_font_scale")]
+    pub font_scale: f32,
+    #[serde(default)]
+    pub window: WindowGeometry,
+    /// How long to wait for SourceTree to save its tabs after being closed.
+    #[serde(default = "default_close_timeout_secs")]
+    pub close_timeout_secs: u64,
+    #[serde(default = "confirm_delete_by_default")]
+    pub confirm_delete: bool,
+    /// SourceTree's executable, when it isn't installed in the usual place.
+    #[serde(default)]
+    pub exec_path: Option<PathBuf>,
+    /// What a workspace is opened in when it doesn't list its own launchers.
+    #[serde(default = "default_launcher")]
+    pub default_launcher: Launcher,
+    /// The language to show, such as `de`. The OS's language when unset.
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            theme: Theme::default(),
+            font_scale: default_font_scale(),
+            window: WindowGeometry::default(),
+            close_timeout_secs: default_close_timeout_secs(),
+            confirm_delete: confirm_delete_by_default(),
+            exec_path: None,
+            default_launcher: default_launcher(),
+            language: None,
+        }
+    }
+}
+
+impl Preferences {
+    pub fn path() -> Option<PathBuf> {
+        Some(sourcetree_settings_path()?.join("st-workspaces-preferences.json"))
+    }
+
+    pub fn read() -> anyhow::Result<Preferences> {
+        let path = Preferences::path().ok_or(Error::PathNotFound("preferences file"))?;
+        let contents = read_file(&path)?;
+        let preferences: Preferences = parse_json(&path, &contents)?;
+        Ok(preferences)
+    }
+
+    /// Like [`Preferences::read`], but preferences that haven't been saved yet are the defaults.
+    /// A file that can't be parsed is still an error, so it isn't saved over.
+    pub fn read_or_default() -> anyhow::Result<Preferences> {
+        match Preferences::read() {
+            Err(why) if is_not_found(&why) => Ok(Preferences::default()),
+            result => result,
+        }
+    }
+
+    pub fn write(&self) -> anyhow::Result<()> {
+        let path = Preferences::path().ok_or(Error::PathNotFound("preferences file"))?;
+        let contents = serde_json::to_string_pretty(self)?;
+        write_file(&path, contents)?;
+        Ok(())
+    }
+
+    pub fn close_timeout(&self) -> Duration {
+        Duration::from_secs(self.close_timeout_secs)
+    }
+
+    pub fn sourcetree_exec_path(&self) -> Option<PathBuf> {
+        self.exec_path.clone().or_else(sourcetree_exec_path)
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !(MIN_FONT_SCALE..=MAX_FONT_SCALE).contains(&self.font_scale) {
+            bail!(tr!(
+                "settings-error-font-scale",
+                min = MIN_FONT_SCALE,
+                max = MAX_FONT_SCALE
+            ));
+        }
+
+        if self.window.width < MIN_WINDOW_SIZE[0] || self.window.height < MIN_WINDOW_SIZE[1] {
+            bail!(tr!(
+                "settings-error-window-size",
+                width = MIN_WINDOW_SIZE[0],
+                height = MIN_WINDOW_SIZE[1]
+            ));
+        }
+
+        if let Some(language) = self
+            .language
+            .as_deref()
+            .filter(|language| shipped_language(language).is_none())
+        {
+            bail!(tr!("settings-error-language", language = language));
+        }
+
+        Ok(())
+    }
+
+    /// A setting by its dotted name, such as `window.width`.
+    pub fn get(&self, key: &str) -> anyhow::Result<Value> {
+        let value = serde_json::to_value(self)?;
+        match lookup(&value, key) {
+            Some(value) => Ok(value.clone()),
+            None => bail!(tr!("settings-error-unknown", key = key)),
+        }
+    }
+
+    /// Sets a setting from the command line. The value is read as JSON, and failing that as a
+    /// string or a launcher's kind, so `light` and `vs_code` work without quotes.
+    pub fn set(&mut self, key: &str, value: &str) -> anyhow::Result<()> {
+        let mut candidates = Vec::new();
+        if let Ok(value) = serde_json::from_str::<Value>(value) {
+            candidates.push(value);
+        }
+        candidates.push(Value::String(value.to_owned()));
+        candidates.push(serde_json::json!({ "kind": value }));
+
+        let current = serde_json::to_value(&*self)?;
+        lookup(&current, key)
+            .ok_or_else(|| anyhow::anyhow!(tr!("settings-error-unknown", key = key)))?;
+
+        for candidate in candidates {
+            let mut updated = current.clone();
+            *lookup_mut(&mut updated, key).expect("checked above") = candidate;
+            if let Ok(preferences) = serde_json::from_value::<Preferences>(updated) {
+                preferences.validate()?;
+                *self = preferences;
+                return Ok(());
+            }
+        }
+
+        bail!(tr!("settings-error-value", value = value, key = key))
+    }
+
+    /// Puts a setting back to its default.
+    pub fn reset(&mut self, key: &str) -> anyhow::Result<()> {
+        let default = Preferences::default().get(key)?;
+        self.set(key, &default.to_string())
+    }
+}
+
+fn lookup<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.')
+        .try_fold(value, |value, part| value.get(part))
+}
+
+fn lookup_mut<'a>(value: &'a mut Value, key: &str) -> Option<&'a mut Value> {
+    key.split('.')
+        .try_fold(value, |value, part| value.get_mut(part))
+}
+
+fn default_width() -> f32 {
+    800.0
+}
+
+fn default_height() -> f32 {
+    600.0
+}
+
+fn default_font_scale() -> f32 {
+    1.0
+}
+
+fn default_close_timeout_secs() -> u64 {
+    4
+}
+
+fn confirm_delete_by_default() -> bool {
+    true
+}
+
+fn default_launcher() -> Launcher {
+    Launcher::SourceTree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_default_missing_settings() {
+        let preferences: Preferences = serde_json::from_str(r#"{ "theme": "light" }"#).unwrap();
+
+        assert_eq!(preferences.theme, Theme::Light);
+        assert_eq!(preferences.close_timeout(), Duration::from_secs(4));
+        assert_eq!(preferences.default_launcher, Launcher::SourceTree);
+    }
+
+    #[test]
+    fn should_set_settings_from_command_line() {
+        let mut preferences = Preferences::default();
+
+        preferences.set("window.width", "1024").unwrap();
+        preferences.set("theme", "light").unwrap();
+        preferences.set("default_launcher", "vs_code").unwrap();
+        preferences.set("language", "de").unwrap();
+        preferences
+            .set("exec_path", "C:\\SourceTree\\SourceTree.exe")
+            .unwrap();
+
+        assert_eq!(preferences.window.width, 1024.0);
+        assert_eq!(preferences.theme, Theme::Light);
+        assert_eq!(preferences.default_launcher, Launcher::VsCode);
+        assert_eq!(preferences.language.as_deref(), Some("de"));
+        assert_eq!(
+            preferences.sourcetree_exec_path(),
+            Some(PathBuf::from("C:\\SourceTree\\SourceTree.exe"))
+        );
+
+        preferences.reset("exec_path").unwrap();
+        assert_eq!(preferences.exec_path, None);
+    }
+
+    #[test]
+    fn should_follow_system_theme() {
+        let output = "\r\nHKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize\r\n    AppsUseLightTheme    REG_DWORD    0x0\r\n";
+
+        assert_eq!(parse_apps_use_light_theme(output), Some(false));
+        assert_eq!(Theme::System.resolve(Some(false)), Theme::Light);
+        assert_eq!(Theme::System.resolve(None), Theme::Dark);
+        assert_eq!(
+            Theme::HighContrast.resolve(Some(false)),
+            Theme::HighContrast
+        );
+    }
+
+    #[test]
+    fn should_reject_bad_settings() {
+        let mut preferences = Preferences::default();
+
+        assert!(preferences.set("colour", "red").is_err());
+        assert!(preferences.set("theme", "purple").is_err());
+        assert!(preferences.set("font_scale", "10").is_err());
+        assert!(preferences.set("language", "xx").is_err());
+        assert_eq!(preferences, Preferences::default());
+    }
+}
@@ -0,0 +1,218 @@
+use std::{
+    collections::BTreeMap,
+    io::Read,
+    path::Path,
+    process::{Command, Stdio},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+use crate::error::Error;
+
+/// How long to wait for output after a process exits, in case something it started still holds
+/// its pipes open.
+const OUTPUT_GRACE: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessOutput {
+    /// The exit code, missing when the process timed out or was killed by a signal.
+    pub code: Option<i32>,
+    pub timed_out: bool,
+    /// Standard output followed by standard error.
+    pub output: String,
+}
+
+impl ProcessOutput {
+    pub fn succeeded(&self) -> bool {
+        self.code == Some(0)
+    }
+}
+
+/// Builds a command that runs a whole command line through the platform's shell, so scripts,
+/// pipes and quoting behave as they would when typed.
+pub fn shell_command(
+    command_line: &str,
+    working_dir: Option<&Path>,
+    env: &BTreeMap<String, String>,
+) -> Command {
+    // cmd doesn't unescape arguments the way programs built on the C runtime do, so the command
+    // line is passed on exactly as written.
+    #[cfg(windows)]
+    let mut command = {
+        use std::os::windows::process::CommandExt;
+
+        let mut command = Command::new("cmd");
+        command.arg("/C").raw_arg(command_line);
+        command
+    };
+    #[cfg(not(windows))]
+    let mut command = {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(command_line);
+        command
+    };
+
+    if let Some(working_dir) = working_dir {
+        command.current_dir(working_dir);
+    }
+
+    command.envs(env);
+    command
+}
+
+/// Starts the command and leaves it running, for things like editors and terminals.
+pub fn spawn_detached(command: &mut Command) -> anyhow::Result<()> {
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|why| Error::launch(command, why))?;
+    Ok(())
+}
+
+/// Runs the command to completion, killing it if it takes longer than `timeout`.
+pub fn run_with_timeout(command: &mut Command, timeout: Duration) -> anyhow::Result<ProcessOutput> {
+    // Its own group, so everything it starts can be killed with it.
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(command, 0);
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|why| Error::launch(command, why))?;
+
+    let (sender, receiver) = mpsc::channel();
+    for mut pipe in [
+        child
+            .stdout
+            .take()
+            .map(|pipe| Box::new(pipe) as Box<dyn Read + Send>),
+        child
+            .stderr
+            .take()
+            .map(|pipe| Box::new(pipe) as Box<dyn Read + Send>),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        let sender = sender.clone();
+        std::thread::spawn(move || {
+            let mut buffer = Vec::new();
+            let _ = pipe.read_to_end(&mut buffer);
+            let _ = sender.send(String::from_utf8_lossy(&buffer).into_owned());
+        });
+    }
+    drop(sender);
+
+    let start_time = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+
+        if start_time.elapsed() > timeout {
+            kill_tree(child.id());
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+
+        std::thread::sleep(Duration::from_millis(50));
+    };
+
+    // Whatever was written before a timeout is kept too, since it's usually why it hung.
+    let mut output = String::new();
+    while let Ok(text) = receiver.recv_timeout(OUTPUT_GRACE) {
+        output.push_str(&text);
+    }
+
+    Ok(ProcessOutput {
+        code: status.and_then(|status| status.code()),
+        timed_out: status.is_none(),
+        output,
+    })
+}
+
+/// Kills what the shell started along with it, since killing the shell alone leaves its children
+/// running with the pipes open.
+#[cfg(windows)]
+fn kill_tree(pid: u32) {
+    let _ = Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &pid.to_string()])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+#[cfg(unix)]
+fn kill_tree(pid: u32) {
+    let _ = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{pid}")])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+#[cfg(not(any(windows, unix)))]
+fn kill_tree(_pid: u32) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_capture_output() {
+        let mut env = BTreeMap::new();
+        env.insert("ST_WORKSPACES_TEST".to_owned(), "hello".to_owned());
+        let command_line = match cfg!(windows) {
+            true => "echo %ST_WORKSPACES_TEST%",
+            false => "echo $ST_WORKSPACES_TEST",
+        };
+
+        let mut command = shell_command(command_line, None, &env);
+        let output = run_with_timeout(&mut command, Duration::from_secs(10)).unwrap();
+        assert!(output.succeeded());
+        assert_eq!(output.output.trim(), "hello");
+    }
+
+    #[test]
+    fn should_kill_process_after_timeout() {
+        let command_line = match cfg!(windows) {
+            true => "ping -n 6 127.0.0.1 > nul",
+            false => "sleep 5",
+        };
+
+        let mut command = shell_command(command_line, None, &BTreeMap::new());
+        let output = run_with_timeout(&mut command, Duration::from_millis(200)).unwrap();
+        assert!(output.timed_out);
+        assert!(!output.succeeded());
+    }
+
+    #[test]
+    fn should_keep_output_written_before_timeout() {
+        let command_line = match cfg!(windows) {
+            true => "echo started && ping -n 6 127.0.0.1 > nul",
+            false => "echo started && sleep 5",
+        };
+
+        let mut command = shell_command(command_line, None, &BTreeMap::new());
+        let output = run_with_timeout(&mut command, Duration::from_millis(500)).unwrap();
+        assert!(output.timed_out);
+        assert_eq!(output.output.trim(), "started");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn should_pass_quoted_arguments_as_written() {
+        let command_line = r#"if "a b"=="a b" echo same"#;
+        let mut command = shell_command(command_line, None, &BTreeMap::new());
+        let output = run_with_timeout(&mut command, Duration::from_secs(10)).unwrap();
+        assert!(output.succeeded());
+        assert_eq!(output.output.trim(), "same");
+    }
+}
@@ -0,0 +1,246 @@
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::workspaces::{Workspace, WorkspaceSource};
+
+pub const PROJECT_FILE_NAME: &str = ".st-workspace";
+
+/// The settings shared through a project file. Snapshots, stashes and exports belong to one
+/// machine, so they stay in `st-workspaces.json`.
+const SHARED_SETTINGS: [&str; 8] = [
+    "notes",
+    "color",
+    "icon",
+    "tags",
+    "rules",
+    "hooks",
+    "environment",
+    "launchers",
+];
+
+/// A workspace committed inside a repository, listing its sibling repos relative to itself. Its
+/// other shared settings, such as rules, hooks and notes, are kept as they are in
+/// `st-workspaces.json`.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+struct ProjectFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    uuid: Option<Uuid>,
+    name: String,
+    #[serde(default)]
+    repo_paths: Vec<String>,
+    #[serde(flatten)]
+    settings: Map<String, Value>,
+}
+
+pub fn project_file_path(repo_path: &Path) -> PathBuf {
+    repo_path.join(PROJECT_FILE_NAME)
+}
+
+pub fn has_project_file(repo_path: &Path) -> bool {
+    project_file_path(repo_path).is_file()
+}
+
+/// Reads the repo's `.st-workspace`. Relative repo paths are resolved against the repo, and a file
+/// without an id gets one derived from its location, so it stays the same between runs.
+pub fn read_project_workspace(repo_path: &Path) -> anyhow::Result<Workspace> {
+    let contents = std::fs::read_to_string(project_file_path(repo_path))?;
+    let project_file: ProjectFile = serde_json::from_str(&contents)?;
+    let uuid = project_file.uuid.unwrap_or_else(|| {
+        Uuid::new_v5(
+            &Uuid::NAMESPACE_URL,
+            project_file_path(repo_path).to_string_lossy().as_bytes(),
+        )
+    });
+
+    let repo_paths: Vec<String> = project_file
+        .repo_paths
+        .iter()
+        .map(|path| resolve_path(repo_path, path))
+        .collect();
+
+    let mut settings = project_file.settings;
+    settings.retain(|key, _| SHARED_SETTINGS.contains(&key.as_str()));
+    settings.insert("uuid".to_owned(), serde_json::to_value(uuid)?);
+    settings.insert("name".to_owned(), Value::String(project_file.name));
+    settings.insert("repo_paths".to_owned(), serde_json::to_value(repo_paths)?);
+    let mut workspace: Workspace = serde_json::from_value(Value::Object(settings))?;
+    workspace.source = WorkspaceSource::Project(repo_path.to_owned());
+    Ok(workspace)
+}
+
+/// Writes the workspace back to its repo's `.st-workspace`, keeping paths relative where possible.
+/// The file is left alone when nothing changed, so the repo doesn't show spurious edits.
+pub fn write_project_workspace(workspace: &Workspace, repo_path: &Path) -> anyhow::Result<()> {
+    let existing_uuid = std::fs::read_to_string(project_file_path(repo_path))
+        .ok()
+        .and_then(|contents| serde_json::from_str::<ProjectFile>(&contents).ok())
+        .and_then(|project_file| project_file.uuid);
+
+    let Value::Object(mut settings) = serde_json::to_value(workspace)? else {
+        bail!("A workspace should serialize to an object.");
+    };
+    settings.retain(|key, _| SHARED_SETTINGS.contains(&key.as_str()));
+
+    let project_file = ProjectFile {
+        uuid: existing_uuid,
+        name: workspace.name.clone(),
+        repo_paths: workspace
+            .repo_paths
+            .iter()
+            .map(|path| relative_path(repo_path, path))
+            .collect(),
+        settings,
+    };
+
+    let contents = serde_json::to_string_pretty(&project_file)?;
+    let path = project_file_path(repo_path);
+    if std::fs::read_to_string(&path).ok().as_deref() == Some(contents.as_str()) {
+        return Ok(());
+    }
+
+    std::fs::write(path, contents)?;
+    Ok(())
+}
+
+fn resolve_path(repo_path: &Path, path: &str) -> String {
+    if path.starts_with("${") || Path::new(path).is_absolute() {
+        return path.to_owned();
+    }
+
+    normalize(&repo_path.join(path)).display().to_string()
+}
+
+fn relative_path(repo_path: &Path, path: &str) -> String {
+    if path.starts_with("${") {
+        return path.to_owned();
+    }
+
+    let path = normalize(Path::new(path));
+    let repo_path = normalize(repo_path);
+    if path == repo_path {
+        return ".".to_owned();
+    }
+
+    if let Ok(inside) = path.strip_prefix(&repo_path) {
+        return inside.display().to_string().replace('\\', "/");
+    }
+
+    if let Some(sibling) = repo_path
+        .parent()
+        .and_then(|parent| path.strip_prefix(parent).ok())
+    {
+        return format!("../{}", sibling.display()).replace('\\', "/");
+    }
+
+    path.display().to_string()
+}
+
+/// Removes `.` and `..` components without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dirty_guard::PendingStash;
+
+    fn create_test_repo() -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("st-workspaces-project-{}", Uuid::new_v4()))
+            .join("project-one");
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn should_resolve_sibling_repos() -> anyhow::Result<()> {
+        let repo_path = create_test_repo();
+        std::fs::write(
+            project_file_path(&repo_path),
+            r#"{ "name": "Project", "repo_paths": [".", "../project-two", "${CODE}/tools"] }"#,
+        )?;
+
+        let workspace = read_project_workspace(&repo_path)?;
+        let parent = repo_path.parent().unwrap();
+        assert_eq!(workspace.name, "Project");
+        assert_eq!(workspace.repo_paths[0], repo_path.display().to_string());
+        assert_eq!(
+            workspace.repo_paths[1],
+            parent.join("project-two").display().to_string()
+        );
+        assert_eq!(workspace.repo_paths[2], "${CODE}/tools");
+        assert_eq!(workspace.uuid, read_project_workspace(&repo_path)?.uuid);
+        Ok(())
+    }
+
+    #[test]
+    fn should_write_relative_paths() -> anyhow::Result<()> {
+        let repo_path = create_test_repo();
+        let parent = repo_path.parent().unwrap();
+        let mut workspace = Workspace::new("Project", Uuid::new_v4());
+        workspace.repo_paths.push(repo_path.display().to_string());
+        workspace
+            .repo_paths
+            .push(parent.join("project-two").display().to_string());
+
+        write_project_workspace(&workspace, &repo_path)?;
+        let contents = std::fs::read_to_string(project_file_path(&repo_path))?;
+        let project_file: ProjectFile = serde_json::from_str(&contents)?;
+        assert_eq!(project_file.repo_paths, vec![".", "../project-two"]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_keep_workspace_settings() -> anyhow::Result<()> {
+        let repo_path = create_test_repo();
+        std::fs::write(
+            project_file_path(&repo_path),
+            r#"{ "name": "Project", "repo_paths": ["."] }"#,
+        )?;
+
+        let mut workspace = read_project_workspace(&repo_path)?;
+        workspace.notes = "Build with `make`.".to_owned();
+        workspace.add_tag("client-a");
+        workspace
+            .environment
+            .insert("GIT_SSH_COMMAND".to_owned(), "ssh -i key".to_owned());
+        write_project_workspace(&workspace, &repo_path)?;
+
+        assert_eq!(read_project_workspace(&repo_path)?, workspace);
+        Ok(())
+    }
+
+    #[test]
+    fn should_keep_local_state_out_of_project_file() -> anyhow::Result<()> {
+        let repo_path = create_test_repo();
+        let mut workspace = Workspace::new("Project", Uuid::new_v4());
+        workspace.repo_paths.push(repo_path.display().to_string());
+        workspace.pending_stashes.push(PendingStash {
+            repo_path: repo_path.display().to_string(),
+            message: "st-workspaces: Project".to_owned(),
+        });
+        write_project_workspace(&workspace, &repo_path)?;
+
+        let contents = std::fs::read_to_string(project_file_path(&repo_path))?;
+        assert!(!contents.contains("pending_stashes"));
+        assert!(read_project_workspace(&repo_path)?
+            .pending_stashes
+            .is_empty());
+        Ok(())
+    }
+}
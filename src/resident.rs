@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    tr,
+    workspaces::{Workspace, Workspaces},
+};
+
+pub const DEFAULT_HOTKEY: &str = "Ctrl+Alt+W";
+
+/// Settings for staying in the tray between switches instead of closing.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ResidentConfig {
+    /// Start resident without needing `--resident`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Shows the quick switcher from anywhere, written like `Ctrl+Alt+W`.
+    #[serde(default = "default_hotkey")]
+    pub hotkey: String,
+    #[serde(default = "notify_by_default")]
+    pub notify_on_switch: bool,
+}
+
+impl Default for ResidentConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hotkey: default_hotkey(),
+            notify_on_switch: notify_by_default(),
+        }
+    }
+}
+
+/// The workspaces listed in the tray menu, by name.
+pub fn tray_entries(workspaces: &Workspaces) -> Vec<(Uuid, String)> {
+    let mut entries: Vec<_> = workspaces
+        .workspaces
+        .values()
+        .map(|workspace| (workspace.uuid, workspace.name.clone()))
+        .collect();
+    entries.sort_by(|a, b| {
+        a.1.to_lowercase()
+            .cmp(&b.1.to_lowercase())
+            .then(a.0.cmp(&b.0))
+    });
+    entries
+}
+
+/// The notification shown once a switch has finished.
+pub fn switch_notification(workspace: &Workspace, repo_count: usize) -> String {
+    tr!(
+        "notification-switched",
+        name = workspace.name.as_str(),
+        count = repo_count
+    )
+}
+
+fn default_hotkey() -> String {
+    DEFAULT_HOTKEY.to_owned()
+}
+
+fn notify_by_default() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_list_tray_entries_by_name() {
+        let mut workspaces = Workspaces::default();
+        for name in ["beta", "Alpha", "gamma"] {
+            let workspace = Workspace::new(name, Uuid::new_v4());
+            workspaces.workspaces.insert(workspace.uuid, workspace);
+        }
+
+        let names: Vec<_> = tray_entries(&workspaces)
+            .into_iter()
+            .map(|(_, name)| name)
+            .collect();
+        assert_eq!(names, vec!["Alpha", "beta", "gamma"]);
+    }
+
+    #[test]
+    fn should_default_missing_resident_settings() {
+        let config: ResidentConfig = serde_json::from_str(r#"{ "enabled": true }"#).unwrap();
+        assert_eq!(config.hotkey, DEFAULT_HOTKEY);
+        assert!(config.notify_on_switch);
+    }
+}
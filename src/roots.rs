@@ -0,0 +1,244 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, MAIN_SEPARATOR};
+
+use serde::{Deserialize, Serialize};
+
+/// Named path roots, like `CODE -> D:\src`, so repo paths can be written as `${CODE}/project-one`
+/// and mean the right thing on every machine.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct PathRoots {
+    roots: BTreeMap<String, String>,
+}
+
+impl PathRoots {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.roots.get(name).map(|root| root.as_str())
+    }
+
+    pub fn insert(&mut self, name: &str, path: &str) {
+        self.roots.insert(name.to_owned(), path.to_owned());
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.roots.remove(name);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.roots.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Replaces a leading `${NAME}` with the root's local path. Paths without a known root are
+    /// returned unchanged.
+    pub fn expand(&self, path: &str) -> String {
+        let Some((name, rest)) = split_root_variable(path) else {
+            return path.to_owned();
+        };
+
+        let Some(root) = self.roots.get(name) else {
+            return path.to_owned();
+        };
+
+        let root = root.trim_end_matches(['/', '\\']);
+        let rest: String = rest
+            .chars()
+            .map(|c| {
+                if c == '/' || c == '\\' {
+                    MAIN_SEPARATOR
+                } else {
+                    c
+                }
+            })
+            .collect();
+
+        format!("{root}{rest}")
+    }
+
+    /// Rewrites an absolute path as `${NAME}/...` using the longest matching root. Paths outside
+    /// every root are returned unchanged.
+    pub fn contract(&self, path: &str) -> String {
+        if split_root_variable(path).is_some() {
+            return path.to_owned();
+        }
+
+        let best_match = self
+            .roots
+            .iter()
+            .filter_map(|(name, root)| {
+                let root = root.trim_end_matches(['/', '\\']);
+                strip_path_prefix(path, root).map(|rest| (name, root.len(), rest))
+            })
+            .max_by_key(|(_, root_len, _)| *root_len);
+
+        match best_match {
+            Some((name, _, rest)) => {
+                let rest = rest.replace('\\', "/");
+                format!("${{{name}}}{rest}")
+            }
+            None => path.to_owned(),
+        }
+    }
+}
+
+/// A bulk rewrite of one path prefix into another, used when importing workspaces made on a
+/// different machine.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PathRemap {
+    pub from: String,
+    pub to: String,
+}
+
+impl PathRemap {
+    pub fn new(from: &str, to: &str) -> Self {
+        Self {
+            from: from.to_owned(),
+            to: to.to_owned(),
+        }
+    }
+
+    pub fn apply(&self, path: &str) -> Option<String> {
+        let from = self.from.trim_end_matches(['/', '\\']);
+        if from.is_empty() {
+            return None;
+        }
+
+        let rest = strip_path_prefix(path, from)?;
+        let to = self.to.trim_end_matches(['/', '\\']);
+        Some(format!("{to}{rest}"))
+    }
+}
+
+/// Rewrites each path with the first remap that matches it.
+pub fn remap_paths(paths: &mut [String], remaps: &[PathRemap]) {
+    for path in paths.iter_mut() {
+        if let Some(remapped) = remaps.iter().find_map(|remap| remap.apply(path)) {
+            *path = remapped;
+        }
+    }
+}
+
+/// The distinct parent directories of the given paths, which are the prefixes most worth
+/// remapping.
+pub fn parent_prefixes<'a>(paths: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let prefixes: BTreeSet<String> = paths
+        .filter_map(|path| {
+            let trimmed = path.trim_end_matches(['/', '\\']);
+            let index = trimmed.rfind(['/', '\\'])?;
+            Some(trimmed[..index].to_owned())
+        })
+        .filter(|prefix| !prefix.is_empty())
+        .collect();
+
+    prefixes.into_iter().collect()
+}
+
+/// Whether the path, once expanded, exists on this machine.
+pub fn expanded_path_exists(roots: &PathRoots, path: &str) -> bool {
+    Path::new(&roots.expand(path)).exists()
+}
+
+fn split_root_variable(path: &str) -> Option<(&str, &str)> {
+    let rest = path.strip_prefix("${")?;
+    let end = rest.find('}')?;
+    Some((&rest[..end], &rest[end + 1..]))
+}
+
+/// Strips `prefix` when it matches whole path components. Comparison ignores case and separator
+/// style, as the paths may come from Windows.
+fn strip_path_prefix<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    if path.len() < prefix.len() || !path.is_char_boundary(prefix.len()) {
+        return None;
+    }
+
+    let (head, rest) = path.split_at(prefix.len());
+    let same_head = head
+        .chars()
+        .zip(prefix.chars())
+        .all(|(a, b)| normalize_char(a) == normalize_char(b));
+
+    if same_head && (rest.is_empty() || rest.starts_with(['/', '\\'])) {
+        Some(rest)
+    } else {
+        None
+    }
+}
+
+fn normalize_char(c: char) -> char {
+    match c {
+        '\\' => '/',
+        c => c.to_ascii_lowercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_roots() -> PathRoots {
+        let mut roots = PathRoots::default();
+        roots.insert("CODE", r"D:\src");
+        roots.insert("CLIENT", r"D:\src\clients\client-a\");
+        roots
+    }
+
+    #[test]
+    fn should_expand_root_variable() {
+        let roots = create_test_roots();
+        let expanded = roots.expand("${CODE}/project-one");
+        assert_eq!(expanded, format!(r"D:\src{MAIN_SEPARATOR}project-one"));
+    }
+
+    #[test]
+    fn should_leave_unknown_roots_alone() {
+        let roots = create_test_roots();
+        assert_eq!(roots.expand("${HOME}/project"), "${HOME}/project");
+        assert_eq!(roots.expand(r"C:\dev\project"), r"C:\dev\project");
+    }
+
+    #[test]
+    fn should_contract_with_longest_root() {
+        let roots = create_test_roots();
+        assert_eq!(roots.contract(r"D:\src\project-one"), "${CODE}/project-one");
+        assert_eq!(
+            roots.contract(r"d:\SRC\clients\client-a\api"),
+            "${CLIENT}/api"
+        );
+        assert_eq!(
+            roots.contract(r"D:\srcother\project"),
+            r"D:\srcother\project"
+        );
+    }
+
+    #[test]
+    fn should_remap_prefixes_in_bulk() {
+        let mut paths = vec![
+            r"C:\dev\project-one".to_owned(),
+            r"C:\dev\tools\project-two".to_owned(),
+            "/home/me/elsewhere".to_owned(),
+        ];
+        let remaps = vec![
+            PathRemap::new(r"C:\dev\tools", "${TOOLS}"),
+            PathRemap::new(r"C:\dev\", "${CODE}"),
+        ];
+
+        remap_paths(&mut paths, &remaps);
+        assert_eq!(paths[0], r"${CODE}\project-one");
+        assert_eq!(paths[1], r"${TOOLS}\project-two");
+        assert_eq!(paths[2], "/home/me/elsewhere");
+    }
+
+    #[test]
+    fn should_find_parent_prefixes() {
+        let paths = [
+            r"C:\dev\project-one".to_owned(),
+            r"C:\dev\project-two\".to_owned(),
+            "/home/me/repo".to_owned(),
+        ];
+        let prefixes = parent_prefixes(paths.iter());
+        assert_eq!(prefixes, vec!["/home/me".to_owned(), r"C:\dev".to_owned()]);
+    }
+}
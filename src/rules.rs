@@ -0,0 +1,192 @@
+use std::{path::PathBuf, sync::atomic::AtomicBool};
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bookmarks::Bookmarks,
+    git::run_git,
+    roots::PathRoots,
+    scanner::{matches_pattern, scan, ScanOptions},
+    tr,
+};
+
+/// A rule that works out some of a workspace's repos when it is opened, instead of storing them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WorkspaceRule {
+    /// Every repo below a directory.
+    ReposUnder {
+        root: String,
+        #[serde(default = "default_max_depth")]
+        max_depth: usize,
+    },
+    /// Every repo bookmarked in a SourceTree bookmarks folder.
+    BookmarkFolder { folder: String },
+    /// Bookmarked repos with a remote URL matching a pattern, like `github.com/org/*`.
+    RemoteMatches { pattern: String },
+}
+
+impl WorkspaceRule {
+    pub fn description(&self) -> String {
+        match self {
+            WorkspaceRule::ReposUnder { root, max_depth } => {
+                tr!("rule-repos-under", root = root.as_str(), depth = *max_depth)
+            }
+            WorkspaceRule::BookmarkFolder { folder } => {
+                tr!("rule-bookmark-folder", folder = folder.as_str())
+            }
+            WorkspaceRule::RemoteMatches { pattern } => {
+                tr!("rule-remote-matches", pattern = pattern.as_str())
+            }
+        }
+    }
+
+    /// The repo paths the rule currently picks out.
+    pub fn evaluate(&self, roots: &PathRoots) -> Vec<String> {
+        match self {
+            WorkspaceRule::ReposUnder { root, max_depth } => {
+                let options = ScanOptions {
+                    root: PathBuf::from(roots.expand(root)),
+                    max_depth: *max_depth,
+                    ..Default::default()
+                };
+                scan(&options, &AtomicBool::new(false), |_| {})
+                    .into_iter()
+                    .map(|repo| repo.path.display().to_string())
+                    .collect()
+            }
+            WorkspaceRule::BookmarkFolder { folder } => match Bookmarks::read() {
+                Ok(bookmarks) => bookmarks.folder_repo_paths(folder),
+                Err(why) => {
+                    warn!("Couldn't read bookmarks for rule. '{}'", why);
+                    Vec::new()
+                }
+            },
+            WorkspaceRule::RemoteMatches { pattern } => match Bookmarks::read() {
+                Ok(bookmarks) => bookmarks
+                    .repo_paths()
+                    .into_iter()
+                    .filter(|repo_path| {
+                        remote_urls(repo_path)
+                            .iter()
+                            .any(|url| remote_matches(pattern, url))
+                    })
+                    .collect(),
+                Err(why) => {
+                    warn!("Couldn't read bookmarks for rule. '{}'", why);
+                    Vec::new()
+                }
+            },
+        }
+    }
+}
+
+/// Evaluates every rule, dropping repeats.
+pub fn evaluate_rules(rules: &[WorkspaceRule], roots: &PathRoots) -> Vec<String> {
+    let mut repo_paths: Vec<String> = Vec::new();
+    for repo_path in rules.iter().flat_map(|rule| rule.evaluate(roots)) {
+        if !repo_paths.contains(&repo_path) {
+            repo_paths.push(repo_path);
+        }
+    }
+    repo_paths
+}
+
+/// Whether a remote URL matches the pattern, ignoring the scheme and user, so `github.com/org/*`
+/// matches both `https://github.com/org/repo.git` and `git@github.com:org/repo.git`.
+pub fn remote_matches(pattern: &str, url: &str) -> bool {
+    let url = url.trim();
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let without_user = without_scheme
+        .split_once('@')
+        .map_or(without_scheme, |(_, rest)| rest);
+    let normalized = match url.contains("://") {
+        true => without_user.to_owned(),
+        false => without_user.replacen(':', "/", 1),
+    };
+
+    matches_pattern(pattern, &normalized) || matches_pattern(pattern, url)
+}
+
+fn remote_urls(repo_path: &str) -> Vec<String> {
+    run_git(
+        &PathBuf::from(repo_path),
+        &["config", "--get-regexp", r"^remote\..*\.url$"],
+    )
+    .map(|output| {
+        output
+            .lines()
+            .filter_map(|line| line.split_once(' ').map(|(_, url)| url.to_owned()))
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+fn default_max_depth() -> usize {
+    ScanOptions::default().max_depth
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn should_match_remote_urls() {
+        let pattern = "github.com/org/*";
+        assert!(remote_matches(pattern, "https://github.com/org/repo.git"));
+        assert!(remote_matches(pattern, "git@github.com:org/repo.git"));
+        assert!(remote_matches(pattern, "ssh://git@github.com/org/repo"));
+        assert!(!remote_matches(
+            pattern,
+            "https://github.com/other/repo.git"
+        ));
+    }
+
+    #[test]
+    fn should_evaluate_repos_under_root() {
+        let root = std::env::temp_dir().join(format!("st-workspaces-rules-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("one/.git")).unwrap();
+        std::fs::create_dir_all(root.join("two/.git")).unwrap();
+
+        let mut roots = PathRoots::default();
+        roots.insert("CLIENT", &root.display().to_string());
+        let rules = vec![
+            WorkspaceRule::ReposUnder {
+                root: "${CLIENT}".to_owned(),
+                max_depth: 1,
+            },
+            WorkspaceRule::ReposUnder {
+                root: root.join("one").display().to_string(),
+                max_depth: 0,
+            },
+        ];
+
+        let repo_paths = evaluate_rules(&rules, &roots);
+        assert_eq!(
+            repo_paths,
+            vec![
+                root.join("one").display().to_string(),
+                root.join("two").display().to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn should_persist_rules() {
+        let rule = WorkspaceRule::BookmarkFolder {
+            folder: "Client A".to_owned(),
+        };
+        let serialized = serde_json::to_string(&rule).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"kind":"bookmark_folder","folder":"Client A"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<WorkspaceRule>(&serialized).unwrap(),
+            rule
+        );
+    }
+}
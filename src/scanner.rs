@@ -0,0 +1,299 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver},
+        Arc,
+    },
+    thread,
+};
+
+/// What kind of working copy a directory is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepoKind {
+    Git,
+    Mercurial,
+    Submodule,
+    Worktree,
+}
+
+impl std::fmt::Display for RepoKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepoKind::Git => write!(f, "git"),
+            RepoKind::Mercurial => write!(f, "hg"),
+            RepoKind::Submodule => write!(f, "submodule"),
+            RepoKind::Worktree => write!(f, "worktree"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredRepo {
+    pub path: PathBuf,
+    pub kind: RepoKind,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    pub root: PathBuf,
+    /// How many directories deep to look below the root.
+    pub max_depth: usize,
+    /// Directory names to skip. `*` and `?` wildcards are allowed.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::new(),
+            max_depth: 4,
+            ignore_patterns: vec!["node_modules".to_owned(), "target".to_owned()],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanProgress {
+    /// A directory was looked at. Carries how many have been looked at so far.
+    Visited(usize, PathBuf),
+    Found(DiscoveredRepo),
+    Finished,
+}
+
+/// A scan running on a background thread.
+pub struct ScanHandle {
+    pub progress: Receiver<ScanProgress>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ScanHandle {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Starts scanning on a background thread. Progress is sent as it happens, ending with `Finished`.
+pub fn spawn_scan(options: ScanOptions) -> ScanHandle {
+    let (sender, receiver) = mpsc::channel();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let thread_cancelled = cancelled.clone();
+
+    thread::spawn(move || {
+        scan(&options, &thread_cancelled, |progress| {
+            // The receiver going away just means nobody is interested anymore.
+            let _ = sender.send(progress);
+        });
+        let _ = sender.send(ScanProgress::Finished);
+    });
+
+    ScanHandle {
+        progress: receiver,
+        cancelled,
+    }
+}
+
+/// Walks the directory tree below the root, returning every working copy found. Working copies
+/// are searched too, so submodules and nested repos are found.
+pub fn scan(
+    options: &ScanOptions,
+    cancelled: &AtomicBool,
+    mut on_progress: impl FnMut(ScanProgress),
+) -> Vec<DiscoveredRepo> {
+    let mut found = Vec::new();
+    let mut visited = 0;
+    let mut pending = vec![(options.root.clone(), 0)];
+
+    while let Some((directory, depth)) = pending.pop() {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+
+        visited += 1;
+        on_progress(ScanProgress::Visited(visited, directory.clone()));
+
+        if let Some(kind) = detect_repo_kind(&directory) {
+            let repo = DiscoveredRepo {
+                path: directory.clone(),
+                kind,
+            };
+            on_progress(ScanProgress::Found(repo.clone()));
+            found.push(repo);
+        }
+
+        if depth >= options.max_depth {
+            continue;
+        }
+
+        let Ok(entries) = std::fs::read_dir(&directory) else {
+            continue;
+        };
+
+        let mut children: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+            .map(|entry| entry.path())
+            .filter(|path| !is_ignored(path, &options.ignore_patterns))
+            .collect();
+
+        // Reversed so the stack pops them in name order.
+        children.sort();
+        children.reverse();
+        pending.extend(children.into_iter().map(|child| (child, depth + 1)));
+    }
+
+    found
+}
+
+/// Works out whether the directory is a working copy, and what kind.
+pub fn detect_repo_kind(path: &Path) -> Option<RepoKind> {
+    let git_path = path.join(".git");
+    if git_path.is_dir() {
+        return Some(RepoKind::Git);
+    }
+
+    if git_path.is_file() {
+        // Submodules and worktrees have a `.git` file pointing into the owning repo.
+        let contents = std::fs::read_to_string(&git_path).ok()?;
+        let git_dir = contents
+            .trim()
+            .strip_prefix("gitdir:")?
+            .trim()
+            .replace('\\', "/");
+        if git_dir.contains("/modules/") {
+            return Some(RepoKind::Submodule);
+        }
+
+        if git_dir.contains("/worktrees/") {
+            return Some(RepoKind::Worktree);
+        }
+
+        return Some(RepoKind::Git);
+    }
+
+    if path.join(".hg").is_dir() {
+        return Some(RepoKind::Mercurial);
+    }
+
+    None
+}
+
+/// Matches a name against a pattern where `*` is any run of characters and `?` is any one.
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, star_n)) = backtrack {
+            p = star + 1;
+            n = star_n + 1;
+            backtrack = Some((star, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+fn is_ignored(path: &Path, ignore_patterns: &[String]) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+
+    name == ".git"
+        || name == ".hg"
+        || ignore_patterns
+            .iter()
+            .any(|pattern| matches_pattern(pattern, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn create_test_tree() -> PathBuf {
+        let root = std::env::temp_dir().join(format!("st-workspaces-scan-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("app/.git/modules/lib")).unwrap();
+        std::fs::create_dir_all(root.join("app/lib")).unwrap();
+        std::fs::write(root.join("app/lib/.git"), "gitdir: ../.git/modules/lib\n").unwrap();
+        std::fs::create_dir_all(root.join("app-hotfix")).unwrap();
+        std::fs::write(
+            root.join("app-hotfix/.git"),
+            "gitdir: C:\\src\\app\\.git\\worktrees\\app-hotfix\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("old/.hg")).unwrap();
+        std::fs::create_dir_all(root.join("node_modules/dep/.git")).unwrap();
+        std::fs::create_dir_all(root.join("deep/er/still/repo/.git")).unwrap();
+        root
+    }
+
+    #[test]
+    fn should_find_working_copies() {
+        let root = create_test_tree();
+        let options = ScanOptions {
+            root: root.clone(),
+            max_depth: 2,
+            ..Default::default()
+        };
+
+        let found = scan(&options, &AtomicBool::new(false), |_| {});
+        let expected = vec![
+            DiscoveredRepo {
+                path: root.join("app"),
+                kind: RepoKind::Git,
+            },
+            DiscoveredRepo {
+                path: root.join("app").join("lib"),
+                kind: RepoKind::Submodule,
+            },
+            DiscoveredRepo {
+                path: root.join("app-hotfix"),
+                kind: RepoKind::Worktree,
+            },
+            DiscoveredRepo {
+                path: root.join("old"),
+                kind: RepoKind::Mercurial,
+            },
+        ];
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn should_report_progress_from_background_scan() {
+        let root = create_test_tree();
+        let handle = spawn_scan(ScanOptions {
+            root,
+            max_depth: 8,
+            ignore_patterns: vec!["node_*".to_owned()],
+        });
+
+        let progress: Vec<_> = handle.progress.iter().collect();
+        let found = progress
+            .iter()
+            .filter(|progress| matches!(progress, ScanProgress::Found(_)))
+            .count();
+        assert_eq!(found, 5);
+        assert_eq!(progress.last(), Some(&ScanProgress::Finished));
+    }
+
+    #[test]
+    fn should_match_wildcards() {
+        assert!(matches_pattern("node_modules", "node_modules"));
+        assert!(matches_pattern("*.tmp", "build.tmp"));
+        assert!(matches_pattern("b?ild*", "build-output"));
+        assert!(!matches_pattern("*.tmp", "build.tmp.keep"));
+        assert!(!matches_pattern("target", "targets"));
+    }
+}
@@ -0,0 +1,161 @@
+use uuid::Uuid;
+
+use crate::{filters::WorkspaceFilter, ui_state::SortMode, workspaces::Workspaces};
+
+/// The workspaces picked in the list, for acting on several at once. The current workspace is the
+/// one whose details are shown, and is kept among them.
+#[derive(Debug, Default, Clone)]
+pub struct Selection {
+    selected: Vec<Uuid>,
+    /// Where a Shift click extends from.
+    anchor: Option<Uuid>,
+}
+
+impl Selection {
+    pub fn contains(&self, uuid: &Uuid) -> bool {
+        self.selected.contains(uuid)
+    }
+
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// The selected workspaces in the order they're listed.
+    pub fn in_order(&self, order: &[Uuid]) -> Vec<Uuid> {
+        order
+            .iter()
+            .filter(|uuid| self.contains(uuid))
+            .copied()
+            .collect()
+    }
+
+    pub fn select_only(&mut self, uuid: Uuid) {
+        self.selected = vec![uuid];
+        self.anchor = Some(uuid);
+    }
+
+    /// Handles a click in the list, returning the workspace to show. Ctrl toggles a workspace,
+    /// Shift selects everything from the last one clicked, and both together add that range.
+    pub fn click(&mut self, uuid: Uuid, order: &[Uuid], toggle: bool, extend: bool) -> Uuid {
+        if extend {
+            let anchor = self.anchor.unwrap_or(uuid);
+            let range = range_between(order, anchor, uuid);
+            if !toggle {
+                self.selected.clear();
+            }
+            for uuid in range {
+                if !self.contains(&uuid) {
+                    self.selected.push(uuid);
+                }
+            }
+            return uuid;
+        }
+
+        if !toggle {
+            self.select_only(uuid);
+            return uuid;
+        }
+
+        self.anchor = Some(uuid);
+        match self.selected.iter().position(|selected| *selected == uuid) {
+            Some(index) if self.selected.len() > 1 => {
+                self.selected.remove(index);
+                self.selected[index.min(self.selected.len() - 1)]
+            }
+            Some(_) => uuid,
+            None => {
+                self.selected.push(uuid);
+                uuid
+            }
+        }
+    }
+
+    /// Drops workspaces that no longer exist, falling back to the current one.
+    pub fn sync(&mut self, workspaces: &Workspaces) {
+        self.selected
+            .retain(|uuid| workspaces.workspaces.contains_key(uuid));
+        let current = workspaces.current_workspace;
+        if !self.contains(&current) && !current.is_nil() {
+            self.select_only(current);
+        }
+    }
+}
+
+/// Every workspace the filter matches, in the order the list shows them: by source, then sorted
+/// within each.
+pub fn display_order(
+    workspaces: &Workspaces,
+    sort_mode: SortMode,
+    filter: &WorkspaceFilter,
+) -> Vec<Uuid> {
+    workspaces
+        .sources()
+        .iter()
+        .flat_map(|source| {
+            let mut listed = workspaces.by_source(source);
+            listed.retain(|workspace| filter.matches(workspace));
+            sort_mode.sort(&mut listed);
+            listed.into_iter().map(|workspace| workspace.uuid)
+        })
+        .collect()
+}
+
+/// What to select once the given workspaces are removed: the next one still listed after them,
+/// or failing that the one before.
+pub fn neighbour(order: &[Uuid], removed: &[Uuid]) -> Option<Uuid> {
+    let last_removed = order.iter().rposition(|uuid| removed.contains(uuid))?;
+    let is_kept = |uuid: &&Uuid| !removed.contains(uuid);
+
+    order[last_removed..]
+        .iter()
+        .find(is_kept)
+        .or_else(|| order[..last_removed].iter().rev().find(is_kept))
+        .copied()
+}
+
+fn range_between(order: &[Uuid], from: Uuid, to: Uuid) -> Vec<Uuid> {
+    let position = |uuid| order.iter().position(|listed| *listed == uuid);
+    match (position(from), position(to)) {
+        (Some(from), Some(to)) => order[from.min(to)..=from.max(to)].to_vec(),
+        _ => vec![to],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_order() -> Vec<Uuid> {
+        (0..5).map(|_| Uuid::new_v4()).collect()
+    }
+
+    #[test]
+    fn should_select_with_modifiers() {
+        let order = create_order();
+        let mut selection = Selection::default();
+
+        selection.click(order[1], &order, false, false);
+        selection.click(order[3], &order, false, true);
+        assert_eq!(selection.in_order(&order), order[1..=3].to_vec());
+
+        let shown = selection.click(order[2], &order, true, false);
+        assert_eq!(selection.in_order(&order), vec![order[1], order[3]]);
+        assert_ne!(shown, order[2]);
+
+        selection.click(order[4], &order, false, false);
+        assert_eq!(selection.in_order(&order), vec![order[4]]);
+    }
+
+    #[test]
+    fn should_pick_neighbour_after_removal() {
+        let order = create_order();
+
+        assert_eq!(neighbour(&order, &[order[1], order[2]]), Some(order[3]));
+        assert_eq!(neighbour(&order, &[order[4]]), Some(order[3]));
+        assert_eq!(neighbour(&order, &order), None);
+    }
+}
@@ -0,0 +1,188 @@
+use std::fs;
+
+use log::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    error::Error,
+    local_config::LocalConfig,
+    open_tabs::OpenTabs,
+    preferences::Preferences,
+    roots::PathRoots,
+    snapshots::{push_snapshot, BranchSnapshot},
+    sourcetree_actions::{self, CloseResult},
+    tr,
+    workspaces::Workspaces,
+};
+
+/// Closes SourceTree and folds the tabs it had open back into the workspace they came from. This
+/// is the start of every run, whether one-shot or resident.
+pub fn sync_last_session(wait_for_open_tabs_change: bool) -> anyhow::Result<Workspaces> {
+    // We have to get the last workspace id before trying to close SourceTree, as SourceTree
+    // overwrites the OpenTabs file.
+    let last_workspace_id = discover_last_workspace_id();
+    // Read before closing SourceTree, so a file that can't be read stops everything and is never
+    // written over.
+    let mut workspaces = load_workspaces()?;
+    close_sourcetree(wait_for_open_tabs_change)?;
+
+    if let Some(workspace_id) = last_workspace_id {
+        let roots = load_roots();
+        update_last_workspace(&mut workspaces, workspace_id, &roots);
+        workspaces.write()?;
+        save_open_tabs(&workspaces, &roots)
+    }
+
+    Ok(workspaces)
+}
+
+/// The saved workspaces, or none yet when there's no file. One that can't be read is an error.
+pub fn load_workspaces() -> anyhow::Result<Workspaces> {
+    let mut workspaces = Workspaces::read()?;
+    workspaces.force_valid_workspace();
+    Ok(workspaces)
+}
+
+/// This machine's path roots. A config that can't be read is logged and leaves paths unexpanded.
+pub fn load_roots() -> PathRoots {
+    match LocalConfig::read_or_default() {
+        Ok(config) => config.roots,
+        Err(why) => {
+            error!(
+                "Couldn't read the local config, so path roots won't be expanded. '{}'",
+                why
+            );
+            PathRoots::default()
+        }
+    }
+}
+
+fn discover_last_workspace_id() -> Option<Uuid> {
+    match OpenTabs::read() {
+        Ok(open_tabs) => {
+            info!("Was able to open tabs.");
+            let last_workspace_id = open_tabs.workspace_id.unwrap_or_else(Uuid::new_v4);
+            if !last_workspace_id.is_nil() {
+                info!("Last workspace id is {:?}", last_workspace_id);
+                return Some(last_workspace_id);
+            }
+            None
+        }
+        Err(why) => {
+            info!("Wasn't able to open tabs file --> {}", why);
+            None
+        }
+    }
+}
+
+fn save_open_tabs(workspaces: &Workspaces, roots: &PathRoots) {
+    if let Some(current_workspace) = workspaces.current_workspace() {
+        let open_tabs = OpenTabs::for_workspace(current_workspace, roots);
+        let write_result = OpenTabs::write(&open_tabs);
+        match write_result {
+            Ok(_) => info!("Saved current open tabs"),
+            Err(why) => warn!("Couldn't save current open tabs. '{}'", why),
+        }
+    }
+}
+
+fn close_sourcetree(wait_for_open_tabs_change: bool) -> Result<(), anyhow::Error> {
+    let open_tabs_path = OpenTabs::path().ok_or(Error::PathNotFound("open tabs file"))?;
+    let open_tabs_metadata = fs::metadata(&open_tabs_path);
+
+    // try to close SourceTree first, as this should never be up at the same time.
+    let close_result = sourcetree_actions::close_sourcetree();
+
+    match close_result {
+        Ok(CloseResult::Closed) => info!("Closed SourceTree."),
+        Ok(CloseResult::ProcessNotRunning) => {
+            info!("Didn't close SourceTree, because it wasn't running")
+        }
+        Err(why) => {
+            error!("Error occurred closing SourceTree, '{}'", why);
+            return Err(why.context(
+                "Can't recover because we don't know the state of SourceTree at this point.",
+            ));
+        }
+    }
+
+    if wait_for_open_tabs_change {
+        let timeout = Preferences::read().unwrap_or_default().close_timeout();
+        match open_tabs_metadata {
+            Ok(initial_metadata) => {
+                let start_time = std::time::Instant::now();
+                loop {
+                    let duration = std::time::Instant::now() - start_time;
+                    if duration > timeout {
+                        break;
+                    }
+
+                    if let Ok(latest_metadata) = fs::metadata(&open_tabs_path) {
+                        if latest_metadata.modified().unwrap()
+                            != initial_metadata.modified().unwrap()
+                        {
+                            break;
+                        }
+                    }
+
+                    std::thread::sleep(std::time::Duration::from_millis(200));
+                }
+            }
+            Err(why) => {
+                warn!(
+                    "Couldn't get open tabs metadata '{}'. Waiting {} seconds.",
+                    why,
+                    timeout.as_secs()
+                );
+
+                std::thread::sleep(timeout);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn update_last_workspace(workspaces: &mut Workspaces, last_workspace_id: Uuid, roots: &PathRoots) {
+    info!("Updating last workspace...");
+
+    match OpenTabs::read() {
+        Ok(open_tabs) => {
+            info!("Was able to open tabs.");
+            let mut last_workspace = open_tabs.to_workspace(roots);
+
+            if let Some(saved_workspace) = workspaces.workspaces.get(&last_workspace_id) {
+                info!(
+                    "Last workspace {} in saved workspace. Updating with latest.",
+                    last_workspace_id
+                );
+
+                // Start from the saved workspace so everything but the tabs is kept.
+                last_workspace = saved_workspace.clone();
+                last_workspace.set_repo_paths_from_tabs(&open_tabs.tabs, roots);
+                push_snapshot(
+                    &mut last_workspace.snapshots,
+                    BranchSnapshot::capture(&open_tabs.tabs, "", roots),
+                );
+            } else {
+                info!(
+                    "Last workspace {} not in saved workspaces. Creating new workspace.",
+                    last_workspace_id
+                );
+
+                last_workspace.name = tr!("workspace-last-name");
+            };
+
+            info!("The last workspace is {:?}", last_workspace);
+            workspaces
+                .workspaces
+                .insert(last_workspace.uuid, last_workspace);
+        }
+        Err(why) => {
+            error!(
+                "Couldn't open SourceTree's Open Tabs from last session. '{}'",
+                why
+            );
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use std::{
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    git::run_git,
+    git_batch::{check_branch_name, current_branch, RepoOutcome, RepoResult},
+    roots::PathRoots,
+    tr,
+};
+
+/// How many snapshots a workspace keeps before dropping the oldest.
+pub const MAX_SNAPSHOTS: usize = 20;
+
+/// The branch and commit each of a workspace's repos had checked out at one point in time.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BranchSnapshot {
+    /// Seconds since the Unix epoch.
+    pub taken_at: u64,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub label: String,
+    pub repos: Vec<RepoHead>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RepoHead {
+    /// Kept with its roots contracted, like a workspace's repo paths, so it fits any machine.
+    pub repo_path: String,
+    /// `None` when the head was detached.
+    pub branch: Option<String>,
+    pub commit: String,
+}
+
+impl BranchSnapshot {
+    /// Records the heads of the given repos. Repos that aren't git working copies are left out.
+    pub fn capture(repo_paths: &[String], label: &str, roots: &PathRoots) -> Self {
+        let repos = repo_paths
+            .iter()
+            .filter_map(|repo_path| {
+                let repo_path = roots.expand(repo_path);
+                let path = Path::new(&repo_path);
+                let commit = run_git(path, &["rev-parse", "HEAD"]).ok()?;
+                Some(RepoHead {
+                    repo_path: roots.contract(&repo_path),
+                    branch: current_branch(path),
+                    commit: commit.trim().to_owned(),
+                })
+            })
+            .collect();
+
+        Self {
+            taken_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default(),
+            label: label.to_owned(),
+            repos,
+        }
+    }
+
+    /// A one line description, like `Tue 2023-06-06 17:42  hotfix (3 repos)`.
+    pub fn description(&self) -> String {
+        let taken_at = Local
+            .timestamp_opt(self.taken_at as i64, 0)
+            .single()
+            .map(|time| time.format("%a %Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let label = match self.label.is_empty() {
+            true => String::new(),
+            false => format!("  {}", self.label),
+        };
+
+        tr!(
+            "snapshot-description",
+            taken_at = format!("{taken_at}{label}"),
+            count = self.repos.len()
+        )
+    }
+
+    /// Checks each repo back out to its recorded branch, or commit when it was detached. Repos
+    /// with uncommitted changes are refused rather than risk losing work.
+    pub fn restore(&self, roots: &PathRoots) -> Vec<RepoResult> {
+        self.repos
+            .iter()
+            .map(|head| restore_head(head, roots))
+            .collect()
+    }
+}
+
+/// Adds the snapshot to the history, unless nothing moved since the last one.
+pub fn push_snapshot(snapshots: &mut Vec<BranchSnapshot>, snapshot: BranchSnapshot) {
+    if snapshot.repos.is_empty() {
+        return;
+    }
+
+    if snapshots
+        .last()
+        .is_some_and(|last| last.repos == snapshot.repos && snapshot.label.is_empty())
+    {
+        return;
+    }
+
+    snapshots.push(snapshot);
+    if snapshots.len() > MAX_SNAPSHOTS {
+        let excess = snapshots.len() - MAX_SNAPSHOTS;
+        snapshots.drain(..excess);
+    }
+}
+
+/// Whether the working copy has changes to tracked files.
+pub fn is_dirty(path: &Path) -> anyhow::Result<bool> {
+    let output = run_git(path, &["status", "--porcelain", "--untracked-files=no"])?;
+    Ok(!output.trim().is_empty())
+}
+
+fn restore_head(head: &RepoHead, roots: &PathRoots) -> RepoResult {
+    let repo_path = roots.expand(&head.repo_path);
+    let path = Path::new(&repo_path);
+    let result = |outcome, summary: &str, output: &str| RepoResult {
+        repo_path: repo_path.clone(),
+        outcome,
+        summary: summary.to_owned(),
+        output: output.to_owned(),
+    };
+
+    if !path.is_dir() {
+        return result(RepoOutcome::Failed, &tr!("repo-not-found"), "");
+    }
+
+    // Snapshots are read from disk, so what they name is checked before it's handed to git.
+    let valid_target = match head.branch.as_deref() {
+        Some(branch) => check_branch_name(path, branch).is_ok(),
+        None => !head.commit.is_empty() && head.commit.chars().all(|c| c.is_ascii_hexdigit()),
+    };
+    if !valid_target {
+        return result(RepoOutcome::Failed, &tr!("repo-invalid-target"), "");
+    }
+
+    let target = head.branch.as_deref().unwrap_or(&head.commit);
+    let at_target = match head.branch.as_deref() {
+        Some(branch) => current_branch(path).as_deref() == Some(branch),
+        None => {
+            run_git(path, &["rev-parse", "HEAD"]).is_ok_and(|commit| commit.trim() == head.commit)
+        }
+    };
+    if at_target {
+        return result(RepoOutcome::Skipped, &tr!("repo-already-checked-out"), "");
+    }
+
+    match is_dirty(path) {
+        Ok(false) => {}
+        Ok(true) => {
+            return result(
+                RepoOutcome::Failed,
+                &tr!("repo-uncommitted-changes", branch = target),
+                "",
+            )
+        }
+        Err(why) => {
+            return result(
+                RepoOutcome::Failed,
+                &tr!("repo-error-reading-status"),
+                &why.to_string(),
+            )
+        }
+    }
+
+    match run_git(path, &["checkout", "--quiet", target, "--"]) {
+        Ok(output) => result(
+            RepoOutcome::Succeeded,
+            &tr!("repo-checked-out", branch = target),
+            &output,
+        ),
+        Err(why) => result(
+            RepoOutcome::Failed,
+            &tr!("repo-conflict-checking-out", branch = target),
+            &why.to_string(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git_batch::tests::create_test_repo;
+
+    #[test]
+    fn should_restore_recorded_branches() {
+        let repo = create_test_repo("snapshot");
+        run_git(&repo, &["checkout", "--quiet", "-b", "hotfix"]).unwrap();
+        let repo_paths = vec![repo.display().to_string()];
+        let mut roots = PathRoots::default();
+        roots.insert("CODE", &repo.parent().unwrap().display().to_string());
+        let snapshot = BranchSnapshot::capture(&repo_paths, "hotfix", &roots);
+        assert_eq!(snapshot.repos[0].branch.as_deref(), Some("hotfix"));
+        assert_eq!(snapshot.repos[0].repo_path, "${CODE}/snapshot");
+
+        run_git(&repo, &["checkout", "--quiet", "main"]).unwrap();
+        let results = snapshot.restore(&roots);
+        assert_eq!(results[0].outcome, RepoOutcome::Succeeded);
+        assert_eq!(current_branch(&repo).as_deref(), Some("hotfix"));
+
+        let results = snapshot.restore(&roots);
+        assert_eq!(results[0].outcome, RepoOutcome::Skipped);
+    }
+
+    #[test]
+    fn should_refuse_dirty_trees() {
+        let repo = create_test_repo("dirty");
+        let repo_paths = vec![repo.display().to_string()];
+        run_git(&repo, &["branch", "other"]).unwrap();
+        let roots = PathRoots::default();
+        let mut snapshot = BranchSnapshot::capture(&repo_paths, "", &roots);
+        snapshot.repos[0].branch = Some("other".to_owned());
+
+        std::fs::write(repo.join("README.md"), "changed\n").unwrap();
+        let results = snapshot.restore(&roots);
+        assert_eq!(results[0].outcome, RepoOutcome::Failed);
+        assert_eq!(current_branch(&repo).as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn should_refuse_targets_read_as_options() {
+        let repo = create_test_repo("option-target");
+        let repo_paths = vec![repo.display().to_string()];
+        let roots = PathRoots::default();
+        let mut snapshot = BranchSnapshot::capture(&repo_paths, "", &roots);
+
+        snapshot.repos[0].branch = Some("--orphan=other".to_owned());
+        let results = snapshot.restore(&roots);
+        assert_eq!(results[0].outcome, RepoOutcome::Failed);
+
+        snapshot.repos[0].branch = None;
+        snapshot.repos[0].commit = "--detach".to_owned();
+        let results = snapshot.restore(&roots);
+        assert_eq!(results[0].outcome, RepoOutcome::Failed);
+        assert_eq!(current_branch(&repo).as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn should_keep_bounded_history_without_repeats() {
+        let head = RepoHead {
+            repo_path: "repo".to_owned(),
+            branch: Some("main".to_owned()),
+            commit: "abc".to_owned(),
+        };
+        let snapshot = |commit: &str| BranchSnapshot {
+            taken_at: 0,
+            label: String::new(),
+            repos: vec![RepoHead {
+                commit: commit.to_owned(),
+                ..head.clone()
+            }],
+        };
+
+        let mut snapshots = Vec::new();
+        push_snapshot(&mut snapshots, snapshot("abc"));
+        push_snapshot(&mut snapshots, snapshot("abc"));
+        assert_eq!(snapshots.len(), 1);
+
+        for index in 0..MAX_SNAPSHOTS + 5 {
+            push_snapshot(&mut snapshots, snapshot(&index.to_string()));
+        }
+        assert_eq!(snapshots.len(), MAX_SNAPSHOTS);
+        assert_eq!(snapshots.last().unwrap().repos[0].commit, "24");
+    }
+}
@@ -1,4 +1,4 @@
-use anyhow::bail;
+use crate::error::Error;
 
 pub enum CloseResult {
     Closed,
@@ -9,12 +9,18 @@ pub enum CloseResult {
 pub fn close_sourcetree() -> anyhow::Result<CloseResult> {
     let mut command = std::process::Command::new("taskkill");
     command.arg("/IM").arg("SourceTree.exe");
-    let output = command.output()?;
+    let output = command
+        .output()
+        .map_err(|why| Error::launch(&command, why))?;
 
     match output.status.code() {
         Some(0) => Ok(CloseResult::Closed),
         Some(128) => Ok(CloseResult::ProcessNotRunning),
-        Some(code) => bail!("Unknown error closing SourceTree. code {code}"),
-        None => bail!("Unknown error closing SourceTree."),
+        code => Err(Error::ProcessExit {
+            command: "taskkill /IM SourceTree.exe".to_owned(),
+            code,
+            output: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+        }
+        .into()),
     }
 }
@@ -0,0 +1,351 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    error::Error, instance::InstanceLock, local_config::LocalConfig, open_tabs::OpenTabs,
+    roots::PathRoots, workspaces::Workspaces,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How often a headless watcher checks whether another workspace has been opened.
+const SWITCH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// What to do when the workspace's repos were edited here while SourceTree changed its tabs.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// Keep the tabs SourceTree added or closed, as well as the repos added or removed here.
+    #[default]
+    Merge,
+    PreferTabs,
+    PreferEditor,
+}
+
+/// Settings for keeping the current workspace in step with SourceTree's tabs as they change.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TabsWatcherConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// How long the tabs must stay unchanged before they're saved.
+    #[serde(default = "default_debounce_ms")]
+    pub debounce_ms: u64,
+    #[serde(default)]
+    pub conflict_policy: ConflictPolicy,
+}
+
+impl Default for TabsWatcherConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            debounce_ms: default_debounce_ms(),
+            conflict_policy: ConflictPolicy::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncOutcome {
+    Unchanged,
+    Updated,
+    /// Both sides had changed and were merged.
+    Merged,
+    /// Both sides had changed and the edits made here were kept.
+    KeptEditor,
+}
+
+/// Signals when a file has changed and then stayed unchanged for the debounce time. Stops when
+/// dropped.
+pub struct FileWatcher {
+    pub changes: Receiver<()>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Drop for FileWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+pub fn spawn_file_watcher(path: PathBuf, debounce: Duration) -> FileWatcher {
+    let (sender, receiver) = mpsc::channel();
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = stop.clone();
+
+    thread::spawn(move || {
+        let modified = |path: &PathBuf| -> Option<SystemTime> {
+            std::fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        };
+
+        let mut last_seen = modified(&path);
+        let mut changed_at: Option<Instant> = None;
+        while !thread_stop.load(Ordering::Relaxed) {
+            let latest = modified(&path);
+            if latest != last_seen {
+                last_seen = latest;
+                changed_at = Some(Instant::now());
+            }
+
+            if changed_at.is_some_and(|changed_at| changed_at.elapsed() >= debounce) {
+                changed_at = None;
+                if sender.send(()).is_err() {
+                    break;
+                }
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+
+    FileWatcher {
+        changes: receiver,
+        stop,
+    }
+}
+
+/// Folds SourceTree's tabs into the current workspace, remembering what each workspace looked like
+/// at the last sync so edits made in between can be told apart from the tabs changing.
+pub struct TabsSync {
+    policy: ConflictPolicy,
+    base: HashMap<Uuid, Vec<String>>,
+}
+
+impl TabsSync {
+    pub fn new(policy: ConflictPolicy) -> Self {
+        Self {
+            policy,
+            base: HashMap::new(),
+        }
+    }
+
+    pub fn apply(
+        &mut self,
+        workspaces: &mut Workspaces,
+        tabs: &[String],
+        roots: &PathRoots,
+    ) -> SyncOutcome {
+        let Some(workspace) = workspaces.current_workspace_mut() else {
+            return SyncOutcome::Unchanged;
+        };
+
+        let mut from_tabs = workspace.clone();
+        from_tabs.set_repo_paths_from_tabs(tabs, roots);
+        let theirs = from_tabs.repo_paths;
+        let ours = workspace.repo_paths.clone();
+        let base = self
+            .base
+            .entry(workspace.uuid)
+            .or_insert_with(|| ours.clone());
+
+        let (repo_paths, outcome) = if theirs == ours {
+            (ours, SyncOutcome::Unchanged)
+        } else if ours == *base {
+            (theirs, SyncOutcome::Updated)
+        } else {
+            match self.policy {
+                ConflictPolicy::Merge => {
+                    (merge_repo_paths(base, &ours, &theirs), SyncOutcome::Merged)
+                }
+                ConflictPolicy::PreferTabs => (theirs, SyncOutcome::Updated),
+                ConflictPolicy::PreferEditor => (ours, SyncOutcome::KeptEditor),
+            }
+        };
+
+        *base = repo_paths.clone();
+        workspace.repo_paths = repo_paths;
+        outcome
+    }
+}
+
+/// Starts from the tabs, then drops the repos removed here and adds the repos added here since
+/// the last sync.
+pub fn merge_repo_paths(base: &[String], ours: &[String], theirs: &[String]) -> Vec<String> {
+    let mut merged: Vec<String> = theirs
+        .iter()
+        .filter(|path| ours.contains(path) || !base.contains(path))
+        .cloned()
+        .collect();
+
+    for path in ours {
+        if !base.contains(path) && !merged.contains(path) {
+            merged.push(path.clone());
+        }
+    }
+
+    merged
+}
+
+/// Watches the tabs without a window, saving them into the current workspace as they change.
+/// Stops once another workspace has been opened, since that run starts its own watcher.
+pub fn run_headless() -> anyhow::Result<()> {
+    let config = LocalConfig::read_or_default()?;
+    if !config.tabs_watcher.enabled {
+        info!("Watching open tabs is turned off, so didn't start.");
+        return Ok(());
+    }
+
+    let path = OpenTabs::path().ok_or(Error::PathNotFound("open tabs file"))?;
+    let watched_workspace = Workspaces::read()?.current_workspace;
+    let watcher = spawn_file_watcher(path, Duration::from_millis(config.tabs_watcher.debounce_ms));
+    let mut sync = TabsSync::new(config.tabs_watcher.conflict_policy);
+    info!("Watching open tabs for workspace {}...", watched_workspace);
+
+    loop {
+        let changed = match watcher.changes.recv_timeout(SWITCH_CHECK_INTERVAL) {
+            Ok(()) => true,
+            Err(RecvTimeoutError::Timeout) => false,
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        };
+
+        match Workspaces::read() {
+            Ok(workspaces) if workspaces.current_workspace != watched_workspace => {
+                info!("Another workspace was opened, so stopped watching open tabs.");
+                return Ok(());
+            }
+            Ok(_) => {}
+            Err(why) => {
+                warn!("Couldn't read workspaces. '{}'", why);
+                continue;
+            }
+        }
+
+        if !changed {
+            continue;
+        }
+
+        let open_tabs = match OpenTabs::read() {
+            Ok(open_tabs) => open_tabs,
+            Err(why) => {
+                warn!("Couldn't read changed open tabs. '{}'", why);
+                continue;
+            }
+        };
+
+        // The GUI owns the workspaces while it's open, and folds the tabs in itself on the next
+        // switch.
+        let _lock = match InstanceLock::try_acquire() {
+            Ok(Some(lock)) => lock,
+            Ok(None) => {
+                info!("Another instance is running, so didn't save open tabs.");
+                continue;
+            }
+            Err(why) => {
+                warn!("Couldn't take the instance lock. '{}'", why);
+                continue;
+            }
+        };
+
+        // Read again under the lock, so whatever was saved meanwhile isn't written over.
+        let mut workspaces = match Workspaces::read() {
+            Ok(workspaces) if workspaces.current_workspace == watched_workspace => workspaces,
+            Ok(_) => {
+                info!("Another workspace was opened, so stopped watching open tabs.");
+                return Ok(());
+            }
+            Err(why) => {
+                warn!("Couldn't read workspaces. '{}'", why);
+                continue;
+            }
+        };
+
+        let outcome = sync.apply(&mut workspaces, &open_tabs.tabs, &config.roots);
+        if outcome != SyncOutcome::Unchanged {
+            match workspaces.write() {
+                Ok(()) => info!("Saved open tabs into the current workspace. {:?}", outcome),
+                Err(why) => warn!(
+                    "Couldn't save open tabs into the current workspace. '{}'",
+                    why
+                ),
+            }
+        }
+    }
+}
+
+fn default_debounce_ms() -> u64 {
+    1500
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::workspaces::Workspace;
+
+    use super::*;
+
+    fn paths(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|path| path.to_string()).collect()
+    }
+
+    fn create_test_workspaces(repo_paths: &[&str]) -> Workspaces {
+        let mut workspace = Workspace::new("Watched", Uuid::new_v4());
+        workspace.repo_paths = paths(repo_paths);
+
+        let mut workspaces = Workspaces::default();
+        workspaces.current_workspace = workspace.uuid;
+        workspaces.workspaces.insert(workspace.uuid, workspace);
+        workspaces
+    }
+
+    #[test]
+    fn should_merge_both_sides_changes() {
+        let base = paths(&["/a", "/b", "/c"]);
+        let ours = paths(&["/a", "/c", "/d"]);
+        let theirs = paths(&["/a", "/b", "/c", "/e"]);
+        assert_eq!(
+            merge_repo_paths(&base, &ours, &theirs),
+            paths(&["/a", "/c", "/e", "/d"])
+        );
+    }
+
+    #[test]
+    fn should_apply_tabs_by_policy() {
+        let roots = PathRoots::default();
+        let mut workspaces = create_test_workspaces(&["/a", "/b"]);
+        let mut sync = TabsSync::new(ConflictPolicy::PreferEditor);
+
+        let outcome = sync.apply(&mut workspaces, &paths(&["/a", "/b", "/c"]), &roots);
+        assert_eq!(outcome, SyncOutcome::Updated);
+
+        workspaces.current_workspace_mut().unwrap().repo_paths = paths(&["/a"]);
+        let outcome = sync.apply(&mut workspaces, &paths(&["/a", "/b", "/c", "/d"]), &roots);
+        assert_eq!(outcome, SyncOutcome::KeptEditor);
+        assert_eq!(
+            workspaces.current_workspace().unwrap().repo_paths,
+            paths(&["/a"])
+        );
+    }
+
+    #[test]
+    fn should_signal_after_file_settles() {
+        let path = std::env::temp_dir().join(format!("st-workspaces-watch-{}", Uuid::new_v4()));
+        std::fs::write(&path, "one").unwrap();
+
+        let watcher = spawn_file_watcher(path.clone(), Duration::from_millis(300));
+        thread::sleep(Duration::from_millis(300));
+        std::fs::write(&path, "two").unwrap();
+        let modified = SystemTime::now() + Duration::from_secs(5);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+
+        assert!(watcher.changes.recv_timeout(Duration::from_secs(5)).is_ok());
+        std::fs::remove_file(path).unwrap();
+    }
+}
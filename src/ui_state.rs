@@ -0,0 +1,180 @@
+use std::{collections::BTreeSet, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    error::{is_not_found, parse_json, read_file, write_file, Error},
+    filters::FilterPreset,
+    paths::sourcetree_settings_path,
+    tr,
+    workspaces::{Workspace, WorkspaceSource},
+};
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortMode {
+    #[default]
+    Name,
+    NameDescending,
+    /// Most repos first.
+    RepoCount,
+}
+
+impl std::fmt::Display for SortMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let key = match self {
+            SortMode::Name => "sort-name",
+            SortMode::NameDescending => "sort-name-descending",
+            SortMode::RepoCount => "sort-repo-count",
+        };
+        write!(f, "{}", tr!(key))
+    }
+}
+
+impl SortMode {
+    pub fn all() -> [SortMode; 3] {
+        [
+            SortMode::Name,
+            SortMode::NameDescending,
+            SortMode::RepoCount,
+        ]
+    }
+
+    pub fn sort(&self, workspaces: &mut [&Workspace]) {
+        match self {
+            SortMode::Name => workspaces.sort(),
+            SortMode::NameDescending => workspaces.sort_by(|a, b| b.cmp(a)),
+            SortMode::RepoCount => workspaces.sort_by(|a, b| {
+                b.repo_paths
+                    .len()
+                    .cmp(&a.repo_paths.len())
+                    .then_with(|| a.cmp(b))
+            }),
+        }
+    }
+}
+
+/// How the window was left, so the next run picks up where this one stopped. Kept apart from the
+/// preferences since it changes all the time.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UiState {
+    /// The width of the workspace list beside the details.
+    #[serde(default = "default_list_width")]
+    pub list_width: f32,
+    #[serde(default)]
+    pub selected_workspace: Option<Uuid>,
+    #[serde(default)]
+    pub sort_mode: SortMode,
+    /// The workspace groups folded away, by [`group_key`].
+    #[serde(default)]
+    pub collapsed_groups: BTreeSet<String>,
+    #[serde(default)]
+    pub filter_presets: Vec<FilterPreset>,
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            list_width: default_list_width(),
+            selected_workspace: None,
+            sort_mode: SortMode::default(),
+            collapsed_groups: BTreeSet::new(),
+            filter_presets: Vec::new(),
+        }
+    }
+}
+
+impl UiState {
+    pub fn path() -> Option<PathBuf> {
+        Some(sourcetree_settings_path()?.join("st-workspaces-ui-state.json"))
+    }
+
+    pub fn read() -> anyhow::Result<UiState> {
+        let path = UiState::path().ok_or(Error::PathNotFound("UI state file"))?;
+        let contents = read_file(&path)?;
+        let state: UiState = parse_json(&path, &contents)?;
+        Ok(state)
+    }
+
+    /// Like [`UiState::read`], but a state that hasn't been saved yet is the default one.
+    /// A file that can't be parsed is still an error, so it isn't saved over.
+    pub fn read_or_default() -> anyhow::Result<UiState> {
+        match UiState::read() {
+            Err(why) if is_not_found(&why) => Ok(UiState::default()),
+            result => result,
+        }
+    }
+
+    pub fn write(&self) -> anyhow::Result<()> {
+        let path = UiState::path().ok_or(Error::PathNotFound("UI state file"))?;
+        let contents = serde_json::to_string_pretty(self)?;
+        write_file(&path, contents)?;
+        Ok(())
+    }
+
+    pub fn is_collapsed(&self, source: &WorkspaceSource) -> bool {
+        self.collapsed_groups.contains(&group_key(source))
+    }
+
+    pub fn set_collapsed(&mut self, source: &WorkspaceSource, collapsed: bool) {
+        let key = group_key(source);
+        match collapsed {
+            true => self.collapsed_groups.insert(key),
+            false => self.collapsed_groups.remove(&key),
+        };
+    }
+}
+
+/// Tells project groups apart by their repo rather than the name shown.
+pub fn group_key(source: &WorkspaceSource) -> String {
+    match source {
+        WorkspaceSource::Personal => "personal".to_owned(),
+        WorkspaceSource::Team => "team".to_owned(),
+        WorkspaceSource::Project(repo_path) => format!("project:{}", repo_path.display()),
+    }
+}
+
+fn default_list_width() -> f32 {
+    240.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_workspace(name: &str, repo_count: usize) -> Workspace {
+        let mut workspace = Workspace::new(name, Uuid::new_v4());
+        workspace.repo_paths = (0..repo_count).map(|index| format!("/{index}")).collect();
+        workspace
+    }
+
+    #[test]
+    fn should_sort_by_mode() {
+        let alpha = create_workspace("Alpha", 1);
+        let beta = create_workspace("Beta", 3);
+        let gamma = create_workspace("Gamma", 3);
+        let mut workspaces = vec![&gamma, &alpha, &beta];
+
+        SortMode::NameDescending.sort(&mut workspaces);
+        let names: Vec<_> = workspaces.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, vec!["Gamma", "Beta", "Alpha"]);
+
+        SortMode::RepoCount.sort(&mut workspaces);
+        let names: Vec<_> = workspaces.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, vec!["Beta", "Gamma", "Alpha"]);
+    }
+
+    #[test]
+    fn should_remember_collapsed_groups() {
+        let mut state: UiState = serde_json::from_str("{}").unwrap();
+        let project = WorkspaceSource::Project(PathBuf::from("/repos/app"));
+
+        state.set_collapsed(&project, true);
+        let state: UiState = serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+
+        assert!(state.is_collapsed(&project));
+        assert!(!state.is_collapsed(&WorkspaceSource::Personal));
+        assert_eq!(state.list_width, default_list_width());
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::{BTreeSet, HashMap};
+
+use uuid::Uuid;
+
+use crate::workspaces::Workspace;
+
+/// A workspace changed both here and in the file, or changed on one side and deleted on the other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceConflict {
+    pub uuid: Uuid,
+    /// `None` when deleted here.
+    pub ours: Option<Workspace>,
+    /// `None` when deleted from the file.
+    pub theirs: Option<Workspace>,
+}
+
+impl WorkspaceConflict {
+    pub fn name(&self) -> &str {
+        self.ours
+            .as_ref()
+            .or(self.theirs.as_ref())
+            .map(|workspace| workspace.name.as_str())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MergeOutcome {
+    /// Conflicted workspaces keep our side until the conflict is resolved.
+    pub merged: HashMap<Uuid, Workspace>,
+    pub conflicts: Vec<WorkspaceConflict>,
+}
+
+/// Merges the workspaces edited here with the ones in the file, by id, against the version both
+/// started from.
+pub fn merge_workspaces(
+    base: &HashMap<Uuid, Workspace>,
+    ours: &HashMap<Uuid, Workspace>,
+    theirs: &HashMap<Uuid, Workspace>,
+) -> MergeOutcome {
+    let uuids: BTreeSet<_> = base
+        .keys()
+        .chain(ours.keys())
+        .chain(theirs.keys())
+        .copied()
+        .collect();
+
+    let mut outcome = MergeOutcome::default();
+    for uuid in uuids {
+        let (base, ours, theirs) = (base.get(&uuid), ours.get(&uuid), theirs.get(&uuid));
+        let merged = if ours == theirs || theirs == base {
+            ours
+        } else if ours == base {
+            theirs
+        } else {
+            outcome.conflicts.push(WorkspaceConflict {
+                uuid,
+                ours: ours.cloned(),
+                theirs: theirs.cloned(),
+            });
+            ours
+        };
+
+        if let Some(workspace) = merged {
+            outcome.merged.insert(uuid, workspace.clone());
+        }
+    }
+
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(uuid: Uuid, name: &str, repo_paths: &[&str]) -> Workspace {
+        let mut workspace = Workspace::new(name, uuid);
+        workspace.repo_paths = repo_paths.iter().map(|path| path.to_string()).collect();
+        workspace
+    }
+
+    fn by_id(workspaces: &[&Workspace]) -> HashMap<Uuid, Workspace> {
+        workspaces
+            .iter()
+            .map(|workspace| (workspace.uuid, (*workspace).clone()))
+            .collect()
+    }
+
+    #[test]
+    fn should_take_changes_from_either_side() {
+        let (edited_here, edited_there) = (Uuid::new_v4(), Uuid::new_v4());
+        let base = by_id(&[
+            &workspace(edited_here, "Here", &["a"]),
+            &workspace(edited_there, "There", &["b"]),
+        ]);
+        let added_there = workspace(Uuid::new_v4(), "New", &["c"]);
+        let ours = by_id(&[
+            &workspace(edited_here, "Here", &["a", "a2"]),
+            &workspace(edited_there, "There", &["b"]),
+        ]);
+        let theirs = by_id(&[
+            &workspace(edited_here, "Here", &["a"]),
+            &workspace(edited_there, "There", &["b", "b2"]),
+            &added_there,
+        ]);
+
+        let outcome = merge_workspaces(&base, &ours, &theirs);
+
+        assert!(outcome.conflicts.is_empty());
+        assert_eq!(outcome.merged[&edited_here].repo_paths, vec!["a", "a2"]);
+        assert_eq!(outcome.merged[&edited_there].repo_paths, vec!["b", "b2"]);
+        assert_eq!(outcome.merged[&added_there.uuid], added_there);
+    }
+
+    #[test]
+    fn should_take_deletions_of_unchanged_workspaces() {
+        let deleted = workspace(Uuid::new_v4(), "Deleted", &["a"]);
+        let base = by_id(&[&deleted]);
+
+        let outcome = merge_workspaces(&base, &base, &HashMap::new());
+
+        assert!(outcome.conflicts.is_empty());
+        assert!(outcome.merged.is_empty());
+    }
+
+    #[test]
+    fn should_keep_ours_and_report_conflicts() {
+        let uuid = Uuid::new_v4();
+        let base = by_id(&[&workspace(uuid, "Both", &["a"])]);
+        let ours = by_id(&[&workspace(uuid, "Both", &["a", "ours"])]);
+
+        let edited = merge_workspaces(
+            &base,
+            &ours,
+            &by_id(&[&workspace(uuid, "Both", &["a", "theirs"])]),
+        );
+        let deleted = merge_workspaces(&base, &ours, &HashMap::new());
+
+        assert_eq!(edited.merged, ours);
+        assert_eq!(edited.conflicts.len(), 1);
+        assert_eq!(
+            edited.conflicts[0].theirs.as_ref().unwrap().repo_paths,
+            vec!["a", "theirs"]
+        );
+        assert_eq!(deleted.merged, ours);
+        assert_eq!(deleted.conflicts[0].theirs, None);
+        assert_eq!(deleted.conflicts[0].name(), "Both");
+    }
+}
@@ -1,15 +1,72 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
 
-use anyhow::bail;
-use serde::{Deserialize, Serialize};
+use log::warn;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use uuid::Uuid;
 
-use crate::paths::sourcetree_settings_path;
+use crate::{
+    catalogue::Catalogue,
+    dirty_guard::PendingStash,
+    error::{is_not_found, parse_json, read_file, write_file, Error},
+    hooks::WorkspaceHook,
+    ide_export::IdeExport,
+    launchers::Launcher,
+    local_config::LocalConfig,
+    paths::sourcetree_settings_path,
+    project_file::{has_project_file, read_project_workspace, write_project_workspace},
+    roots::PathRoots,
+    rules::{evaluate_rules, WorkspaceRule},
+    snapshots::BranchSnapshot,
+    tr,
+};
 
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Workspaces {
     pub current_workspace: Uuid,
+    #[serde(serialize_with = "serialize_ordered")]
     pub workspaces: HashMap<Uuid, Workspace>,
+    /// The team catalogue that the `Team` workspaces were loaded from.
+    #[serde(skip)]
+    pub catalogue: Option<Catalogue>,
+    /// The team workspaces as last read from or written to the catalogue. `None` when it couldn't
+    /// be read, so that a broken or unreachable catalogue is never overwritten.
+    #[serde(skip)]
+    loaded_team: Option<HashMap<Uuid, Workspace>>,
+    /// The project workspaces as last read from or written to their repos.
+    #[serde(skip)]
+    loaded_projects: HashMap<Uuid, Workspace>,
+    /// The workspaces as their IDE project files were last written, or as read.
+    #[serde(skip)]
+    exported: HashMap<Uuid, Workspace>,
+    /// Team workspaces hidden by a personal one with the same id, kept so they stay in the
+    /// catalogue.
+    #[serde(skip)]
+    shadowed_team: HashMap<Uuid, Workspace>,
+    /// What the team and project workspaces have on this machine alone, kept out of their shared
+    /// files.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    local_state: BTreeMap<Uuid, LocalState>,
+}
+
+/// A workspace's snapshots, stashes and exports, which only make sense on the machine they were
+/// made on.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+struct LocalState {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    snapshots: Vec<BranchSnapshot>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pending_stashes: Vec<PendingStash>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    exports: Vec<IdeExport>,
+}
+
+impl LocalState {
+    fn is_empty(&self) -> bool {
+        *self == LocalState::default()
+    }
 }
 
 impl Workspaces {
@@ -21,55 +78,537 @@ impl Workspaces {
         self.workspaces.get_mut(&self.current_workspace)
     }
 
+    /// Points the current workspace at one that exists, the first in display order when it
+    /// doesn't, or nil when there are none.
     pub fn force_valid_workspace(&mut self) {
-        if !self.workspaces.is_empty() && self.current_workspace.is_nil() {
-            self.current_workspace = *self.workspaces.iter().next().unwrap().0;
+        if self.workspaces.contains_key(&self.current_workspace) {
+            return;
         }
+
+        self.current_workspace = self
+            .sources()
+            .iter()
+            .find_map(|source| {
+                self.by_source(source)
+                    .first()
+                    .map(|workspace| workspace.uuid)
+            })
+            .unwrap_or_default();
     }
 
-    pub fn write(&self) -> anyhow::Result<()> {
-        let Some(path) = Workspaces::path() else {
-            bail!("Error getting workspace path for reading.");
+    /// Writes each workspace back to the source it came from: the personal file, the team
+    /// catalogue when it is writable, or the repo's `.st-workspace`.
+    pub fn write(&mut self) -> anyhow::Result<()> {
+        let path = Workspaces::path().ok_or(Error::PathNotFound("workspaces file"))?;
+
+        self.collect_local_state();
+        let mut personal = self.only_from_source(&WorkspaceSource::Personal);
+        personal.local_state = self.local_state.clone();
+        write_to_path(&path, &personal)?;
+
+        if let Some(catalogue) = self.catalogue.clone().filter(|c| !c.read_only) {
+            self.write_catalogue(&catalogue)?;
+        }
+
+        self.write_projects()?;
+
+        self.write_exports();
+        Ok(())
+    }
+
+    /// Rewrites the catalogue when a team workspace changed since it was read, and never when it
+    /// couldn't be read.
+    fn write_catalogue(&mut self, catalogue: &Catalogue) -> anyhow::Result<()> {
+        let Some(loaded_team) = self.loaded_team.as_ref() else {
+            return Ok(());
         };
 
-        write_to_path(&path, self)?;
+        let mut team = self.only_from_source(&WorkspaceSource::Team);
+        for workspace in team.workspaces.values_mut() {
+            *workspace = workspace.shared();
+        }
+        for (uuid, workspace) in self.shadowed_team.iter() {
+            team.workspaces
+                .entry(*uuid)
+                .or_insert_with(|| workspace.clone());
+        }
+        if team.workspaces == *loaded_team {
+            return Ok(());
+        }
+
+        catalogue.write(&team)?;
+        self.loaded_team = Some(team.workspaces);
         Ok(())
     }
 
-    pub fn path() -> Option<PathBuf> {
-        let Some(settings_path) = sourcetree_settings_path() else {
-            return None;
+    /// Writes the `.st-workspace` of each project workspace whose shared settings changed since it
+    /// was read.
+    fn write_projects(&mut self) -> anyhow::Result<()> {
+        let changed: Vec<Workspace> = self
+            .workspaces
+            .values()
+            .filter(|workspace| matches!(workspace.source, WorkspaceSource::Project(_)))
+            .map(Workspace::shared)
+            .filter(|workspace| self.loaded_projects.get(&workspace.uuid) != Some(workspace))
+            .collect();
+
+        for workspace in changed {
+            if let WorkspaceSource::Project(repo_path) = &workspace.source {
+                write_project_workspace(&workspace, repo_path)?;
+            }
+            self.loaded_projects.insert(workspace.uuid, workspace);
+        }
+        Ok(())
+    }
+
+    /// Regenerates the IDE project files of the workspaces that changed since they were last
+    /// exported. A failed export is logged rather than stopping the save.
+    fn write_exports(&mut self) {
+        let changed: Vec<Workspace> = self
+            .workspaces
+            .values()
+            .filter(|workspace| self.exported.get(&workspace.uuid) != Some(workspace))
+            .cloned()
+            .collect();
+        if changed.iter().all(|workspace| workspace.exports.is_empty()) {
+            self.exported.extend(
+                changed
+                    .into_iter()
+                    .map(|workspace| (workspace.uuid, workspace)),
+            );
+            return;
+        }
+
+        let roots = match LocalConfig::read_or_default() {
+            Ok(config) => config.roots,
+            Err(why) => {
+                warn!(
+                    "Couldn't read the local config, so didn't export. '{}'",
+                    why
+                );
+                return;
+            }
         };
+        for workspace in changed {
+            for export in workspace.exports.iter() {
+                if let Err(why) = export.write(&workspace, &roots) {
+                    warn!(
+                        "Couldn't write {} export of {} to {}. '{}'",
+                        export.format, workspace.name, export.path, why
+                    );
+                }
+            }
+            self.exported.insert(workspace.uuid, workspace);
+        }
+    }
 
-        Some(settings_path.join("st-workspaces.json"))
+    pub fn path() -> Option<PathBuf> {
+        Some(sourcetree_settings_path()?.join("st-workspaces.json"))
     }
 
     pub fn read() -> anyhow::Result<Workspaces> {
-        let Some(path) = Workspaces::path() else {
-            bail!("Error getting workspace path for reading.");
+        let path = Workspaces::path().ok_or(Error::PathNotFound("workspaces file"))?;
+
+        let mut workspaces = match read_from_path(&path) {
+            Ok(workspaces) => workspaces,
+            Err(why) if is_not_found(&why) => Workspaces::default(),
+            Err(why) => return Err(why),
         };
 
-        let workspaces = read_from_path(&path)?;
+        workspaces.exported = workspaces.workspaces.clone();
+        match LocalConfig::read_or_default() {
+            Ok(config) => {
+                if let Err(why) = workspaces.load_sources(&config) {
+                    warn!("Couldn't load all workspace sources. '{}'", why);
+                }
+            }
+            Err(why) => warn!(
+                "Couldn't read the local config, so only personal workspaces are loaded. '{}'",
+                why
+            ),
+        }
+
         Ok(workspaces)
     }
+
+    /// Reads workspaces from any file, such as an export from another machine.
+    pub fn read_from(path: &Path) -> anyhow::Result<Workspaces> {
+        read_from_path(path)
+    }
+
+    pub fn write_to(&self, path: &Path) -> anyhow::Result<()> {
+        write_to_path(path, self)
+    }
+
+    /// Reloads the team and project workspaces alongside the personal ones. Sources are merged in
+    /// precedence order, personal then team then project, so the first source to have an id wins.
+    pub fn load_sources(&mut self, config: &LocalConfig) -> anyhow::Result<()> {
+        self.collect_local_state();
+        self.workspaces
+            .retain(|_, workspace| workspace.source == WorkspaceSource::Personal);
+        self.catalogue = config.catalogue.clone();
+        self.loaded_team = None;
+        self.shadowed_team.clear();
+        self.loaded_projects.clear();
+
+        let catalogue_result = match config.catalogue.as_ref() {
+            Some(catalogue) => catalogue.read().map(|shared| {
+                let mut loaded_team = HashMap::new();
+                for (uuid, workspace) in shared.workspaces {
+                    let mut workspace = workspace.shared();
+                    workspace.source = WorkspaceSource::Team;
+                    loaded_team.insert(uuid, workspace.clone());
+                    match self.workspaces.contains_key(&uuid) {
+                        true => self.shadowed_team.insert(uuid, workspace),
+                        false => self.workspaces.insert(uuid, workspace),
+                    };
+                }
+                self.loaded_team = Some(loaded_team);
+            }),
+            None => Ok(()),
+        };
+
+        let mut project_repos: BTreeSet<PathBuf> = config.project_repos.iter().cloned().collect();
+        project_repos.extend(
+            self.workspaces
+                .values()
+                .flat_map(|workspace| workspace.repo_paths.iter())
+                .map(|repo_path| PathBuf::from(config.roots.expand(repo_path))),
+        );
+
+        for repo_path in project_repos.iter().filter(|path| has_project_file(path)) {
+            match read_project_workspace(repo_path) {
+                Ok(workspace) if !self.workspaces.contains_key(&workspace.uuid) => {
+                    self.loaded_projects
+                        .insert(workspace.uuid, workspace.clone());
+                    self.workspaces.insert(workspace.uuid, workspace);
+                }
+                Ok(_) => {}
+                Err(why) => warn!(
+                    "Couldn't read project workspace in {}. '{}'",
+                    repo_path.display(),
+                    why
+                ),
+            }
+        }
+
+        for workspace in self.workspaces.values_mut() {
+            if let Some(local_state) = self.local_state.get(&workspace.uuid) {
+                workspace.apply_local_state(local_state.clone());
+            }
+        }
+
+        // Sources are exported when they change here, not whenever they're loaded.
+        for workspace in self.workspaces.values() {
+            if workspace.source != WorkspaceSource::Personal {
+                self.exported.insert(workspace.uuid, workspace.clone());
+            }
+        }
+
+        catalogue_result
+    }
+
+    /// The workspaces from one source, sorted for display.
+    pub fn by_source(&self, source: &WorkspaceSource) -> Vec<&Workspace> {
+        let mut workspaces: Vec<_> = self
+            .workspaces
+            .values()
+            .filter(|workspace| workspace.source == *source)
+            .collect();
+        workspaces.sort();
+        workspaces
+    }
+
+    /// Every tag used by any workspace, in order.
+    pub fn tags(&self) -> Vec<String> {
+        let tags: BTreeSet<_> = self
+            .workspaces
+            .values()
+            .flat_map(|workspace| workspace.tags.iter().cloned())
+            .collect();
+        tags.into_iter().collect()
+    }
+
+    /// The distinct sources of the loaded workspaces, in precedence order.
+    pub fn sources(&self) -> Vec<WorkspaceSource> {
+        let sources: BTreeSet<_> = self
+            .workspaces
+            .values()
+            .map(|workspace| workspace.source.clone())
+            .collect();
+        sources.into_iter().collect()
+    }
+
+    /// Copies a workspace into the personal workspaces under a new id.
+    pub fn copy_to_personal(&mut self, uuid: Uuid) -> Option<Uuid> {
+        let mut copy = self.workspaces.get(&uuid)?.clone();
+        copy.uuid = Uuid::new_v4();
+        copy.source = WorkspaceSource::Personal;
+        let copy_uuid = copy.uuid;
+        self.workspaces.insert(copy_uuid, copy);
+        Some(copy_uuid)
+    }
+
+    /// Whether edits to the workspace can be saved.
+    pub fn is_editable(&self, workspace: &Workspace) -> bool {
+        match workspace.source {
+            WorkspaceSource::Personal | WorkspaceSource::Project(_) => true,
+            WorkspaceSource::Team => {
+                self.loaded_team.is_some() && self.catalogue.as_ref().is_some_and(|c| !c.read_only)
+            }
+        }
+    }
+
+    /// Takes the local state of the loaded team and project workspaces, keeping what's there for
+    /// ones that aren't loaded right now.
+    fn collect_local_state(&mut self) {
+        for workspace in self.workspaces.values() {
+            if workspace.source == WorkspaceSource::Personal {
+                continue;
+            }
+
+            let local_state = workspace.local_state();
+            match local_state.is_empty() {
+                true => self.local_state.remove(&workspace.uuid),
+                false => self.local_state.insert(workspace.uuid, local_state),
+            };
+        }
+    }
+
+    fn only_from_source(&self, source: &WorkspaceSource) -> Workspaces {
+        let current_workspace = match source {
+            WorkspaceSource::Personal => self.current_workspace,
+            _ => Uuid::nil(),
+        };
+
+        Workspaces {
+            current_workspace,
+            workspaces: self
+                .workspaces
+                .iter()
+                .filter(|(_, workspace)| workspace.source == *source)
+                .map(|(uuid, workspace)| (*uuid, workspace.clone()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds the other workspaces, replacing any with the same id.
+    pub fn merge(&mut self, other: Workspaces) {
+        self.workspaces.extend(other.workspaces);
+        self.force_valid_workspace();
+    }
+
+    /// The workspaces kept in `st-workspaces.json`.
+    pub fn personal(&self) -> HashMap<Uuid, Workspace> {
+        self.only_from_source(&WorkspaceSource::Personal).workspaces
+    }
+
+    /// Reads just `st-workspaces.json`, without the team or project workspaces.
+    pub fn read_personal() -> anyhow::Result<HashMap<Uuid, Workspace>> {
+        let path = Workspaces::path().ok_or(Error::PathNotFound("workspaces file"))?;
+
+        match read_from_path(&path) {
+            Ok(workspaces) => Ok(workspaces.workspaces),
+            Err(why) if is_not_found(&why) => Ok(HashMap::new()),
+            Err(why) => Err(why),
+        }
+    }
+
+    pub fn replace_personal(&mut self, personal: HashMap<Uuid, Workspace>) {
+        self.workspaces
+            .retain(|_, workspace| workspace.source != WorkspaceSource::Personal);
+        self.workspaces.extend(personal);
+        self.force_valid_workspace();
+    }
+
+    /// Just the given workspaces, such as for exporting them.
+    pub fn subset(&self, uuids: &[Uuid]) -> Workspaces {
+        Workspaces {
+            current_workspace: Uuid::nil(),
+            workspaces: uuids
+                .iter()
+                .filter_map(|uuid| self.workspaces.get(uuid))
+                .map(|workspace| (workspace.uuid, workspace.clone()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Makes a personal workspace with the repos and rules of all the given ones, in order.
+    pub fn merge_into_new(&mut self, uuids: &[Uuid]) -> Option<Uuid> {
+        let merging: Vec<&Workspace> = uuids
+            .iter()
+            .filter_map(|uuid| self.workspaces.get(uuid))
+            .collect();
+        if merging.is_empty() {
+            return None;
+        }
+
+        let name = merging
+            .iter()
+            .map(|workspace| workspace.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" + ");
+        let mut merged = Workspace::new(&name, Uuid::new_v4());
+        for workspace in merging {
+            for repo_path in workspace.repo_paths.iter() {
+                if !merged.repo_paths.contains(repo_path) {
+                    merged.repo_paths.push(repo_path.clone());
+                }
+            }
+            for rule in workspace.rules.iter() {
+                if !merged.rules.contains(rule) {
+                    merged.rules.push(rule.clone());
+                }
+            }
+        }
+
+        let uuid = merged.uuid;
+        self.workspaces.insert(uuid, merged);
+        Some(uuid)
+    }
+}
+
+/// Where a workspace was loaded from, and so where edits to it are written. Variants are ordered
+/// by precedence.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WorkspaceSource {
+    /// The personal `st-workspaces.json`.
+    #[default]
+    Personal,
+    /// The team's shared catalogue.
+    Team,
+    /// A `.st-workspace` committed in the given repo.
+    Project(PathBuf),
+}
+
+impl std::fmt::Display for WorkspaceSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkspaceSource::Personal => write!(f, "{}", tr!("source-personal")),
+            WorkspaceSource::Team => write!(f, "{}", tr!("source-team")),
+            WorkspaceSource::Project(repo_path) => {
+                let name = repo_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy())
+                    .unwrap_or_else(|| repo_path.to_string_lossy());
+                write!(f, "{}", tr!("source-project", name = name.into_owned()))
+            }
+        }
+    }
+}
+
+/// The tag that hides a workspace without removing it.
+pub const ARCHIVED_TAG: &str = "archived";
+
+/// Tags are lower case with dashes for spaces, so `Client A` and `client-a` are the same tag.
+/// `None` for a blank tag.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase();
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// A tag for telling workspaces apart at a glance in the list.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceColor {
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+    Grey,
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+impl WorkspaceColor {
+    pub fn all() -> [WorkspaceColor; 7] {
+        [
+            WorkspaceColor::Red,
+            WorkspaceColor::Orange,
+            WorkspaceColor::Yellow,
+            WorkspaceColor::Green,
+            WorkspaceColor::Blue,
+            WorkspaceColor::Purple,
+            WorkspaceColor::Grey,
+        ]
+    }
+}
+
+impl std::fmt::Display for WorkspaceColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let key = match self {
+            WorkspaceColor::Red => "color-red",
+            WorkspaceColor::Orange => "color-orange",
+            WorkspaceColor::Yellow => "color-yellow",
+            WorkspaceColor::Green => "color-green",
+            WorkspaceColor::Blue => "color-blue",
+            WorkspaceColor::Purple => "color-purple",
+            WorkspaceColor::Grey => "color-grey",
+        };
+        write!(f, "{}", tr!(key))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Workspace {
     pub uuid: Uuid,
     pub name: String,
+    /// Markdown notes, such as what the workspace is for and links to its tickets.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<WorkspaceColor>,
+    /// An emoji shown before the name.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub icon: String,
+    /// Free-form labels such as `client-a` or `hotfix`, kept normalised by [`normalize_tag`].
+    #[serde(
+        default,
+        deserialize_with = "deserialize_tags",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub tags: Vec<String>,
     pub repo_paths: Vec<String>,
+    /// Rules that add repos when the workspace is opened, making it dynamic.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<WorkspaceRule>,
+    /// The branches checked out in the workspace's repos over time, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub snapshots: Vec<BranchSnapshot>,
+    /// Changes stashed when switching away, popped when the workspace is opened again.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pending_stashes: Vec<PendingStash>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<WorkspaceHook>,
+    /// Variables like `GIT_SSH_COMMAND` given to SourceTree and the hooks.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub environment: BTreeMap<String, String>,
+    /// What "Open Workspace" opens the repos in. Empty means just SourceTree.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub launchers: Vec<Launcher>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exports: Vec<IdeExport>,
+    #[serde(skip)]
+    pub source: WorkspaceSource,
 }
 
 impl Ord for Workspace {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.name.cmp(&other.name)
+        self.name
+            .cmp(&other.name)
+            .then_with(|| self.uuid.cmp(&other.uuid))
     }
 }
 
 impl PartialOrd for Workspace {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.name.partial_cmp(&other.name)
+        Some(self.cmp(other))
     }
 }
 
@@ -78,23 +617,175 @@ impl Workspace {
         Self {
             uuid,
             name: name.to_string(),
+            notes: Default::default(),
+            color: Default::default(),
+            icon: Default::default(),
+            tags: Default::default(),
             repo_paths: Default::default(),
+            rules: Default::default(),
+            snapshots: Default::default(),
+            pending_stashes: Default::default(),
+            hooks: Default::default(),
+            environment: Default::default(),
+            launchers: Default::default(),
+            exports: Default::default(),
+            source: WorkspaceSource::Personal,
         }
     }
+
+    /// The workspace's own launchers, or the preferred one when it has none.
+    pub fn default_launchers(&self, default_launcher: &Launcher) -> Vec<Launcher> {
+        match self.launchers.is_empty() {
+            true => vec![default_launcher.clone()],
+            false => self.launchers.clone(),
+        }
+    }
+
+    pub fn is_dynamic(&self) -> bool {
+        !self.rules.is_empty()
+    }
+
+    /// The icon, when there is one, then the name.
+    pub fn label(&self) -> String {
+        match self.icon.trim() {
+            "" => self.name.clone(),
+            icon => format!("{icon} {}", self.name),
+        }
+    }
+
+    /// Whether the name, notes, icon or a tag contain the filter, ignoring case. An empty filter
+    /// matches everything.
+    pub fn matches_filter(&self, filter: &str) -> bool {
+        let filter = filter.trim().to_lowercase();
+        [&self.name, &self.notes, &self.icon]
+            .into_iter()
+            .chain(self.tags.iter())
+            .any(|field| field.to_lowercase().contains(&filter))
+    }
+
+    /// The workspace without its snapshots, stashes and exports, as written to shared files.
+    pub fn shared(&self) -> Workspace {
+        Workspace {
+            snapshots: Vec::new(),
+            pending_stashes: Vec::new(),
+            exports: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    fn local_state(&self) -> LocalState {
+        LocalState {
+            snapshots: self.snapshots.clone(),
+            pending_stashes: self.pending_stashes.clone(),
+            exports: self.exports.clone(),
+        }
+    }
+
+    fn apply_local_state(&mut self, local_state: LocalState) {
+        self.snapshots = local_state.snapshots;
+        self.pending_stashes = local_state.pending_stashes;
+        self.exports = local_state.exports;
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        normalize_tag(tag).is_some_and(|tag| self.tags.contains(&tag))
+    }
+
+    /// Archived workspaces stay in the file but are hidden unless asked for.
+    pub fn is_archived(&self) -> bool {
+        self.has_tag(ARCHIVED_TAG)
+    }
+
+    /// Adds the tag unless it's blank or already there. Returns true when it was added.
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        match normalize_tag(tag) {
+            Some(tag) if !self.tags.contains(&tag) => {
+                self.tags.push(tag);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The web links in the notes, whether bare or written as markdown `[text](url)`.
+    pub fn note_links(&self) -> Vec<&str> {
+        let mut links = Vec::new();
+        for word in self
+            .notes
+            .split(|c: char| c.is_whitespace() || "()<>[]".contains(c))
+        {
+            let link = word.trim_end_matches(['.', ',', ';', ':', '!', '?']);
+            let is_link = link.starts_with("https://") || link.starts_with("http://");
+            if is_link && !links.contains(&link) {
+                links.push(link);
+            }
+        }
+        links
+    }
+
+    /// The paths to open on this machine: the stored repo paths with their roots expanded, then
+    /// whatever the rules currently pick out.
+    pub fn resolved_repo_paths(&self, roots: &PathRoots) -> Vec<String> {
+        let mut repo_paths: Vec<String> = self
+            .repo_paths
+            .iter()
+            .map(|repo_path| roots.expand(repo_path))
+            .collect();
+
+        for repo_path in evaluate_rules(&self.rules, roots) {
+            if !repo_paths.contains(&repo_path) {
+                repo_paths.push(repo_path);
+            }
+        }
+
+        repo_paths
+    }
+
+    /// Stores newly open tabs as the workspace's repos, leaving out those the rules already pick
+    /// out, so the rules stay in charge of them.
+    pub fn set_repo_paths_from_tabs(&mut self, tabs: &[String], roots: &PathRoots) {
+        let rule_paths = evaluate_rules(&self.rules, roots);
+        self.repo_paths = tabs
+            .iter()
+            .filter(|tab| !rule_paths.contains(tab))
+            .map(|tab| roots.contract(tab))
+            .collect();
+    }
 }
 
-fn write_to_path(path: &PathBuf, workspaces: &Workspaces) -> anyhow::Result<()> {
+fn write_to_path(path: &Path, workspaces: &Workspaces) -> anyhow::Result<()> {
     let contents = serde_json::to_string_pretty(&workspaces)?;
-    std::fs::write(path, contents)?;
+    write_file(path, contents)?;
     Ok(())
 }
 
-fn read_from_path(path: &PathBuf) -> anyhow::Result<Workspaces> {
-    let contents = std::fs::read_to_string(path)?;
-    let workspaces: Workspaces = serde_json::from_str(&contents)?;
+fn read_from_path(path: &Path) -> anyhow::Result<Workspaces> {
+    let contents = read_file(path)?;
+    let workspaces: Workspaces = parse_json(path, &contents)?;
     Ok(workspaces)
 }
 
+/// Writes workspaces sorted by id, so that shared files don't churn between saves.
+fn serialize_ordered<S: Serializer>(
+    workspaces: &HashMap<Uuid, Workspace>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let ordered: BTreeMap<_, _> = workspaces.iter().collect();
+    ordered.serialize(serializer)
+}
+
+/// Normalises tags as they're read, so hand-edited ones like `Client A` still match.
+fn deserialize_tags<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in Vec::<String>::deserialize(deserializer)? {
+        match normalize_tag(&tag) {
+            Some(tag) if !tags.contains(&tag) => tags.push(tag),
+            _ => {}
+        }
+    }
+    Ok(tags)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,6 +798,10 @@ mod tests {
     fn create_test_workspaces() -> Workspaces {
         let mut spaces = Workspaces::default();
         let mut space = Workspace::new("First Workspace", Uuid::new_v4());
+        space.notes = "For [TICKET-1](https://example.com/TICKET-1).".to_owned();
+        space.color = Some(WorkspaceColor::Blue);
+        space.icon = "🚀".to_owned();
+        space.tags = vec!["client-a".to_owned(), ARCHIVED_TAG.to_owned()];
         space.repo_paths.push("C:\\fake\\path0".to_owned());
         space.repo_paths.push("C:\\fake\\path1".to_owned());
         space.repo_paths.push("C:\\fake\\path2".to_owned());
@@ -144,6 +839,145 @@ mod tests {
         }
     }
 
+    #[test]
+    fn should_keep_team_workspaces_out_of_personal_file() {
+        let mut spaces = create_test_workspaces();
+        let mut team_space = Workspace::new("Team Workspace", Uuid::new_v4());
+        team_space.source = WorkspaceSource::Team;
+        spaces
+            .workspaces
+            .insert(team_space.uuid, team_space.clone());
+
+        let personal = spaces.only_from_source(&WorkspaceSource::Personal);
+        assert_eq!(personal.workspaces.len(), 2);
+        assert!(!personal.workspaces.contains_key(&team_space.uuid));
+
+        let copy_uuid = spaces.copy_to_personal(team_space.uuid).unwrap();
+        assert_eq!(spaces.workspaces[&copy_uuid].name, team_space.name);
+        assert_eq!(
+            spaces.workspaces[&copy_uuid].source,
+            WorkspaceSource::Personal
+        );
+    }
+
+    #[test]
+    fn should_load_project_workspaces_below_personal() -> anyhow::Result<()> {
+        let repo_path =
+            std::env::temp_dir().join(format!("st-workspaces-sources-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&repo_path)?;
+        std::fs::write(
+            repo_path.join(".st-workspace"),
+            r#"{ "name": "Project", "repo_paths": ["."] }"#,
+        )?;
+
+        let mut spaces = create_test_workspaces();
+        let config = LocalConfig {
+            project_repos: vec![repo_path.clone()],
+            ..Default::default()
+        };
+        spaces.load_sources(&config)?;
+
+        let project_source = WorkspaceSource::Project(repo_path);
+        assert_eq!(spaces.by_source(&project_source).len(), 1);
+        assert_eq!(spaces.by_source(&WorkspaceSource::Personal).len(), 2);
+        assert_eq!(
+            spaces.sources(),
+            vec![WorkspaceSource::Personal, project_source]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_keep_team_catalogue_safe() -> anyhow::Result<()> {
+        let catalogue_path =
+            std::env::temp_dir().join(format!("st-workspaces-team-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&catalogue_path)?;
+        let catalogue = Catalogue::new(&catalogue_path, false);
+        let config = LocalConfig {
+            catalogue: Some(catalogue.clone()),
+            ..Default::default()
+        };
+
+        std::fs::write(catalogue.file_path(), "<<<<<<< HEAD")?;
+        let mut spaces = create_test_workspaces();
+        assert!(spaces.load_sources(&config).is_err());
+        spaces.write_catalogue(&catalogue)?;
+        assert_eq!(
+            std::fs::read_to_string(catalogue.file_path())?,
+            "<<<<<<< HEAD"
+        );
+
+        let mut team = Workspaces::default();
+        let personal_uuid = spaces.by_source(&WorkspaceSource::Personal)[0].uuid;
+        for uuid in [personal_uuid, Uuid::new_v4()] {
+            team.workspaces
+                .insert(uuid, Workspace::new("Team Workspace", uuid));
+        }
+        catalogue.write(&team)?;
+        spaces.load_sources(&config)?;
+        assert_eq!(spaces.workspaces[&personal_uuid].name, "First Workspace");
+
+        let team_uuid = spaces.by_source(&WorkspaceSource::Team)[0].uuid;
+        spaces.workspaces.get_mut(&team_uuid).unwrap().name = "Renamed".to_owned();
+        spaces.write_catalogue(&catalogue)?;
+
+        let written = catalogue.read()?;
+        assert_eq!(written.workspaces.len(), 2);
+        assert_eq!(written.workspaces[&team_uuid].name, "Renamed");
+        assert_eq!(written.workspaces[&personal_uuid].name, "Team Workspace");
+        Ok(())
+    }
+
+    #[test]
+    fn should_keep_local_state_of_team_workspaces_local() -> anyhow::Result<()> {
+        let catalogue_path =
+            std::env::temp_dir().join(format!("st-workspaces-team-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&catalogue_path)?;
+        let catalogue = Catalogue::new(&catalogue_path, false);
+        let config = LocalConfig {
+            catalogue: Some(catalogue.clone()),
+            ..Default::default()
+        };
+
+        let team_uuid = Uuid::new_v4();
+        let mut team = Workspaces::default();
+        let mut team_workspace = Workspace::new("Team Workspace", team_uuid);
+        team_workspace.snapshots.push(BranchSnapshot {
+            taken_at: 1,
+            label: "from the catalogue".to_owned(),
+            repos: Vec::new(),
+        });
+        team.workspaces.insert(team_uuid, team_workspace);
+        catalogue.write(&team)?;
+
+        let mut spaces = create_test_workspaces();
+        spaces.load_sources(&config)?;
+        assert!(spaces.workspaces[&team_uuid].snapshots.is_empty());
+
+        let snapshot = BranchSnapshot {
+            taken_at: 2,
+            label: "here".to_owned(),
+            repos: Vec::new(),
+        };
+        spaces.workspaces.get_mut(&team_uuid).unwrap().snapshots = vec![snapshot.clone()];
+        spaces.write_catalogue(&catalogue)?;
+        spaces.collect_local_state();
+        // Unchanged, since only the local state differs.
+        let written = catalogue.read()?;
+        assert_eq!(
+            written.workspaces[&team_uuid].snapshots[0].label,
+            "from the catalogue"
+        );
+        assert_eq!(
+            spaces.local_state[&team_uuid].snapshots,
+            vec![snapshot.clone()]
+        );
+
+        spaces.load_sources(&config)?;
+        assert_eq!(spaces.workspaces[&team_uuid].snapshots, vec![snapshot]);
+        Ok(())
+    }
+
     #[test]
     fn should_persist_workspace() -> anyhow::Result<()> {
         let spaces = create_test_workspaces();
@@ -158,4 +992,110 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn should_fall_back_to_first_workspace_by_name() {
+        let mut spaces = create_test_workspaces();
+        spaces.current_workspace = Uuid::new_v4();
+
+        spaces.force_valid_workspace();
+
+        assert_eq!(spaces.current_workspace().unwrap().name, "First Workspace");
+    }
+
+    #[test]
+    fn should_merge_into_new_workspace() {
+        let mut spaces = create_test_workspaces();
+        let mut uuids: Vec<Uuid> = spaces
+            .by_source(&WorkspaceSource::Personal)
+            .iter()
+            .map(|workspace| workspace.uuid)
+            .collect();
+        spaces.workspaces.get_mut(&uuids[1]).unwrap().repo_paths[0] = "C:\\fake\\path0".to_owned();
+        uuids.reverse();
+
+        let uuid = spaces.merge_into_new(&uuids).unwrap();
+
+        let merged = &spaces.workspaces[&uuid];
+        assert_eq!(merged.name, "Second Workspace + First Workspace");
+        assert_eq!(merged.repo_paths.len(), 5);
+        assert_eq!(merged.repo_paths[0], "C:\\fake\\path0");
+        assert_eq!(spaces.workspaces.len(), 3);
+    }
+
+    #[test]
+    fn should_match_filter_on_name_notes_and_icon() {
+        let mut workspace = Workspace::new("Billing", Uuid::new_v4());
+        workspace.notes = "Fixes for the **invoice** run".to_owned();
+        workspace.icon = "💸".to_owned();
+
+        assert!(workspace.matches_filter(""));
+        assert!(workspace.matches_filter("bill"));
+        assert!(workspace.matches_filter("INVOICE"));
+        assert!(workspace.matches_filter("💸"));
+        assert!(!workspace.matches_filter("payroll"));
+        assert_eq!(workspace.label(), "💸 Billing");
+    }
+
+    #[test]
+    fn should_normalize_tags() {
+        let mut workspace = Workspace::new("Billing", Uuid::new_v4());
+
+        assert!(workspace.add_tag(" Client  A "));
+        assert!(!workspace.add_tag("client-a"));
+        assert!(!workspace.add_tag("   "));
+        assert_eq!(workspace.tags, vec!["client-a"]);
+        assert!(workspace.has_tag("CLIENT-A"));
+        assert!(workspace.matches_filter("client"));
+        assert!(!workspace.is_archived());
+
+        workspace.add_tag("Archived");
+        assert!(workspace.is_archived());
+    }
+
+    #[test]
+    fn should_normalize_tags_when_read() {
+        let workspace: Workspace = serde_json::from_str(
+            r#"{"uuid": "6f1f5ac4-9b1e-4a3e-8a4f-2f6a4f0b7c11", "name": "Billing",
+                "repo_paths": [], "tags": ["Client A", "client-a", " ", "Archived"]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(workspace.tags, vec!["client-a", "archived"]);
+        assert!(workspace.has_tag("client-a"));
+        assert!(workspace.is_archived());
+    }
+
+    #[test]
+    fn should_find_links_in_notes() {
+        let mut workspace = Workspace::new("Billing", Uuid::new_v4());
+        workspace.notes = "See [BILL-12](https://example.com/BILL-12) and \
+            https://example.com/wiki/billing. Also https://example.com/BILL-12"
+            .to_owned();
+
+        assert_eq!(
+            workspace.note_links(),
+            vec![
+                "https://example.com/BILL-12",
+                "https://example.com/wiki/billing"
+            ]
+        );
+    }
+
+    #[test]
+    fn should_keep_metadata_through_export() -> anyhow::Result<()> {
+        let spaces = create_test_workspaces();
+        let uuids: Vec<Uuid> = spaces.workspaces.keys().copied().collect();
+        let path =
+            std::env::temp_dir().join(format!("st-workspaces-export-{}.json", Uuid::new_v4()));
+
+        spaces.subset(&uuids).write_to(&path)?;
+        let imported = Workspaces::read_from(&path)?;
+        std::fs::remove_file(&path)?;
+
+        for (uuid, workspace) in spaces.workspaces.iter() {
+            assert_eq!(imported.workspaces[uuid], *workspace);
+        }
+        Ok(())
+    }
 }